@@ -3,9 +3,10 @@ use crate::benchmark::timer::time_execution_millis;
 use crate::board::{Board, BoardRepresentation};
 use crate::scheduling::CorePolicy;
 use crate::search::fault_tolerant::with_recovery;
-use crate::search::parallel::parallel_search_with_fault;
-use crate::search::parallel::parallel_search_with_policy;
-use crate::search::search;
+use crate::search::minimax::{SearchConfig, SharedSearch};
+use crate::search::parallel::{parallel_search_shared, parallel_search_with_mode, ParallelMode};
+use crate::search::search_shared;
+use crate::search::transposition::TTStats;
 use crate::types::Move;
 
 #[derive(Clone)]
@@ -17,6 +18,8 @@ pub struct BenchmarkConfig {
     pub core_policy: CorePolicy,
     pub mixed_ratio: f32, // 0.80 = 8P+2E M1 pro ratio
     pub inject_panic: Option<usize>,
+    pub search: SearchConfig,
+    pub parallel_mode: ParallelMode,
 }
 
 impl Default for BenchmarkConfig {
@@ -29,6 +32,8 @@ impl Default for BenchmarkConfig {
             core_policy: CorePolicy::None,
             mixed_ratio: 0.80,
             inject_panic: None,
+            search: SearchConfig::default(),
+            parallel_mode: ParallelMode::RootSplit,
         }
     }
 }
@@ -41,6 +46,10 @@ pub struct BenchmarkResult {
     pub speedup: f64,
     pub efficiency: f64,
     pub core_policy: CorePolicy,
+    pub parallel_mode: ParallelMode,
+    /// Transposition table counters summed over the measurement runs
+    pub tt_stats: TTStats,
+    pub nodes_per_search: f64,
 }
 
 pub fn run_benchmark_with_policy(config: &BenchmarkConfig) -> Vec<BenchmarkResult> {
@@ -50,6 +59,11 @@ pub fn run_benchmark_with_policy(config: &BenchmarkConfig) -> Vec<BenchmarkResul
     println!("  Warmup runs: {}", config.warmup_runs);
     println!("  Measurement runs: {}", config.measurement_runs);
     println!("  Thread configurations: {:?}", config.thread_counts);
+    println!("  Parallel mode: {:?}", config.parallel_mode);
+    println!("  Transposition table: {} MB", config.search.tt_size_mb);
+    println!("  Move ordering: {:?}", config.search.ordering);
+    println!("  Quiescence: {:?}", config.search.quiescence);
+    println!("  Selectivity: {:?}", config.search.selectivity);
 
     let mut results = Vec::new();
     let mut baseline_sps = 0.0;
@@ -57,12 +71,7 @@ pub fn run_benchmark_with_policy(config: &BenchmarkConfig) -> Vec<BenchmarkResul
     for &thread_count in &config.thread_counts {
         println!("\n--- Testing {} thread(s) ---", thread_count);
 
-        let stats = benchmark_thread_config_with_policy(
-            thread_count,
-            config,
-            config.core_policy,
-            config.mixed_ratio,
-        );
+        let (stats, tt_stats, nodes) = benchmark_thread_config_with_policy(thread_count, config);
         let sps = stats.searches_per_second();
 
         if thread_count == 1 || (thread_count == config.thread_counts[0]) {
@@ -75,6 +84,7 @@ pub fn run_benchmark_with_policy(config: &BenchmarkConfig) -> Vec<BenchmarkResul
             1.0
         };
         let efficiency = speedup / thread_count as f64 * 100.0;
+        let nodes_per_search = nodes as f64 / config.measurement_runs.max(1) as f64;
 
         println!(
             "  Median: {:.3}ms (std dev: {:.3}ms)",
@@ -84,6 +94,21 @@ pub fn run_benchmark_with_policy(config: &BenchmarkConfig) -> Vec<BenchmarkResul
         println!("  Searches/second: {:.2}", sps);
         println!("  Speedup: {:.2}x", speedup);
         println!("  Efficiency: {:.1}%", efficiency);
+        println!("  Nodes/search: {:.0}", nodes_per_search);
+        if uses_root_bound(thread_count, config) {
+            let without = nodes_without_root_bound(thread_count, config) as f64;
+            println!(
+                "  Root bound: {:.0} nodes/search without it, {:.1}% saved",
+                without,
+                (1.0 - nodes_per_search / without) * 100.0
+            );
+        }
+        println!(
+            "  TT: {} probes, {:.1}% hits, {} collisions",
+            tt_stats.probes,
+            tt_stats.hit_rate() * 100.0,
+            tt_stats.collisions
+        );
         results.push(BenchmarkResult {
             thread_count,
             stats,
@@ -91,6 +116,9 @@ pub fn run_benchmark_with_policy(config: &BenchmarkConfig) -> Vec<BenchmarkResul
             speedup,
             efficiency,
             core_policy: config.core_policy,
+            parallel_mode: config.parallel_mode,
+            tt_stats,
+            nodes_per_search,
         });
     }
 
@@ -107,89 +135,107 @@ pub fn run_benchmark(config: &BenchmarkConfig) -> Vec<BenchmarkResult> {
 fn benchmark_thread_config_with_policy(
     thread_count: usize,
     config: &BenchmarkConfig,
-    policy: CorePolicy,
-    mixed_ratio: f32,
-) -> BenchmarkStats {
+) -> (BenchmarkStats, TTStats, u64) {
     let mut board = Board::new();
 
     // Warmup phase
     println!("  Warming up...");
     for _ in 0..config.warmup_runs {
         board.setup_starting_position();
-        let _ = execute_search_with_policy(
-            &mut board,
-            config.depth,
-            thread_count,
-            policy,
-            mixed_ratio,
-            None,
-        ); // No panic injection during warmup.
+        let shared = SharedSearch::new(config.search);
+        let _ = execute_search_with_policy(&mut board, thread_count, config, None, &shared); // No panic injection during warmup.
     }
 
     // Measurement phase
     println!("  Measuring...");
     let mut samples = Vec::new();
+    let mut tt_stats = TTStats::default();
+    let mut nodes = 0;
 
     for run in 1..=config.measurement_runs {
         board.setup_starting_position();
+        // Fresh table per run so every sample starts cold; allocation isn't timed
+        let shared = SharedSearch::new(config.search);
 
         let (_, duration_ms) = time_execution_millis(|| {
-            execute_search_with_policy(
-                &mut board,
-                config.depth,
-                thread_count,
-                policy,
-                mixed_ratio,
-                config.inject_panic,
-            ) // Inject panic during measurement.
+            execute_search_with_policy(&mut board, thread_count, config, config.inject_panic, &shared) // Inject panic during measurement.
         });
 
         samples.push(duration_ms);
+        tt_stats.add(&shared.tt_stats());
+        nodes += shared.nodes();
         println!("    Run {:2}: {:.3}ms", run, duration_ms);
     }
 
-    BenchmarkStats::from_samples(&samples)
+    (BenchmarkStats::from_samples(&samples), tt_stats, nodes)
+}
+
+// Whether the measured searches had root workers sharing the best root score
+fn uses_root_bound(thread_count: usize, config: &BenchmarkConfig) -> bool {
+    thread_count > 1
+        && config.search.root_bound
+        && matches!(config.parallel_mode, ParallelMode::RootSplit | ParallelMode::TwoPhase)
+}
+
+// Nodes of one unmeasured search as configured but with the root bound off
+fn nodes_without_root_bound(thread_count: usize, config: &BenchmarkConfig) -> u64 {
+    let config = BenchmarkConfig {
+        search: SearchConfig {
+            root_bound: false,
+            ..config.search
+        },
+        ..config.clone()
+    };
+    let mut board = Board::new();
+    board.setup_starting_position();
+    let shared = SharedSearch::new(config.search);
+    execute_search_with_policy(&mut board, thread_count, &config, None, &shared);
+    shared.nodes()
 }
 
 fn execute_search_with_policy(
     board: &mut Board,
-    depth: u32,
     thread_count: usize,
-    policy: CorePolicy,
-    mixed_ratio: f32,
+    config: &BenchmarkConfig,
     inject_panic: Option<usize>,
+    shared: &SharedSearch,
 ) -> (Move, i32) {
+    let (depth, policy, mode, mixed_ratio) =
+        (config.depth, config.core_policy, config.parallel_mode, config.mixed_ratio);
     if inject_panic.is_some() {
         // Wrapper handles retry; search does real work before panic
         let search_fn = || {
             let mut b = board.clone();
             if thread_count == 1 {
-                search(&mut b, depth)
-            } else {
-                parallel_search_with_fault(
+                search_shared(&mut b, depth, shared)
+            } else if mode == ParallelMode::RootSplit {
+                parallel_search_shared(
                     &mut b,
                     depth,
                     policy,
                     thread_count,
                     mixed_ratio,
                     inject_panic,
+                    shared,
                 )
+            } else {
+                parallel_search_with_mode(&mut b, depth, mode, policy, thread_count, mixed_ratio, shared)
             }
         };
         with_recovery(search_fn, inject_panic)
     } else {
         if thread_count == 1 {
-            search(board, depth)
+            search_shared(board, depth, shared)
         } else {
-            parallel_search_with_policy(board, depth, policy, thread_count, mixed_ratio)
+            parallel_search_with_mode(board, depth, mode, policy, thread_count, mixed_ratio, shared)
         }
     }
 }
 
 fn print_summary_with_policy(results: &[BenchmarkResult]) {
     println!("\n=== PERFORMANCE SUMMARY ===");
-    println!("Threads |      Policy     | Median Time | Searches/sec | Speedup | Efficiency");
-    println!("--------|-----------------|-------------|--------------|---------|-----------");
+    println!("Threads |      Policy     | Median Time | Searches/sec | Speedup | Efficiency | TT hits");
+    println!("--------|-----------------|-------------|--------------|---------|------------|--------");
 
     for result in results {
         let policy_name = match result.core_policy {
@@ -199,13 +245,14 @@ fn print_summary_with_policy(results: &[BenchmarkResult]) {
             CorePolicy::Mixed => "Mixed",
         };
         println!(
-            "{:7} | {:11} |{:10.3}ms | {:12.2} | {:7.2}x | {:9.1}%",
+            "{:7} | {:11} |{:10.3}ms | {:12.2} | {:7.2}x | {:9.1}% | {:6.1}%",
             result.thread_count,
             policy_name,
             result.stats.median,
             result.searches_per_second,
             result.speedup,
-            result.efficiency
+            result.efficiency,
+            result.tt_stats.hit_rate() * 100.0
         );
     }
 
@@ -1,4 +1,6 @@
+use super::position::{place_starting_pieces, read_fen, write_fen};
 use super::traits::{BoardRepresentation, UndoMove};
+use super::zobrist;
 use crate::types::*;
 use crate::types::{BK, BQ, WK, WQ};
 use serde::{Deserialize, Serialize};
@@ -13,18 +15,24 @@ pub struct ArrayBoard {
     en_passant: Option<Square>,
     halfmove_clock: u8,
     fullmove_clock: u16,
+    hash: u64,
+    pawn_key: u64,
 }
 
 impl ArrayBoard {
     pub fn new() -> Self {
-        ArrayBoard {
+        let mut board = ArrayBoard {
             squares: [None; 64],
             to_move: Color::White,
             castling_rights: 0b1111,
             en_passant: None,
             halfmove_clock: 0,
             fullmove_clock: 1,
-        }
+            hash: 0,
+            pawn_key: 0,
+        };
+        board.hash = zobrist::compute_hash(&board);
+        board
     }
 }
 
@@ -34,6 +42,14 @@ impl BoardRepresentation for ArrayBoard {
     }
 
     fn set_piece(&mut self, square: Square, piece: Option<Piece>) {
+        if let Some(old) = self.squares[square.0 as usize] {
+            self.hash ^= zobrist::piece_key(old, square);
+            self.pawn_key ^= zobrist::pawn_piece_key(old, square);
+        }
+        if let Some(new) = piece {
+            self.hash ^= zobrist::piece_key(new, square);
+            self.pawn_key ^= zobrist::pawn_piece_key(new, square);
+        }
         self.squares[square.0 as usize] = piece;
     }
 
@@ -46,6 +62,9 @@ impl BoardRepresentation for ArrayBoard {
     }
 
     fn set_to_move(&mut self, color: Color) {
+        if color != self.to_move {
+            self.hash ^= zobrist::side_key();
+        }
         self.to_move = color;
     }
 
@@ -54,6 +73,7 @@ impl BoardRepresentation for ArrayBoard {
     }
 
     fn set_en_passant(&mut self, square: Option<Square>) {
+        self.hash ^= zobrist::en_passant_key(self.en_passant) ^ zobrist::en_passant_key(square);
         self.en_passant = square;
     }
 
@@ -62,6 +82,7 @@ impl BoardRepresentation for ArrayBoard {
     }
 
     fn set_castling_rights(&mut self, rights: u8) {
+        self.hash ^= zobrist::castling_key(self.castling_rights) ^ zobrist::castling_key(rights);
         self.castling_rights = rights;
     }
 
@@ -88,51 +109,16 @@ impl BoardRepresentation for ArrayBoard {
         self.en_passant = None;
         self.halfmove_clock = 0;
         self.fullmove_clock = 1;
+        self.hash = zobrist::compute_hash(self);
+        self.pawn_key = 0;
 
-        self.set_piece(Square(0), Some(Piece::new(PieceType::Rook, Color::White))); //a1
-        self.set_piece(Square(1), Some(Piece::new(PieceType::Knight, Color::White))); //b1
-        self.set_piece(Square(2), Some(Piece::new(PieceType::Bishop, Color::White))); //c1
-        self.set_piece(Square(3), Some(Piece::new(PieceType::Queen, Color::White))); //d1
-        self.set_piece(Square(4), Some(Piece::new(PieceType::King, Color::White))); //e1
-        self.set_piece(Square(5), Some(Piece::new(PieceType::Bishop, Color::White))); //f1
-        self.set_piece(Square(6), Some(Piece::new(PieceType::Knight, Color::White))); //g1
-        self.set_piece(Square(7), Some(Piece::new(PieceType::Rook, Color::White))); //h1
-
-        for i in 8..16 {
-            // 2
-            self.set_piece(Square(i), Some(Piece::new(PieceType::Pawn, Color::White)));
-        }
-
-        for i in 48..56 {
-            // 7
-            self.set_piece(Square(i), Some(Piece::new(PieceType::Pawn, Color::Black)));
-        }
-
-        self.set_piece(Square(56), Some(Piece::new(PieceType::Rook, Color::Black))); //a8
-        self.set_piece(
-            Square(57),
-            Some(Piece::new(PieceType::Knight, Color::Black)),
-        ); //b8
-        self.set_piece(
-            Square(58),
-            Some(Piece::new(PieceType::Bishop, Color::Black)),
-        ); //c8
-        self.set_piece(Square(59), Some(Piece::new(PieceType::Queen, Color::Black))); //d8
-        self.set_piece(Square(60), Some(Piece::new(PieceType::King, Color::Black))); //e8
-        self.set_piece(
-            Square(61),
-            Some(Piece::new(PieceType::Bishop, Color::Black)),
-        ); //f8
-        self.set_piece(
-            Square(62),
-            Some(Piece::new(PieceType::Knight, Color::Black)),
-        ); //g8
-        self.set_piece(Square(63), Some(Piece::new(PieceType::Rook, Color::Black)));
-        //h8
+        place_starting_pieces(self);
     }
 
     fn clear(&mut self) {
         self.squares = [None; 64];
+        self.hash = zobrist::compute_hash(self);
+        self.pawn_key = 0;
     }
 
     fn make_move(&mut self, _mv: &Move) -> UndoMove {
@@ -253,6 +239,16 @@ impl BoardRepresentation for ArrayBoard {
             self.fullmove_clock += 1;
         }
 
+        // Pieces were hashed by set_piece; fold in the state changes.
+        // Drift introduced by unmake_move is caught here on the next move.
+        self.hash ^= zobrist::side_key()
+            ^ zobrist::castling_key(undo.previous_castling_rights)
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ zobrist::en_passant_key(undo.previous_en_passant)
+            ^ zobrist::en_passant_key(self.en_passant);
+        debug_assert_eq!(self.hash, zobrist::compute_hash(self), "hash drift after {}", _mv);
+        debug_assert_eq!(self.pawn_key, zobrist::compute_pawn_key(self), "pawn key drift after {}", _mv);
+
         undo
     }
 
@@ -305,6 +301,12 @@ impl BoardRepresentation for ArrayBoard {
             }
         }
 
+        self.hash ^= zobrist::side_key()
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ zobrist::castling_key(_undo.previous_castling_rights)
+            ^ zobrist::en_passant_key(self.en_passant)
+            ^ zobrist::en_passant_key(_undo.previous_en_passant);
+
         self.to_move = _undo.previous_to_move;
         self.en_passant = _undo.previous_en_passant;
         self.castling_rights = _undo.previous_castling_rights;
@@ -708,175 +710,25 @@ impl BoardRepresentation for ArrayBoard {
         count
     }
 
+    fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn pawn_key(&self) -> u64 {
+        self.pawn_key
+    }
+
     fn to_fen(&self) -> String {
-        let mut fen = String::new();
-        
-        // Piece placement (from rank 8 to rank 1)
-        for rank in (0..8).rev() {
-            let mut empty_count = 0;
-            for file in 0..8 {
-                let square = Square(rank * 8 + file);
-                if let Some(piece) = self.get_piece(square) {
-                    if empty_count > 0 {
-                        fen.push_str(&empty_count.to_string());
-                        empty_count = 0;
-                    }
-                    let c = match (piece.piece_type, piece.color) {
-                        (PieceType::Pawn, Color::White) => 'P',
-                        (PieceType::Knight, Color::White) => 'N',
-                        (PieceType::Bishop, Color::White) => 'B',
-                        (PieceType::Rook, Color::White) => 'R',
-                        (PieceType::Queen, Color::White) => 'Q',
-                        (PieceType::King, Color::White) => 'K',
-                        (PieceType::Pawn, Color::Black) => 'p',
-                        (PieceType::Knight, Color::Black) => 'n',
-                        (PieceType::Bishop, Color::Black) => 'b',
-                        (PieceType::Rook, Color::Black) => 'r',
-                        (PieceType::Queen, Color::Black) => 'q',
-                        (PieceType::King, Color::Black) => 'k',
-                    };
-                    fen.push(c);
-                } else {
-                    empty_count += 1;
-                }
-            }
-            if empty_count > 0 {
-                fen.push_str(&empty_count.to_string());
-            }
-            if rank > 0 {
-                fen.push('/');
-            }
-        }
-        
-        // Side to move
-        fen.push(' ');
-        fen.push(match self.to_move {
-            Color::White => 'w',
-            Color::Black => 'b',
-        });
-        
-        // Castling rights
-        fen.push(' ');
-        if self.castling_rights == 0 {
-            fen.push('-');
-        } else {
-            if self.castling_rights & WK != 0 { fen.push('K'); }
-            if self.castling_rights & WQ != 0 { fen.push('Q'); }
-            if self.castling_rights & BK != 0 { fen.push('k'); }
-            if self.castling_rights & BQ != 0 { fen.push('q'); }
-        }
-        
-        // En passant
-        fen.push(' ');
-        if let Some(ep) = self.en_passant {
-            fen.push_str(&ep.to_alg());
-        } else {
-            fen.push('-');
-        }
-        
-        // Halfmove clock and fullmove number
-        fen.push_str(&format!(" {} {}", self.halfmove_clock, self.fullmove_clock));
-        
-        fen
+        write_fen(self)
     }
-    
+
     fn from_fen(fen: &str) -> Result<Self, String>
     where
         Self: Sized,
     {
-        let parts: Vec<&str> = fen.split_whitespace().collect();
-        if parts.len() < 4 {
-            return Err("FEN must have at least 4 parts".to_string());
-        }
-        
         let mut board = ArrayBoard::new();
         board.clear();
-        
-        // Parse piece placement
-        let ranks: Vec<&str> = parts[0].split('/').collect();
-        if ranks.len() != 8 {
-            return Err("FEN must have 8 ranks".to_string());
-        }
-        
-        for (rank_idx, rank_str) in ranks.iter().enumerate() {
-            let rank = 7 - rank_idx; // FEN starts from rank 8
-            let mut file = 0u8;
-            
-            for c in rank_str.chars() {
-                if file >= 8 {
-                    return Err(format!("Too many squares in rank {}", rank + 1));
-                }
-                
-                if let Some(digit) = c.to_digit(10) {
-                    file += digit as u8;
-                } else {
-                    let (piece_type, color) = match c {
-                        'P' => (PieceType::Pawn, Color::White),
-                        'N' => (PieceType::Knight, Color::White),
-                        'B' => (PieceType::Bishop, Color::White),
-                        'R' => (PieceType::Rook, Color::White),
-                        'Q' => (PieceType::Queen, Color::White),
-                        'K' => (PieceType::King, Color::White),
-                        'p' => (PieceType::Pawn, Color::Black),
-                        'n' => (PieceType::Knight, Color::Black),
-                        'b' => (PieceType::Bishop, Color::Black),
-                        'r' => (PieceType::Rook, Color::Black),
-                        'q' => (PieceType::Queen, Color::Black),
-                        'k' => (PieceType::King, Color::Black),
-                        _ => return Err(format!("Invalid piece character: {}", c)),
-                    };
-                    let square = Square(rank as u8 * 8 + file);
-                    board.set_piece(square, Some(Piece::new(piece_type, color)));
-                    file += 1;
-                }
-            }
-        }
-        
-        // Parse side to move
-        board.to_move = match parts[1] {
-            "w" => Color::White,
-            "b" => Color::Black,
-            _ => return Err(format!("Invalid side to move: {}", parts[1])),
-        };
-        
-        // Parse castling rights
-        board.castling_rights = 0;
-        if parts[2] != "-" {
-            for c in parts[2].chars() {
-                match c {
-                    'K' => board.castling_rights |= WK,
-                    'Q' => board.castling_rights |= WQ,
-                    'k' => board.castling_rights |= BK,
-                    'q' => board.castling_rights |= BQ,
-                    _ => return Err(format!("Invalid castling character: {}", c)),
-                }
-            }
-        }
-        
-        // Parse en passant
-        if parts[3] != "-" {
-            let ep_chars: Vec<char> = parts[3].chars().collect();
-            if ep_chars.len() != 2 {
-                return Err(format!("Invalid en passant square: {}", parts[3]));
-            }
-            let file = ep_chars[0] as u8 - b'a';
-            let rank = ep_chars[1] as u8 - b'1';
-            if file > 7 || rank > 7 {
-                return Err(format!("Invalid en passant square: {}", parts[3]));
-            }
-            board.en_passant = Some(Square(rank * 8 + file));
-        }
-        
-        // Parse halfmove clock (optional)
-        if parts.len() > 4 {
-            board.halfmove_clock = parts[4].parse().unwrap_or(0);
-        }
-        
-        // Parse fullmove number (optional)
-        if parts.len() > 5 {
-            board.fullmove_clock = parts[5].parse().unwrap_or(1);
-        }
-        
+        read_fen(&mut board, fen)?;
         Ok(board)
     }
 }
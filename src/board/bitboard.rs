@@ -0,0 +1,490 @@
+use super::position::{place_starting_pieces, read_fen, write_fen};
+use super::traits::{BoardRepresentation, UndoMove};
+use super::zobrist;
+use crate::types::*;
+use crate::types::{BK, BQ, WK, WQ};
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+
+// Attack tables ================================
+
+const KNIGHT_DELTAS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_DELTAS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+const ROOK_DELTAS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DELTAS: [(i8, i8); 4] = [(1, 1), (-1, 1), (1, -1), (-1, -1)];
+
+const fn leaper_table(deltas: &[(i8, i8)]) -> [u64; 64] {
+    let mut table = [0u64; 64];
+    let mut sq = 0;
+    while sq < 64 {
+        let file = (sq % 8) as i8;
+        let rank = (sq / 8) as i8;
+        let mut i = 0;
+        while i < deltas.len() {
+            let f = file + deltas[i].0;
+            let r = rank + deltas[i].1;
+            if f >= 0 && f < 8 && r >= 0 && r < 8 {
+                table[sq] |= 1u64 << (r * 8 + f);
+            }
+            i += 1;
+        }
+        sq += 1;
+    }
+    table
+}
+
+/// Squares attacked by a pawn of each color standing on a square.
+const fn pawn_table(forward: i8) -> [u64; 64] {
+    leaper_table(&[(-1, forward), (1, forward)])
+}
+
+/// Full ray (excluding the origin) from every square in each slider direction.
+const fn ray_table(deltas: &[(i8, i8); 4]) -> [[u64; 64]; 4] {
+    let mut table = [[0u64; 64]; 4];
+    let mut dir = 0;
+    while dir < 4 {
+        let mut sq = 0;
+        while sq < 64 {
+            let mut f = (sq % 8) as i8 + deltas[dir].0;
+            let mut r = (sq / 8) as i8 + deltas[dir].1;
+            while f >= 0 && f < 8 && r >= 0 && r < 8 {
+                table[dir][sq] |= 1u64 << (r * 8 + f);
+                f += deltas[dir].0;
+                r += deltas[dir].1;
+            }
+            sq += 1;
+        }
+        dir += 1;
+    }
+    table
+}
+
+static KNIGHT_ATTACKS: [u64; 64] = leaper_table(&KNIGHT_DELTAS);
+static KING_ATTACKS: [u64; 64] = leaper_table(&KING_DELTAS);
+static PAWN_ATTACKS: [[u64; 64]; 2] = [pawn_table(1), pawn_table(-1)];
+static ROOK_RAYS: [[u64; 64]; 4] = ray_table(&ROOK_DELTAS);
+static BISHOP_RAYS: [[u64; 64]; 4] = ray_table(&BISHOP_DELTAS);
+
+/// Walk each ray up to and including its first blocker.
+/// Rays pointing towards higher squares find the blocker with the lowest set bit,
+/// rays pointing towards lower squares with the highest.
+fn slider_attacks(rays: &[[u64; 64]; 4], deltas: &[(i8, i8); 4], square: Square, occupied: u64) -> u64 {
+    let sq = square.0 as usize;
+    let mut attacks = 0;
+    for dir in 0..4 {
+        let ray = rays[dir][sq];
+        let blockers = ray & occupied;
+        if blockers == 0 {
+            attacks |= ray;
+            continue;
+        }
+        let (dx, dy) = deltas[dir];
+        let blocker = if dy > 0 || (dy == 0 && dx > 0) {
+            blockers.trailing_zeros() as usize
+        } else {
+            63 - blockers.leading_zeros() as usize
+        };
+        attacks |= ray & !rays[dir][blocker];
+    }
+    attacks
+}
+
+pub fn knight_attacks(square: Square) -> u64 {
+    KNIGHT_ATTACKS[square.0 as usize]
+}
+
+pub fn king_attacks(square: Square) -> u64 {
+    KING_ATTACKS[square.0 as usize]
+}
+
+pub fn pawn_attacks(color: Color, square: Square) -> u64 {
+    PAWN_ATTACKS[color_index(color)][square.0 as usize]
+}
+
+pub fn rook_attacks(square: Square, occupied: u64) -> u64 {
+    slider_attacks(&ROOK_RAYS, &ROOK_DELTAS, square, occupied)
+}
+
+pub fn bishop_attacks(square: Square, occupied: u64) -> u64 {
+    slider_attacks(&BISHOP_RAYS, &BISHOP_DELTAS, square, occupied)
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn piece_index(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    }
+}
+
+fn opposite(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
+
+// BitBoard ================================
+
+/// Piece-centric board: one 64-bit set per (color, piece type) plus a mailbox
+/// so `get_piece` stays O(1). Attack queries are answered with table lookups
+/// instead of square scans.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BitBoard {
+    pieces: [[u64; 6]; 2],
+    occupancy: [u64; 2],
+    #[serde(with = "BigArray")]
+    mailbox: [Option<Piece>; 64],
+    to_move: Color,
+    castling_rights: u8,
+    en_passant: Option<Square>,
+    halfmove_clock: u8,
+    fullmove_clock: u16,
+    hash: u64,
+    pawn_key: u64,
+}
+
+impl BitBoard {
+    pub fn new() -> Self {
+        let mut board = BitBoard {
+            pieces: [[0; 6]; 2],
+            occupancy: [0; 2],
+            mailbox: [None; 64],
+            to_move: Color::White,
+            castling_rights: 0b1111,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_clock: 1,
+            hash: 0,
+            pawn_key: 0,
+        };
+        board.hash = zobrist::compute_hash(&board);
+        board
+    }
+
+    /// Set of squares holding `piece_type` pieces of `color`.
+    pub fn pieces(&self, piece_type: PieceType, color: Color) -> u64 {
+        self.pieces[color_index(color)][piece_index(piece_type)]
+    }
+
+    /// Set of squares holding any piece of `color`.
+    pub fn occupancy(&self, color: Color) -> u64 {
+        self.occupancy[color_index(color)]
+    }
+
+    pub fn occupied(&self) -> u64 {
+        self.occupancy[0] | self.occupancy[1]
+    }
+
+    /// All pieces of `by_color` attacking `square` (kings excluded, matching `count_attackers`).
+    fn attackers_without_king(&self, square: Square, by_color: Color) -> u64 {
+        let c = color_index(by_color);
+        let occupied = self.occupied();
+        let queens = self.pieces[c][4];
+
+        (pawn_attacks(opposite(by_color), square) & self.pieces[c][0])
+            | (knight_attacks(square) & self.pieces[c][1])
+            | (bishop_attacks(square, occupied) & (self.pieces[c][2] | queens))
+            | (rook_attacks(square, occupied) & (self.pieces[c][3] | queens))
+    }
+}
+
+impl Default for BitBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardRepresentation for BitBoard {
+    fn get_piece(&self, square: Square) -> Option<Piece> {
+        self.mailbox[square.0 as usize]
+    }
+
+    fn set_piece(&mut self, square: Square, piece: Option<Piece>) {
+        let bit = 1u64 << square.0;
+        if let Some(old) = self.mailbox[square.0 as usize] {
+            self.pieces[color_index(old.color)][piece_index(old.piece_type)] &= !bit;
+            self.occupancy[color_index(old.color)] &= !bit;
+            self.hash ^= zobrist::piece_key(old, square);
+            self.pawn_key ^= zobrist::pawn_piece_key(old, square);
+        }
+        if let Some(new) = piece {
+            self.pieces[color_index(new.color)][piece_index(new.piece_type)] |= bit;
+            self.occupancy[color_index(new.color)] |= bit;
+            self.hash ^= zobrist::piece_key(new, square);
+            self.pawn_key ^= zobrist::pawn_piece_key(new, square);
+        }
+        self.mailbox[square.0 as usize] = piece;
+    }
+
+    fn is_empty(&self, square: Square) -> bool {
+        self.occupied() & (1u64 << square.0) == 0
+    }
+
+    fn to_move(&self) -> Color {
+        self.to_move
+    }
+
+    fn set_to_move(&mut self, color: Color) {
+        if color != self.to_move {
+            self.hash ^= zobrist::side_key();
+        }
+        self.to_move = color;
+    }
+
+    fn en_passant(&self) -> Option<Square> {
+        self.en_passant
+    }
+
+    fn set_en_passant(&mut self, square: Option<Square>) {
+        self.hash ^= zobrist::en_passant_key(self.en_passant) ^ zobrist::en_passant_key(square);
+        self.en_passant = square;
+    }
+
+    fn castling_rights(&self) -> u8 {
+        self.castling_rights
+    }
+
+    fn set_castling_rights(&mut self, rights: u8) {
+        self.hash ^= zobrist::castling_key(self.castling_rights) ^ zobrist::castling_key(rights);
+        self.castling_rights = rights;
+    }
+
+    fn halfmove_clock(&self) -> u8 {
+        self.halfmove_clock
+    }
+
+    fn set_halfmove_clock(&mut self, clock: u8) {
+        self.halfmove_clock = clock;
+    }
+
+    fn fullmove_clock(&self) -> u16 {
+        self.fullmove_clock
+    }
+
+    fn set_fullmove_clock(&mut self, clock: u16) {
+        self.fullmove_clock = clock;
+    }
+
+    fn setup_starting_position(&mut self) {
+        *self = BitBoard::new();
+        place_starting_pieces(self);
+    }
+
+    fn clear(&mut self) {
+        self.pieces = [[0; 6]; 2];
+        self.occupancy = [0; 2];
+        self.mailbox = [None; 64];
+        self.hash = zobrist::compute_hash(self);
+        self.pawn_key = 0;
+    }
+
+    fn make_move(&mut self, mv: &Move) -> UndoMove {
+        let undo = UndoMove {
+            captured_piece: self.get_piece(mv.to),
+            previous_en_passant: self.en_passant,
+            previous_castling_rights: self.castling_rights,
+            previous_halfmove_clock: self.halfmove_clock,
+            previous_fullmove_clock: self.fullmove_clock,
+            previous_to_move: self.to_move,
+        };
+
+        let moving_piece = self.get_piece(mv.from).expect("No piece at square");
+
+        let piece_to_place = match (mv.special_move, mv.promotion) {
+            (Some(SpecialMove::Promotion), Some(promoted)) => Piece::new(promoted, moving_piece.color),
+            (Some(SpecialMove::Promotion), None) => panic!("Promotion move without promotion piece type!"),
+            _ => moving_piece,
+        };
+
+        self.set_piece(mv.from, None);
+        self.set_piece(mv.to, Some(piece_to_place));
+        self.en_passant = None;
+
+        match mv.special_move {
+            Some(SpecialMove::EnPassant) => {
+                let captured_pawn_square = match moving_piece.color {
+                    Color::White => Square(mv.to.0 - 8),
+                    Color::Black => Square(mv.to.0 + 8),
+                };
+                self.set_piece(captured_pawn_square, None);
+            }
+            Some(SpecialMove::Castle) => {
+                let (rook_from, rook_to) = castle_rook_squares(moving_piece.color, mv);
+                if let Some(rook) = self.get_piece(rook_from) {
+                    self.set_piece(rook_from, None);
+                    self.set_piece(rook_to, Some(rook));
+                } else {
+                    debug_assert!(false, "rook missing during castling");
+                }
+            }
+            _ => {}
+        }
+
+        // Capturing on or moving from a rook square, or moving the king, drops rights
+        if undo.captured_piece.is_some() {
+            self.castling_rights &= !castling_rights_touched(mv.to);
+        }
+        self.castling_rights &= !castling_rights_touched(mv.from);
+        if moving_piece.piece_type == PieceType::King {
+            match moving_piece.color {
+                Color::White => self.castling_rights &= !(WK | WQ),
+                Color::Black => self.castling_rights &= !(BK | BQ),
+            }
+        }
+
+        if moving_piece.piece_type == PieceType::Pawn && (mv.to.0 as i8 - mv.from.0 as i8).abs() == 16 {
+            self.en_passant = Some(Square((mv.from.0 + mv.to.0) / 2));
+        }
+
+        if moving_piece.piece_type == PieceType::Pawn || undo.captured_piece.is_some() {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        self.to_move = opposite(self.to_move);
+        if self.to_move == Color::White {
+            self.fullmove_clock += 1;
+        }
+
+        self.hash ^= zobrist::side_key()
+            ^ zobrist::castling_key(undo.previous_castling_rights)
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ zobrist::en_passant_key(undo.previous_en_passant)
+            ^ zobrist::en_passant_key(self.en_passant);
+        debug_assert_eq!(self.hash, zobrist::compute_hash(self), "hash drift after {}", mv);
+        debug_assert_eq!(self.pawn_key, zobrist::compute_pawn_key(self), "pawn key drift after {}", mv);
+
+        undo
+    }
+
+    fn unmake_move(&mut self, mv: &Move, undo: UndoMove) {
+        let piece_at_dest = self.get_piece(mv.to).expect("No piece at destination");
+
+        let piece_to_restore = if let Some(SpecialMove::Promotion) = mv.special_move {
+            Piece::new(PieceType::Pawn, piece_at_dest.color)
+        } else {
+            piece_at_dest
+        };
+
+        self.set_piece(mv.to, undo.captured_piece);
+        self.set_piece(mv.from, Some(piece_to_restore));
+
+        match mv.special_move {
+            Some(SpecialMove::EnPassant) => {
+                let captured_pawn_square = match piece_to_restore.color {
+                    Color::White => Square(mv.to.0 - 8),
+                    Color::Black => Square(mv.to.0 + 8),
+                };
+                self.set_piece(
+                    captured_pawn_square,
+                    Some(Piece::new(PieceType::Pawn, opposite(piece_to_restore.color))),
+                );
+            }
+            Some(SpecialMove::Castle) => {
+                let (rook_from, rook_to) = castle_rook_squares(piece_to_restore.color, mv);
+                if let Some(rook) = self.get_piece(rook_to) {
+                    self.set_piece(rook_to, None);
+                    self.set_piece(rook_from, Some(rook));
+                } else {
+                    debug_assert!(false, "rook missing during unmake of castling");
+                }
+            }
+            _ => {}
+        }
+
+        self.hash ^= zobrist::side_key()
+            ^ zobrist::castling_key(self.castling_rights)
+            ^ zobrist::castling_key(undo.previous_castling_rights)
+            ^ zobrist::en_passant_key(self.en_passant)
+            ^ zobrist::en_passant_key(undo.previous_en_passant);
+
+        self.to_move = undo.previous_to_move;
+        self.en_passant = undo.previous_en_passant;
+        self.castling_rights = undo.previous_castling_rights;
+        self.halfmove_clock = undo.previous_halfmove_clock;
+        self.fullmove_clock = undo.previous_fullmove_clock;
+    }
+
+    fn find_king(&self, color: Color) -> Option<Square> {
+        let kings = self.pieces(PieceType::King, color);
+        if kings == 0 {
+            None
+        } else {
+            Some(Square(kings.trailing_zeros() as u8))
+        }
+    }
+
+    fn is_in_check(&self, color: Color) -> bool {
+        match self.find_king(color) {
+            Some(king) => self.is_square_attacked(king, opposite(color)),
+            None => false,
+        }
+    }
+
+    fn is_square_attacked(&self, square: Square, by_color: Color) -> bool {
+        self.attackers_without_king(square, by_color) != 0
+            || king_attacks(square) & self.pieces(PieceType::King, by_color) != 0
+    }
+
+    fn count_attackers(&self, square: Square, by_color: Color) -> u8 {
+        self.attackers_without_king(square, by_color).count_ones() as u8
+    }
+
+    fn count_pieces(&self, piece_type: PieceType, color: Color) -> u8 {
+        self.pieces(piece_type, color).count_ones() as u8
+    }
+
+    fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn pawn_key(&self) -> u64 {
+        self.pawn_key
+    }
+
+    fn to_fen(&self) -> String {
+        write_fen(self)
+    }
+
+    fn from_fen(fen: &str) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        let mut board = BitBoard::new();
+        board.clear();
+        read_fen(&mut board, fen)?;
+        Ok(board)
+    }
+}
+
+fn castle_rook_squares(color: Color, mv: &Move) -> (Square, Square) {
+    let king_side = mv.to.0 > mv.from.0;
+    match (color, king_side) {
+        (Color::White, true) => (Square(7), Square(5)),
+        (Color::White, false) => (Square(0), Square(3)),
+        (Color::Black, true) => (Square(63), Square(61)),
+        (Color::Black, false) => (Square(56), Square(59)),
+    }
+}
+
+fn castling_rights_touched(square: Square) -> u8 {
+    match square {
+        Square(0) => WQ,
+        Square(7) => WK,
+        Square(56) => BQ,
+        Square(63) => BK,
+        _ => 0,
+    }
+}
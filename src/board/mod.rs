@@ -1,7 +1,14 @@
 pub mod array_board;
+pub mod bitboard;
+pub mod position;
 pub mod traits;
+pub mod zobrist;
 
 pub use array_board::ArrayBoard;
+pub use bitboard::BitBoard;
 pub use traits::{BoardRepresentation, UndoMove};
 
+#[cfg(not(feature = "bitboard"))]
 pub type Board = ArrayBoard;
+#[cfg(feature = "bitboard")]
+pub type Board = BitBoard;
@@ -0,0 +1,201 @@
+//! Representation-independent position setup (starting position and FEN),
+//! shared by every `BoardRepresentation` implementation.
+
+use super::traits::BoardRepresentation;
+use crate::types::*;
+use crate::types::{BK, BQ, WK, WQ};
+
+pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+const BACK_RANK: [PieceType; 8] = [
+    PieceType::Rook,
+    PieceType::Knight,
+    PieceType::Bishop,
+    PieceType::Queen,
+    PieceType::King,
+    PieceType::Bishop,
+    PieceType::Knight,
+    PieceType::Rook,
+];
+
+/// Place the standard starting pieces on an already cleared board.
+pub fn place_starting_pieces<B: BoardRepresentation>(board: &mut B) {
+    for file in 0..8u8 {
+        board.set_piece(Square(file), Some(Piece::new(BACK_RANK[file as usize], Color::White)));
+        board.set_piece(Square(8 + file), Some(Piece::new(PieceType::Pawn, Color::White)));
+        board.set_piece(Square(48 + file), Some(Piece::new(PieceType::Pawn, Color::Black)));
+        board.set_piece(Square(56 + file), Some(Piece::new(BACK_RANK[file as usize], Color::Black)));
+    }
+}
+
+pub fn piece_to_char(piece: Piece) -> char {
+    let c = match piece.piece_type {
+        PieceType::Pawn => 'p',
+        PieceType::Knight => 'n',
+        PieceType::Bishop => 'b',
+        PieceType::Rook => 'r',
+        PieceType::Queen => 'q',
+        PieceType::King => 'k',
+    };
+    match piece.color {
+        Color::White => c.to_ascii_uppercase(),
+        Color::Black => c,
+    }
+}
+
+pub fn piece_from_char(c: char) -> Option<Piece> {
+    let piece_type = match c.to_ascii_lowercase() {
+        'p' => PieceType::Pawn,
+        'n' => PieceType::Knight,
+        'b' => PieceType::Bishop,
+        'r' => PieceType::Rook,
+        'q' => PieceType::Queen,
+        'k' => PieceType::King,
+        _ => return None,
+    };
+    let color = if c.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    };
+    Some(Piece::new(piece_type, color))
+}
+
+pub fn write_fen<B: BoardRepresentation>(board: &B) -> String {
+    let mut fen = String::new();
+
+    // Piece placement (from rank 8 to rank 1)
+    for rank in (0..8).rev() {
+        let mut empty_count = 0;
+        for file in 0..8 {
+            let square = Square(rank * 8 + file);
+            if let Some(piece) = board.get_piece(square) {
+                if empty_count > 0 {
+                    fen.push_str(&empty_count.to_string());
+                    empty_count = 0;
+                }
+                fen.push(piece_to_char(piece));
+            } else {
+                empty_count += 1;
+            }
+        }
+        if empty_count > 0 {
+            fen.push_str(&empty_count.to_string());
+        }
+        if rank > 0 {
+            fen.push('/');
+        }
+    }
+
+    // Side to move
+    fen.push(' ');
+    fen.push(match board.to_move() {
+        Color::White => 'w',
+        Color::Black => 'b',
+    });
+
+    // Castling rights
+    fen.push(' ');
+    let rights = board.castling_rights();
+    if rights == 0 {
+        fen.push('-');
+    } else {
+        if rights & WK != 0 { fen.push('K'); }
+        if rights & WQ != 0 { fen.push('Q'); }
+        if rights & BK != 0 { fen.push('k'); }
+        if rights & BQ != 0 { fen.push('q'); }
+    }
+
+    // En passant
+    fen.push(' ');
+    if let Some(ep) = board.en_passant() {
+        fen.push_str(&ep.to_alg());
+    } else {
+        fen.push('-');
+    }
+
+    // Halfmove clock and fullmove number
+    fen.push_str(&format!(" {} {}", board.halfmove_clock(), board.fullmove_clock()));
+
+    fen
+}
+
+/// Parse `fen` into `board`, which must already be cleared.
+pub fn read_fen<B: BoardRepresentation>(board: &mut B, fen: &str) -> Result<(), String> {
+    let parts: Vec<&str> = fen.split_whitespace().collect();
+    if parts.len() < 4 {
+        return Err("FEN must have at least 4 parts".to_string());
+    }
+
+    // Parse piece placement
+    let ranks: Vec<&str> = parts[0].split('/').collect();
+    if ranks.len() != 8 {
+        return Err("FEN must have 8 ranks".to_string());
+    }
+
+    for (rank_idx, rank_str) in ranks.iter().enumerate() {
+        let rank = 7 - rank_idx; // FEN starts from rank 8
+        let mut file = 0u8;
+
+        for c in rank_str.chars() {
+            if file >= 8 {
+                return Err(format!("Too many squares in rank {}", rank + 1));
+            }
+
+            if let Some(digit) = c.to_digit(10) {
+                file += digit as u8;
+            } else {
+                let piece = piece_from_char(c)
+                    .ok_or_else(|| format!("Invalid piece character: {}", c))?;
+                board.set_piece(Square(rank as u8 * 8 + file), Some(piece));
+                file += 1;
+            }
+        }
+    }
+
+    // Parse side to move
+    board.set_to_move(match parts[1] {
+        "w" => Color::White,
+        "b" => Color::Black,
+        _ => return Err(format!("Invalid side to move: {}", parts[1])),
+    });
+
+    // Parse castling rights
+    let mut rights = 0;
+    if parts[2] != "-" {
+        for c in parts[2].chars() {
+            match c {
+                'K' => rights |= WK,
+                'Q' => rights |= WQ,
+                'k' => rights |= BK,
+                'q' => rights |= BQ,
+                _ => return Err(format!("Invalid castling character: {}", c)),
+            }
+        }
+    }
+    board.set_castling_rights(rights);
+
+    // Parse en passant
+    if parts[3] != "-" {
+        let ep_chars: Vec<char> = parts[3].chars().collect();
+        if ep_chars.len() != 2 {
+            return Err(format!("Invalid en passant square: {}", parts[3]));
+        }
+        let file = (ep_chars[0] as u8).wrapping_sub(b'a');
+        let rank = (ep_chars[1] as u8).wrapping_sub(b'1');
+        if file > 7 || rank > 7 {
+            return Err(format!("Invalid en passant square: {}", parts[3]));
+        }
+        board.set_en_passant(Some(Square(rank * 8 + file)));
+    } else {
+        board.set_en_passant(None);
+    }
+
+    // Parse halfmove clock (optional)
+    board.set_halfmove_clock(parts.get(4).and_then(|s| s.parse().ok()).unwrap_or(0));
+
+    // Parse fullmove number (optional)
+    board.set_fullmove_clock(parts.get(5).and_then(|s| s.parse().ok()).unwrap_or(1));
+
+    Ok(())
+}
@@ -52,6 +52,12 @@ pub trait BoardRepresentation {
         count
     }
 
+    /// Zobrist key of the position, maintained incrementally by make/unmake
+    fn hash(&self) -> u64;
+
+    /// Zobrist key of the pawns alone, maintained alongside `hash`
+    fn pawn_key(&self) -> u64;
+
     fn to_fen(&self) -> String;
     fn from_fen(fen: &str) -> Result<Self, String>
     where
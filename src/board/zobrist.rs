@@ -0,0 +1,152 @@
+//! Zobrist keys for position hashing.
+//! Keys are generated at compile time from a fixed seed so hashes are stable
+//! across runs and threads (required for sharing transposition tables).
+
+use super::traits::BoardRepresentation;
+use crate::types::*;
+
+struct ZobristKeys {
+    pieces: [[[u64; 64]; 6]; 2],
+    side_to_move: u64,
+    castling: [u64; 16],
+    en_passant_file: [u64; 8],
+}
+
+const SEED: u64 = 0x6465_7669_6368_6573; // "devichess"
+
+/// SplitMix64 step: returns (next state, output).
+const fn splitmix64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (state, z ^ (z >> 31))
+}
+
+const fn generate_keys() -> ZobristKeys {
+    let mut keys = ZobristKeys {
+        pieces: [[[0; 64]; 6]; 2],
+        side_to_move: 0,
+        castling: [0; 16],
+        en_passant_file: [0; 8],
+    };
+    let mut state = SEED;
+
+    let mut color = 0;
+    while color < 2 {
+        let mut piece = 0;
+        while piece < 6 {
+            let mut sq = 0;
+            while sq < 64 {
+                let (next, key) = splitmix64(state);
+                state = next;
+                keys.pieces[color][piece][sq] = key;
+                sq += 1;
+            }
+            piece += 1;
+        }
+        color += 1;
+    }
+
+    let (next, key) = splitmix64(state);
+    state = next;
+    keys.side_to_move = key;
+
+    // No rights hashes to zero so an empty-rights position keeps a plain key
+    let mut rights = 1;
+    while rights < 16 {
+        let (next, key) = splitmix64(state);
+        state = next;
+        keys.castling[rights] = key;
+        rights += 1;
+    }
+
+    let mut file = 0;
+    while file < 8 {
+        let (next, key) = splitmix64(state);
+        state = next;
+        keys.en_passant_file[file] = key;
+        file += 1;
+    }
+
+    keys
+}
+
+static KEYS: ZobristKeys = generate_keys();
+
+pub fn piece_key(piece: Piece, square: Square) -> u64 {
+    let color = match piece.color {
+        Color::White => 0,
+        Color::Black => 1,
+    };
+    let piece_idx = match piece.piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    };
+    KEYS.pieces[color][piece_idx][square.0 as usize]
+}
+
+/// `piece_key` for pawns, nothing for other pieces: the pawn key's share of a
+/// square.
+pub fn pawn_piece_key(piece: Piece, square: Square) -> u64 {
+    if piece.piece_type == PieceType::Pawn {
+        piece_key(piece, square)
+    } else {
+        0
+    }
+}
+
+/// XORed in when Black is to move.
+pub fn side_key() -> u64 {
+    KEYS.side_to_move
+}
+
+pub fn castling_key(rights: u8) -> u64 {
+    KEYS.castling[(rights & 0x0F) as usize]
+}
+
+/// Keyed on the file only; the rank is implied by the side to move.
+pub fn en_passant_key(square: Option<Square>) -> u64 {
+    match square {
+        Some(sq) => KEYS.en_passant_file[(sq.0 % 8) as usize],
+        None => 0,
+    }
+}
+
+/// Hash every component from scratch. Boards maintain their key incrementally;
+/// this is the reference used to verify them in debug builds.
+pub fn compute_hash<B: BoardRepresentation>(board: &B) -> u64 {
+    let mut hash = 0;
+
+    for sq in 0..64 {
+        if let Some(piece) = board.get_piece(Square(sq)) {
+            hash ^= piece_key(piece, Square(sq));
+        }
+    }
+
+    if board.to_move() == Color::Black {
+        hash ^= side_key();
+    }
+    hash ^= castling_key(board.castling_rights());
+    hash ^= en_passant_key(board.en_passant());
+
+    hash
+}
+
+/// Key of the pawns alone from scratch, the reference for the pawn key boards
+/// maintain incrementally.
+pub fn compute_pawn_key<B: BoardRepresentation>(board: &B) -> u64 {
+    let mut hash = 0;
+    for sq in 0..64 {
+        if let Some(piece) = board.get_piece(Square(sq)) {
+            if piece.piece_type == PieceType::Pawn {
+                hash ^= piece_key(piece, Square(sq));
+            }
+        }
+    }
+    hash
+}
@@ -1,5 +1,12 @@
-use crate::scheduling::CorePolicy;
-use clap::Parser;
+use crate::scheduling::{CorePolicy, CpuSet};
+use crate::search::parallel::ParallelMode;
+use clap::{Parser, ValueEnum};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EvalFormat {
+    Table,
+    Json,
+}
 
 #[derive(Parser)]
 pub struct Cli {
@@ -10,9 +17,21 @@ pub struct Cli {
     #[arg(long, help = "Run full thread count sweep for benchmarking")]
     pub benchmark_sweep: bool,
 
+    #[arg(long, help = "Run the benchmark once per parallel mode, all into one CSV")]
+    pub compare_modes: bool,
+
     #[arg(long)]
     pub soak: bool,
 
+    #[arg(long, help = "Speak UCI on stdin/stdout for a GUI or match runner")]
+    pub uci: bool,
+
+    #[arg(long, help = "Print the evaluation of --fen term by term")]
+    pub eval: bool,
+
+    #[arg(long, value_enum, default_value_t = EvalFormat::Table, help = "Output of --eval")]
+    pub eval_format: EvalFormat,
+
     // ENGINE DEBUG
     #[arg(long)]
     pub perft: bool,
@@ -27,15 +46,94 @@ pub struct Cli {
     #[arg(long, default_value_t = 1)]
     pub threads: usize,
 
+    #[arg(long, value_enum, default_value_t = ParallelMode::RootSplit, help = "How --threads split the search")]
+    pub parallel_mode: ParallelMode,
+
     #[arg(long, default_value_t = 4)]
     pub depth: u32,
 
+    #[arg(long, help = "Search for this many milliseconds instead of to --depth")]
+    pub movetime: Option<u64>,
+
+    #[arg(long, help = "Deepen one ply at a time up to --depth and print each iteration")]
+    pub iterative: bool,
+
     #[arg(long, default_value_t = 5)]
     pub warmup: usize,
 
     #[arg(long, default_value_t = 10)]
     pub runs: usize,
 
+    #[arg(long, default_value_t = 16, help = "Transposition table size in MB")]
+    pub hash: usize,
+
+    // MOVE ORDERING
+    #[arg(long, help = "Don't search the transposition table move first")]
+    pub no_tt_move: bool,
+
+    #[arg(long, help = "Don't order captures by MVV-LVA")]
+    pub no_mvv_lva: bool,
+
+    #[arg(long, help = "Disable killer moves")]
+    pub no_killers: bool,
+
+    #[arg(long, help = "Disable the history heuristic")]
+    pub no_history: bool,
+
+    #[arg(long, help = "Don't move captures that lose material by SEE to the back")]
+    pub no_see: bool,
+
+    // QUIESCENCE
+    #[arg(long, help = "Return the static evaluation at depth 0 instead of searching captures")]
+    pub no_qsearch: bool,
+
+    #[arg(long, help = "Search all check evasions in quiescence")]
+    pub qsearch_evasions: bool,
+
+    #[arg(long, help = "Disable delta pruning in quiescence")]
+    pub no_delta_pruning: bool,
+
+    #[arg(long, help = "Search captures that lose material by SEE in quiescence")]
+    pub no_see_pruning: bool,
+
+    // SELECTIVITY
+    #[arg(long, help = "Turn on every selective search technique below")]
+    pub selective: bool,
+
+    #[arg(long, help = "Null-move pruning")]
+    pub null_move: bool,
+
+    #[arg(long, help = "Late-move reductions")]
+    pub lmr: bool,
+
+    #[arg(long, help = "Reverse futility and futility pruning")]
+    pub futility: bool,
+
+    #[arg(long, help = "Search one ply deeper when in check")]
+    pub check_extensions: bool,
+
+    #[arg(long, help = "Search every root move with a full window instead of against the best root score so far")]
+    pub no_root_bound: bool,
+
+    // EVALUATION
+    #[arg(long, value_name = "PATH", help = "Evaluate with the weights in this JSON file instead of the built-in ones")]
+    pub eval_params: Option<String>,
+
+    #[arg(long, value_name = "POSITIONS", help = "Tune the evaluation weights on an EPD or CSV file of positions with results")]
+    pub tune: Option<String>,
+
+    #[arg(long, default_value = "eval_params.json", help = "Where --tune writes the tuned weights")]
+    pub tune_output: String,
+
+    #[arg(long, default_value_t = 10, help = "Most passes over the weights for --tune")]
+    pub tune_passes: usize,
+
+    #[arg(long, help = "Leave king safety (pawn shield, open files, king attacks) out of the evaluation")]
+    pub no_king_safety: bool,
+
+    #[arg(long, help = "Leave piece mobility out of the evaluation")]
+    pub no_mobility: bool,
+
     // HETEROGENEOUS SCHEDULING
     #[arg(long, value_enum, help = "Core scheduling policy for heterogeneous architectures")]
     pub core_policy: Option<CorePolicy>,
@@ -43,6 +141,15 @@ pub struct Cli {
     #[arg(long, default_value_t = 0.80, help = "Ratio of fast cores in mixed mode (0.0-1.0)")]
     pub mixed_ratio: f32,
 
+    #[arg(long, help = "CPUs to treat as P-cores, e.g. 0-7 (default: read from sysfs)")]
+    pub p_core_set: Option<CpuSet>,
+
+    #[arg(long, help = "CPUs to treat as E-cores, e.g. 8-9 (default: read from sysfs)")]
+    pub e_core_set: Option<CpuSet>,
+
+    #[arg(long, help = "Emulate E-cores by throttling their workers this many times slower (12.8 matches the M1 Pro)")]
+    pub emulate_slowdown: Option<f32>,
+
     // FAULT TOLERANCE
     #[arg(long)]
     pub inject_panic: Option<usize>,
@@ -84,6 +191,10 @@ pub struct Cli {
     #[arg(long, default_value_t = 0.3)]
     pub light_threshold: f32,
 
+    /// Keep the probe's heavy/light split for the whole search instead of letting idle workers take the other pool's moves
+    #[arg(long, default_value_t = false)]
+    pub no_adaptive: bool,
+
     /// FEN position for benchmarking (default: starting position)
     #[arg(long)]
     pub fen: Option<String>,
@@ -147,12 +258,152 @@ mod tests {
         assert_eq!(args.mixed_ratio, 0.5);
     }
 
+    #[test]
+    fn test_core_sets() {
+        let args = parse_test_args(&["devi"]);
+        assert!(args.p_core_set.is_none() && args.e_core_set.is_none());
+
+        let args = parse_test_args(&["devi", "--p-core-set", "0-7", "--e-core-set", "8,9"]);
+        assert_eq!(args.p_core_set.unwrap().cpus(), &[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(args.e_core_set.unwrap().cpus(), &[8, 9]);
+
+        assert!(Cli::try_parse_from(["devi", "--p-core-set", "7-0"]).is_err());
+    }
+
+    #[test]
+    fn test_emulate_slowdown() {
+        assert_eq!(parse_test_args(&["devi"]).emulate_slowdown, None);
+        let args = parse_test_args(&["devi", "--two-phase", "--emulate-slowdown", "12.8"]);
+        assert_eq!(args.emulate_slowdown, Some(12.8));
+    }
+
     #[test]
     fn test_csv_output_flag() {
         let args = parse_test_args(&["devi", "--csv-output", "custom/path.csv"]);
         assert_eq!(args.csv_output, Some("custom/path.csv".to_string()));
     }
 
+    #[test]
+    fn test_hash_size() {
+        let args = parse_test_args(&["devi"]);
+        assert_eq!(args.hash, 16);
+        let args = parse_test_args(&["devi", "--hash", "64"]);
+        assert_eq!(args.hash, 64);
+    }
+
+    #[test]
+    fn test_movetime() {
+        let args = parse_test_args(&["devi"]);
+        assert_eq!(args.movetime, None);
+        let args = parse_test_args(&["devi", "--movetime", "500"]);
+        assert_eq!(args.movetime, Some(500));
+    }
+
+    #[test]
+    fn test_iterative_flag() {
+        assert!(!parse_test_args(&["devi"]).iterative);
+        let args = parse_test_args(&["devi", "--iterative", "--depth", "6"]);
+        assert!(args.iterative);
+        assert_eq!(args.depth, 6);
+    }
+
+    #[test]
+    fn test_uci_flag() {
+        assert!(!parse_test_args(&["devi"]).uci);
+        assert!(parse_test_args(&["devi", "--uci"]).uci);
+    }
+
+    #[test]
+    fn test_ordering_flags() {
+        let args = parse_test_args(&["devi", "--no-killers", "--no-history"]);
+        assert!(!args.no_tt_move);
+        assert!(!args.no_mvv_lva);
+        assert!(args.no_killers);
+        assert!(args.no_history);
+        assert!(!args.no_see);
+        assert!(parse_test_args(&["devi", "--no-see"]).no_see);
+    }
+
+    #[test]
+    fn test_quiescence_flags() {
+        let args = parse_test_args(&["devi"]);
+        assert!(!args.no_qsearch && !args.qsearch_evasions && !args.no_delta_pruning && !args.no_see_pruning);
+        let args = parse_test_args(&["devi", "--no-qsearch", "--qsearch-evasions"]);
+        assert!(args.no_qsearch);
+        assert!(args.qsearch_evasions);
+    }
+
+    #[test]
+    fn test_eval_mode() {
+        let args = parse_test_args(&["devi", "--eval", "--fen", "8/8/8/8/8/8/8/K6k w - - 0 1"]);
+        assert!(args.eval);
+        assert_eq!(args.eval_format, EvalFormat::Table);
+        let args = parse_test_args(&["devi", "--eval", "--eval-format", "json"]);
+        assert_eq!(args.eval_format, EvalFormat::Json);
+    }
+
+    #[test]
+    fn test_tune_flags() {
+        let args = parse_test_args(&["devi"]);
+        assert_eq!(args.tune, None);
+        assert_eq!(args.tune_output, "eval_params.json");
+        let args = parse_test_args(&["devi", "--tune", "games.epd", "--tune-passes", "3"]);
+        assert_eq!(args.tune.as_deref(), Some("games.epd"));
+        assert_eq!(args.tune_passes, 3);
+    }
+
+    #[test]
+    fn test_selectivity_flags() {
+        let args = parse_test_args(&["devi"]);
+        assert!(!args.selective && !args.null_move && !args.lmr && !args.futility && !args.check_extensions);
+        let args = parse_test_args(&["devi", "--null-move", "--lmr"]);
+        assert!(args.null_move && args.lmr);
+        assert!(!args.futility);
+        assert!(parse_test_args(&["devi", "--selective"]).selective);
+        assert!(parse_test_args(&["devi", "--no-root-bound"]).no_root_bound);
+    }
+
+    #[test]
+    fn test_evaluation_flags() {
+        let args = parse_test_args(&["devi"]);
+        assert!(!args.no_king_safety && !args.no_mobility);
+        assert_eq!(args.eval_params, None);
+        let args = parse_test_args(&["devi", "--no-mobility", "--eval-params", "tuned.json"]);
+        assert!(!args.no_king_safety);
+        assert!(args.no_mobility);
+        assert_eq!(args.eval_params.as_deref(), Some("tuned.json"));
+    }
+
+    #[test]
+    fn test_parallel_mode() {
+        let args = parse_test_args(&["devi"]);
+        assert_eq!(args.parallel_mode, ParallelMode::RootSplit);
+
+        let args = parse_test_args(&["devi", "--threads", "4", "--parallel-mode", "lazy-smp"]);
+        assert_eq!(args.parallel_mode, ParallelMode::LazySmp);
+
+        let args = parse_test_args(&["devi", "--parallel-mode", "two-phase"]);
+        assert_eq!(args.parallel_mode, ParallelMode::TwoPhase);
+
+        let args = parse_test_args(&["devi", "--parallel-mode", "ybwc"]);
+        assert_eq!(args.parallel_mode, ParallelMode::Ybwc);
+
+        let args = parse_test_args(&["devi", "--parallel-mode", "jamboree"]);
+        assert_eq!(args.parallel_mode, ParallelMode::Jamboree);
+
+        let args = parse_test_args(&["devi", "--parallel-mode", "abdada"]);
+        assert_eq!(args.parallel_mode, ParallelMode::Abdada);
+
+        let args = parse_test_args(&["devi", "--benchmark", "--benchmark-sweep", "--compare-modes"]);
+        assert!(args.compare_modes);
+    }
+
+    #[test]
+    fn test_adaptive_two_phase() {
+        assert!(!parse_test_args(&["devi", "--two-phase"]).no_adaptive);
+        assert!(parse_test_args(&["devi", "--two-phase", "--no-adaptive"]).no_adaptive);
+    }
+
     #[test]
     fn test_csv_output_default() {
         let args = parse_test_args(&["devi"]);
@@ -1,14 +1,24 @@
 use crate::benchmark::{run_benchmark, BenchmarkConfig, BenchmarkResult};
 use crate::board::{Board, BoardRepresentation};
+use crate::cli::cli::EvalFormat;
 use crate::cli::Cli;
-use crate::evaluation::evaluate;
+use crate::evaluation::{evaluate, evaluate_traced_with, EvalParams};
 use crate::moves::{perft, perft_divide, perft_parallel};
-use crate::scheduling::CorePolicy;
+use crate::scheduling::emulation::{emulated_slowdown, emulation_enabled, set_emulated_slowdown};
+use crate::scheduling::{core_sets, set_core_sets, CorePolicy, CoreSets, CpuSet};
 use crate::search::fault_tolerant::with_recovery;
 use crate::search::parallel::parallel_search_with_fault;
-use crate::search::parallel::parallel_search_with_policy;
+use crate::search::minimax::{QuiescenceConfig, SearchConfig, SharedSearch};
+use crate::search::ordering::OrderingConfig;
+use crate::search::selectivity::SelectivityConfig;
+use crate::search::iterative::{pv_string, SearchResult};
+use crate::search::parallel::{parallel_iterative_search_with_mode, parallel_search_shared, parallel_search_with_mode, two_phase_iterative_search, ParallelMode};
 use crate::search::probe::ClassificationConfig;
-use crate::search::{parallel_search, search, two_phase_search, two_phase_search_with_metrics, TwoPhaseConfig, TwoPhaseMetrics}; 
+use crate::search::time_control::{TimeControl, TimeManager};
+use crate::search::transposition::TTStats;
+use crate::tuning::{load_positions, tune, TunerConfig};
+use crate::search::{parallel_search, iterative_search, search, search_shared, two_phase_search, two_phase_search_with_metrics, TwoPhaseConfig, TwoPhaseMetrics}; 
+use clap::ValueEnum;
 use rayon;
 use std::fs::{create_dir_all, File};
 use std::io::{BufWriter, Write};
@@ -38,7 +48,13 @@ pub fn run_full_benchmark(args: &Cli) {
         }
     };
 
-    let config = BenchmarkConfig {
+    let modes = if args.compare_modes {
+        ParallelMode::value_variants().to_vec()
+    } else {
+        vec![parallel_mode(args)]
+    };
+
+    let mut config = BenchmarkConfig {
         depth: args.depth,
         warmup_runs: args.warmup,
         measurement_runs: args.runs,
@@ -46,9 +62,12 @@ pub fn run_full_benchmark(args: &Cli) {
         core_policy: policy,
         mixed_ratio: mixed_ratio,
         inject_panic: args.inject_panic,
+        search: search_config(args),
+        parallel_mode: parallel_mode(args),
     };
 
     println!("Core scheduling policy: {:?}", policy);
+    print_core_sets();
     if matches!(policy, CorePolicy::Mixed) {
         println!(
             "Mixed ratio: {:.2} ({}% fast cores)",
@@ -57,25 +76,121 @@ pub fn run_full_benchmark(args: &Cli) {
         );
     }
 
-    let results = run_benchmark(&config);
+    // Each mode's speedups are against its own first thread count
+    let mut results = Vec::new();
+    for mode in modes {
+        config.parallel_mode = mode;
+        results.extend(run_benchmark(&config));
+    }
     export_benchmark_csv(&results, args.csv_output.as_deref());
 }
 
+/// Apply `--p-core-set`, `--e-core-set` and `--emulate-slowdown` before any
+/// pool is built.
+pub fn configure_scheduling(args: &Cli) {
+    if let Some(slowdown) = args.emulate_slowdown {
+        set_emulated_slowdown(slowdown);
+    }
+    if args.p_core_set.is_none() && args.e_core_set.is_none() {
+        return;
+    }
+    let sets = CoreSets::discover().with_overrides(args.p_core_set.clone(), args.e_core_set.clone());
+    if set_core_sets(sets).is_err() {
+        eprintln!("Warning: core sets were already fixed, ignoring --p-core-set/--e-core-set");
+    }
+}
+
+fn print_core_sets() {
+    let sets = core_sets();
+    if sets.fast().is_none() && sets.efficient().is_none() {
+        println!("Core sets: none to pin to ({:?})", sets.source);
+    } else {
+        let list = |set: &CpuSet| if set.is_empty() { "none".to_string() } else { set.to_string() };
+        println!("Core sets: P {} / E {} ({:?})", list(&sets.p_cores), list(&sets.e_cores), sets.source);
+    }
+    if emulation_enabled() {
+        println!("Emulated E-cores: {:.1}x slower", emulated_slowdown());
+    }
+}
+
+/// Print the evaluation of `--fen` (or the starting position) term by term.
+pub fn run_eval(args: &Cli) {
+    let board = match &args.fen {
+        Some(fen) => match Board::from_fen(fen) {
+            Ok(board) => board,
+            Err(e) => {
+                eprintln!("Failed to parse FEN: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            let mut board = Board::new();
+            board.setup_starting_position();
+            board
+        }
+    };
+
+    let trace = evaluate_traced_with(&board, &eval_params(args));
+    match args.eval_format {
+        EvalFormat::Table => println!("{}", trace),
+        EvalFormat::Json => println!("{}", serde_json::to_string_pretty(&trace).unwrap()),
+    }
+}
+
+/// Tune the evaluation weights on `--tune` and save them to `--tune-output`.
+pub fn run_tune(args: &Cli) {
+    let Some(path) = &args.tune else { return };
+    let positions = match load_positions(path) {
+        Ok(positions) => positions,
+        Err(e) => {
+            eprintln!("Failed to load positions: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let mut params = eval_params(args);
+    println!("Tuning {} weights on {} positions", params.weights_mut().len(), positions.len());
+
+    let config = TunerConfig {
+        passes: args.tune_passes,
+        ..TunerConfig::default()
+    };
+    let start = Instant::now();
+    let result = tune(&positions, &params, &config, |pass, error| {
+        println!("  Pass {}: error {:.6} ({:.1}s)", pass, error, start.elapsed().as_secs_f64());
+    });
+
+    println!("K: {:.2}", result.k);
+    println!("Error: {:.6} -> {:.6}", result.initial_error, result.final_error);
+    match result.params.save(&args.tune_output) {
+        Ok(()) => println!("Weights written to {}", args.tune_output),
+        Err(e) => {
+            eprintln!("Failed to write weights: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 pub fn run_single_search(args: &Cli) {
     let mut board = Board::new();
     board.setup_starting_position();
 
     println!("Starting position evaluation: {}", evaluate(&mut board));
-    println!("Searching to depth {}...", args.depth);
+    match args.movetime {
+        Some(ms) => println!("Searching for {}ms...", ms),
+        None if args.iterative => println!("Deepening to depth {}...", args.depth),
+        None => println!("Searching to depth {}...", args.depth),
+    }
 
     let policy = args.core_policy.unwrap_or(CorePolicy::None);
     let mixed_ratio = args.mixed_ratio;
 
     if let Some(ref p) = args.core_policy {
         println!("Using core policy: {:?}", p);
+        print_core_sets();
     }
 
-    if args.two_phase {
+    let mode = parallel_mode(args);
+    if mode == ParallelMode::TwoPhase {
         let config = TwoPhaseConfig {
             probe_depth: args.probe_depth,
             p_core_threads: args.p_cores,
@@ -83,7 +198,9 @@ pub fn run_single_search(args: &Cli) {
             classification: ClassificationConfig {
                 heavy_ratio: args.heavy_ratio,
                 light_threshold: args.light_threshold,
+                adaptive: !args.no_adaptive,
             },
+            search: search_config(args),
         };
         
         println!("Using two-phase scheduler:");
@@ -91,18 +208,42 @@ pub fn run_single_search(args: &Cli) {
         println!("  P-cores: {}", config.p_core_threads);
         println!("  E-cores: {}", config.e_core_threads);
         println!("  Heavy ratio: {:.1}", config.classification.heavy_ratio);
+        println!("  Adaptive: {}", config.classification.adaptive);
+        print_core_sets();
         
+        if is_iterative(args) {
+            let shared = iterative_shared(args, &board);
+            let result = two_phase_iterative_search(&mut board, &config, &shared);
+            print_search_result(&result, &shared);
+            return;
+        }
+
         let start = Instant::now();
-        let (best_move, score) = two_phase_search(&mut board, args.depth, &config);
+        let (best_move, score, metrics) = two_phase_search_with_metrics(&mut board, args.depth, &config);
         let elapsed = start.elapsed();
         
         println!("\nResult:");
         println!("  Best move: {}", best_move.to_algebraic());
         println!("  Score: {}", score);
         println!("  Time: {:.3}ms", elapsed.as_secs_f64() * 1000.0);
+        println!("  Nodes: {}", metrics.nodes);
+        print_tt_stats(&metrics.tt_stats);
+        return;
+    }
+
+    if is_iterative(args) {
+        let shared = iterative_shared(args, &board);
+        let result = if args.threads == 1 {
+            iterative_search(&mut board, &shared)
+        } else {
+            parallel_iterative_search_with_mode(&mut board, mode, policy, args.threads, mixed_ratio, &shared)
+        };
+        print_search_result(&result, &shared);
         return;
     }
 
+    let shared = SharedSearch::new(search_config(args));
+
     // Wrap with recovery if panic injection requested
     let start = Instant::now();
     let (best_move, score) = if args.inject_panic.is_some() {
@@ -111,17 +252,17 @@ pub fn run_single_search(args: &Cli) {
         let search_fn = || {
             let mut b = board.clone();
             if args.threads == 1 {
-                search(&mut b, args.depth)
+                search_shared(&mut b, args.depth, &shared)
             } else {
-                parallel_search_with_policy(&mut b, args.depth, policy, args.threads, mixed_ratio)
+                parallel_search_with_mode(&mut b, args.depth, mode, policy, args.threads, mixed_ratio, &shared)
             }
         };
         with_recovery(search_fn, args.inject_panic)
     } else {
         if args.threads == 1 {
-            search(&mut board, args.depth)
+            search_shared(&mut board, args.depth, &shared)
         } else {
-            parallel_search_with_policy(&mut board, args.depth, policy, args.threads, mixed_ratio)
+            parallel_search_with_mode(&mut board, args.depth, mode, policy, args.threads, mixed_ratio, &shared)
         }
     };
     let elapsed = start.elapsed();
@@ -130,6 +271,123 @@ pub fn run_single_search(args: &Cli) {
     println!("  Best move: {}", best_move.to_algebraic());
     println!("  Score: {}", score);
     println!("  Time: {:.3}ms", elapsed.as_secs_f64() * 1000.0);
+    println!("  Nodes: {} ({} quiescence)", shared.nodes(), shared.qnodes());
+    print_tt_stats(&shared.tt_stats());
+    println!("  Hashfull: {}/1000", shared.tt.hashfull());
+}
+
+// `--two-phase` predates `--parallel-mode` and still selects it
+fn parallel_mode(args: &Cli) -> ParallelMode {
+    if args.two_phase {
+        ParallelMode::TwoPhase
+    } else {
+        args.parallel_mode
+    }
+}
+
+fn is_iterative(args: &Cli) -> bool {
+    args.iterative || args.movetime.is_some()
+}
+
+/// Shared state for an iterative search of `board`, limited by `--movetime`
+/// or else `--depth`. The clock starts now.
+fn iterative_shared(args: &Cli, board: &Board) -> SharedSearch {
+    let limits = match args.movetime {
+        Some(ms) => TimeManager::new(&TimeControl::movetime(ms), board.to_move()),
+        None => TimeManager::fixed_depth(args.depth),
+    };
+    SharedSearch::new(search_config(args)).with_time(limits)
+}
+
+fn print_search_result(result: &SearchResult, shared: &SharedSearch) {
+    println!();
+    for info in &result.iterations {
+        println!(
+            "  depth {:>2}  score {:>7}  nodes {:>10}  time {:>9.3}ms  pv {}",
+            info.depth,
+            info.score,
+            info.nodes,
+            info.elapsed.as_secs_f64() * 1000.0,
+            pv_string(&info.pv)
+        );
+    }
+
+    println!("\nResult:");
+    println!("  Best move: {}", result.best_move.to_algebraic());
+    println!("  Score: {}", result.score);
+    println!("  Depth: {}", result.depth);
+    println!("  PV: {}", result.pv_string());
+    println!("  Time: {:.3}ms", result.elapsed.as_secs_f64() * 1000.0);
+    println!("  Nodes: {} ({} quiescence)", result.nodes, shared.qnodes());
+    println!("  NPS: {}", result.nps);
+    print_tt_stats(&shared.tt_stats());
+}
+
+/// Search knobs from the command line.
+fn search_config(args: &Cli) -> SearchConfig {
+    SearchConfig {
+        tt_size_mb: args.hash,
+        ordering: OrderingConfig {
+            tt_move: !args.no_tt_move,
+            mvv_lva: !args.no_mvv_lva,
+            killers: !args.no_killers,
+            history: !args.no_history,
+            see: !args.no_see,
+        },
+        quiescence: QuiescenceConfig {
+            enabled: !args.no_qsearch,
+            promotions: true,
+            evasions: args.qsearch_evasions,
+            delta_pruning: !args.no_delta_pruning,
+            see_pruning: !args.no_see_pruning,
+        },
+        selectivity: SelectivityConfig {
+            null_move: args.selective || args.null_move,
+            lmr: args.selective || args.lmr,
+            reverse_futility: args.selective || args.futility,
+            futility: args.selective || args.futility,
+            check_extensions: args.selective || args.check_extensions,
+        },
+        eval: eval_params(args),
+        root_bound: !args.no_root_bound,
+    }
+}
+
+/// Evaluation weights from `--eval-params` (or the built-in ones), with the
+/// terms switched off on the command line left out. Exits if the file can't
+/// be read.
+pub fn eval_params(args: &Cli) -> EvalParams {
+    let mut params = match &args.eval_params {
+        Some(path) => match EvalParams::load(path) {
+            Ok(params) => params,
+            Err(e) => {
+                eprintln!("Failed to load evaluation parameters: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => EvalParams::default(),
+    };
+    if args.no_king_safety {
+        params.king_safety.pawn_shield = false;
+        params.king_safety.open_files = false;
+        params.king_safety.king_attack = false;
+    }
+    if args.no_mobility {
+        params.mobility.enabled = false;
+    }
+    params
+}
+
+fn print_tt_stats(stats: &TTStats) {
+    println!(
+        "  TT: {} probes, {} hits ({:.1}%), {} misses, {} collisions, {} stores",
+        stats.probes,
+        stats.hits,
+        stats.hit_rate() * 100.0,
+        stats.misses,
+        stats.collisions,
+        stats.stores
+    );
 }
 
 pub fn run_recovery_analysis(args: &Cli) {
@@ -435,7 +693,7 @@ pub fn export_benchmark_csv_with_policy(results: &[BenchmarkResult], custom_path
     // Write header with timestamp as first column
     writeln!(
         file,
-        "timestamp,threads,policy,median_ms,searches_per_sec,speedup,efficiency"
+        "timestamp,threads,policy,mode,median_ms,searches_per_sec,speedup,efficiency,tt_probes,tt_hits,tt_misses,tt_collisions,nodes_per_search"
     )
     .unwrap();
 
@@ -443,14 +701,20 @@ pub fn export_benchmark_csv_with_policy(results: &[BenchmarkResult], custom_path
     for result in results {
         writeln!(
             file,
-            "{},{},{:?},{:.3},{:.2},{:.2},{:.1}",
+            "{},{},{:?},{:?},{:.3},{:.2},{:.2},{:.1},{},{},{},{},{:.0}",
             timestamp_str, // Add timestamp to each row
             result.thread_count,
             result.core_policy,
+            result.parallel_mode,
             result.stats.median,
             result.searches_per_second,
             result.speedup,
-            result.efficiency
+            result.efficiency,
+            result.tt_stats.probes,
+            result.tt_stats.hits,
+            result.tt_stats.misses,
+            result.tt_stats.collisions,
+            result.nodes_per_search
         )
         .unwrap();
     }
@@ -777,6 +1041,7 @@ pub const BENCHMARK_POSITIONS: &[(&str, &str)] = &[
 
 pub fn run_two_phase_benchmark(args: &Cli) {
     println!("=== TWO-PHASE SCHEDULER BENCHMARK ===\n");
+    print_core_sets();
     
     let depth = args.depth;
     let warmup = args.warmup;
@@ -813,7 +1078,7 @@ pub fn run_two_phase_benchmark(args: &Cli) {
         
         // Baseline: 10 threads, no scheduling
         println!("\n[Baseline] 10 threads, CorePolicy::None");
-        let baseline_result = benchmark_baseline(&mut board, depth, warmup, runs, pos_name);
+        let baseline_result = benchmark_baseline(&mut board, depth, warmup, runs, pos_name, search_config(args));
         let baseline_sps = baseline_result.searches_per_second;
         all_results.push(baseline_result);
         
@@ -822,7 +1087,7 @@ pub fn run_two_phase_benchmark(args: &Cli) {
         
         // FastBias: P-cores only
         println!("\n[FastBias] 8 threads, CorePolicy::FastBias");
-        let fast_result = benchmark_fast_bias(&mut board, depth, warmup, runs, pos_name, baseline_sps);
+        let fast_result = benchmark_fast_bias(&mut board, depth, warmup, runs, pos_name, search_config(args), baseline_sps);
         all_results.push(fast_result);
         
         std::thread::sleep(std::time::Duration::from_millis(100));
@@ -832,8 +1097,8 @@ pub fn run_two_phase_benchmark(args: &Cli) {
             println!("\n[TwoPhase] probe_depth={}, ratio=0.6", probe_depth);
             let tp_result = benchmark_two_phase(
                 &mut board, depth, warmup, runs, pos_name,
-                probe_depth, 0.6, 0.3, 
-                args.p_cores, args.e_cores,
+                probe_depth, classification(args, 0.6),
+                args.p_cores, args.e_cores, search_config(args),
                 baseline_sps
             );
             all_results.push(tp_result);
@@ -846,8 +1111,8 @@ pub fn run_two_phase_benchmark(args: &Cli) {
             println!("\n[TwoPhase] probe_depth=1, ratio={:.1}", ratio);
             let tp_result = benchmark_two_phase(
                 &mut board, depth, warmup, runs, pos_name,
-                1, ratio, 0.3,
-                args.p_cores, args.e_cores,
+                1, classification(args, ratio),
+                args.p_cores, args.e_cores, search_config(args),
                 baseline_sps
             );
             all_results.push(tp_result);
@@ -865,19 +1130,21 @@ pub fn run_two_phase_benchmark(args: &Cli) {
     print_two_phase_summary(&all_results);
 }
 
-fn benchmark_baseline(board: &mut Board, depth: u32, warmup: usize, runs: usize, pos_name: &str) -> TwoPhaseBenchmarkResult {
+fn benchmark_baseline(board: &mut Board, depth: u32, warmup: usize, runs: usize, pos_name: &str, search: SearchConfig) -> TwoPhaseBenchmarkResult {
     // Warmup
     for _ in 0..warmup {
         let mut b = board.clone();
-        let _ = parallel_search_with_policy(&mut b, depth, CorePolicy::None, 10, 0.8);
+        let shared = SharedSearch::new(search);
+        let _ = parallel_search_shared(&mut b, depth, CorePolicy::None, 10, 0.8, None, &shared);
     }
     
     // Measure
     let mut samples: Vec<TwoPhaseMetrics> = Vec::new();
     for i in 1..=runs {
         let mut b = board.clone();
+        let shared = SharedSearch::new(search);
         let start = Instant::now();
-        let (mv, score) = parallel_search_with_policy(&mut b, depth, CorePolicy::None, 10, 0.8);
+        let (mv, score) = parallel_search_shared(&mut b, depth, CorePolicy::None, 10, 0.8, None, &shared);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
         
         let metrics = TwoPhaseMetrics {
@@ -889,6 +1156,10 @@ fn benchmark_baseline(board: &mut Board, depth: u32, warmup: usize, runs: usize,
             light_move_count: 0,
             best_move: mv.to_algebraic(),
             score,
+            tt_stats: shared.tt_stats(),
+            nodes: shared.nodes(),
+            migrated_to_p: 0,
+            migrated_to_e: 0,
         };
         samples.push(metrics);
         print!("  Run {}: {:.1}ms  ", i, elapsed);
@@ -914,17 +1185,19 @@ fn benchmark_baseline(board: &mut Board, depth: u32, warmup: usize, runs: usize,
     }
 }
 
-fn benchmark_fast_bias(board: &mut Board, depth: u32, warmup: usize, runs: usize, pos_name: &str, baseline_sps: f64) -> TwoPhaseBenchmarkResult {
+fn benchmark_fast_bias(board: &mut Board, depth: u32, warmup: usize, runs: usize, pos_name: &str, search: SearchConfig, baseline_sps: f64) -> TwoPhaseBenchmarkResult {
     for _ in 0..warmup {
         let mut b = board.clone();
-        let _ = parallel_search_with_policy(&mut b, depth, CorePolicy::FastBias, 8, 0.8);
+        let shared = SharedSearch::new(search);
+        let _ = parallel_search_shared(&mut b, depth, CorePolicy::FastBias, 8, 0.8, None, &shared);
     }
     
     let mut samples: Vec<TwoPhaseMetrics> = Vec::new();
     for i in 1..=runs {
         let mut b = board.clone();
+        let shared = SharedSearch::new(search);
         let start = Instant::now();
-        let (mv, score) = parallel_search_with_policy(&mut b, depth, CorePolicy::FastBias, 8, 0.8);
+        let (mv, score) = parallel_search_shared(&mut b, depth, CorePolicy::FastBias, 8, 0.8, None, &shared);
         let elapsed = start.elapsed().as_secs_f64() * 1000.0;
         
         let metrics = TwoPhaseMetrics {
@@ -936,6 +1209,10 @@ fn benchmark_fast_bias(board: &mut Board, depth: u32, warmup: usize, runs: usize
             light_move_count: 0,
             best_move: mv.to_algebraic(),
             score,
+            tt_stats: shared.tt_stats(),
+            nodes: shared.nodes(),
+            migrated_to_p: 0,
+            migrated_to_e: 0,
         };
         samples.push(metrics);
         print!("  Run {}: {:.1}ms  ", i, elapsed);
@@ -961,15 +1238,23 @@ fn benchmark_fast_bias(board: &mut Board, depth: u32, warmup: usize, runs: usize
     }
 }
 
-fn benchmark_two_phase(board: &mut Board, depth: u32, warmup: usize, runs: usize, pos_name: &str,probe_depth: u8, heavy_ratio: f32, light_threshold: f32,p_cores: usize, e_cores: usize, baseline_sps: f64) -> TwoPhaseBenchmarkResult {
+// Classification for the two-phase benchmark sweep at `heavy_ratio`
+fn classification(args: &Cli, heavy_ratio: f32) -> ClassificationConfig {
+    ClassificationConfig {
+        heavy_ratio,
+        light_threshold: 0.3,
+        adaptive: !args.no_adaptive,
+    }
+}
+
+fn benchmark_two_phase(board: &mut Board, depth: u32, warmup: usize, runs: usize, pos_name: &str,probe_depth: u8, classification: ClassificationConfig,p_cores: usize, e_cores: usize, search: SearchConfig, baseline_sps: f64) -> TwoPhaseBenchmarkResult {
+    let heavy_ratio = classification.heavy_ratio;
     let config = TwoPhaseConfig {
         probe_depth,
         p_core_threads: p_cores,
         e_core_threads: e_cores,
-        classification: ClassificationConfig {
-            heavy_ratio,
-            light_threshold,
-        },
+        classification,
+        search,
     };
     
     for _ in 0..warmup {
@@ -982,9 +1267,10 @@ fn benchmark_two_phase(board: &mut Board, depth: u32, warmup: usize, runs: usize
         let mut b = board.clone();
         let (_, _, metrics) = two_phase_search_with_metrics(&mut b, depth, &config);
         samples.push(metrics.clone());
-        print!("  Run {}: {:.1}ms (probe: {:.1}ms, P1: {:.1}ms, P2: {:.1}ms)  ", 
+        print!("  Run {}: {:.1}ms (probe: {:.1}ms, P1: {:.1}ms, P2: {:.1}ms, migrated {} to P, {} to E)  ", 
             i, metrics.total_time_ms, metrics.probe_time_ms, 
-            metrics.phase1_time_ms, metrics.phase2_time_ms);
+            metrics.phase1_time_ms, metrics.phase2_time_ms,
+            metrics.migrated_to_p, metrics.migrated_to_e);
         if i % 2 == 0 { println!(); }
     }
     if runs % 2 != 0 { println!(); }
@@ -996,7 +1282,12 @@ fn benchmark_two_phase(board: &mut Board, depth: u32, warmup: usize, runs: usize
     let sps = 1000.0 / median_total;
     
     TwoPhaseBenchmarkResult {
-        config_name: format!("two_phase_p{}_r{}", probe_depth, (heavy_ratio * 10.0) as u8),
+        config_name: format!(
+            "two_phase_p{}_r{}{}",
+            probe_depth,
+            (heavy_ratio * 10.0) as u8,
+            if classification.adaptive { "" } else { "_static" }
+        ),
         position_name: pos_name.to_string(),
         probe_depth,
         heavy_ratio,
@@ -1048,7 +1339,7 @@ fn export_two_phase_csv(results: &[TwoPhaseBenchmarkResult], path: &str, depth:
     let mut file = std::fs::File::create(path).expect("Failed to create CSV file");
     let ts = chrono::Local::now().format("%Y-%m-%d_%H:%M:%S").to_string();
     
-    writeln!(file, "timestamp,depth,position,config,probe_depth,heavy_ratio,median_total_ms,median_probe_ms,median_phase1_ms,median_phase2_ms,stddev_ms,searches_per_sec,speedup,heavy_count,light_count").unwrap();
+    writeln!(file, "timestamp,depth,position,config,probe_depth,heavy_ratio,median_total_ms,median_probe_ms,median_phase1_ms,median_phase2_ms,stddev_ms,searches_per_sec,speedup,heavy_count,light_count,tt_hit_rate,tt_collisions,nodes_per_search,migrated_to_p,migrated_to_e").unwrap();
     
     for r in results {
         let times: Vec<f64> = r.samples.iter().map(|s| s.total_time_ms).collect();
@@ -1058,11 +1349,21 @@ fn export_two_phase_csv(results: &[TwoPhaseBenchmarkResult], path: &str, depth:
         } else {
             (0, 0)
         };
+        let mut tt = TTStats::default();
+        for sample in &r.samples {
+            tt.add(&sample.tt_stats);
+        }
+        let nodes = r.samples.iter().map(|s| s.nodes).sum::<u64>() as f64 / r.samples.len().max(1) as f64;
+        let mean = |count: fn(&TwoPhaseMetrics) -> usize| {
+            r.samples.iter().map(count).sum::<usize>() as f64 / r.samples.len().max(1) as f64
+        };
         
-        writeln!(file, "{},{},{},{},{},{:.2},{:.3},{:.3},{:.3},{:.3},{:.3},{:.2},{:.3},{},{}",
+        writeln!(file, "{},{},{},{},{},{:.2},{:.3},{:.3},{:.3},{:.3},{:.3},{:.2},{:.3},{},{},{:.4},{},{:.0},{:.1},{:.1}",
             ts, depth, r.position_name, r.config_name, r.probe_depth, r.heavy_ratio,
             r.median_total_ms, r.median_probe_ms, r.median_phase1_ms, r.median_phase2_ms,
-            stddev, r.searches_per_second, r.speedup_vs_baseline, heavy, light
+            stddev, r.searches_per_second, r.speedup_vs_baseline, heavy, light,
+            tt.hit_rate(), tt.collisions, nodes,
+            mean(|s| s.migrated_to_p), mean(|s| s.migrated_to_e)
         ).unwrap();
     }
     
@@ -1,8 +1,10 @@
 pub mod cli;
 pub mod commands;
+pub mod uci;
 
 pub use cli::{parse_args, Cli};
 pub use commands::{
     run_fault_overhead_analysis, run_full_benchmark, run_perft_test, run_single_search,
     run_soak_test,
 };
+pub use uci::run_uci;
@@ -0,0 +1,386 @@
+//! UCI front-end, so devi can be driven by a GUI or match runner. Commands are
+//! read on the calling thread; `go` starts the search on a background thread,
+//! which streams `info` lines and finishes with `bestmove`. The table is kept
+//! between moves until `ucinewgame`.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::EvalParams;
+use crate::moves::generate_legal_moves;
+use crate::scheduling::CorePolicy;
+use crate::search::iterative::{pv_string, IterationInfo, SearchResult};
+use crate::search::minimax::{iterative_search, mate_distance, SearchConfig, SharedSearch};
+use crate::search::parallel::{parallel_iterative_search_with_mode, ParallelMode};
+use crate::search::selectivity::SelectivityConfig;
+use crate::search::time_control::{TimeControl, TimeManager};
+use crate::search::transposition::{TranspositionTable, DEFAULT_TT_MB};
+use crate::types::*;
+use clap::ValueEnum;
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const MAX_THREADS: usize = 256;
+const MAX_HASH_MB: usize = 4096;
+// Fast-core share for the mixed policy, as `--mixed-ratio` defaults to
+const MIXED_RATIO: f32 = 0.80;
+
+/// Where the engine writes its replies. Shared with the search thread.
+pub type Output = Arc<Mutex<Box<dyn Write + Send>>>;
+
+/// Settings changed with `setoption`.
+#[derive(Clone, Copy, Debug)]
+pub struct UciOptions {
+    pub threads: usize,
+    pub hash_mb: usize,
+    pub core_policy: CorePolicy,
+    pub parallel_mode: ParallelMode,
+    /// Older switch for `ParallelMode` two-phase; either selects it
+    pub two_phase: bool,
+    /// Null-move pruning, LMR, futility pruning and check extensions. On for
+    /// play, where strength matters more than matching a full-width search
+    pub selective: bool,
+    /// Weights from the `EvalParams` file, or the built-in ones
+    pub eval: EvalParams,
+}
+
+impl Default for UciOptions {
+    fn default() -> Self {
+        Self {
+            threads: 1,
+            hash_mb: DEFAULT_TT_MB,
+            core_policy: CorePolicy::None,
+            parallel_mode: ParallelMode::RootSplit,
+            two_phase: false,
+            selective: true,
+            eval: EvalParams::default(),
+        }
+    }
+}
+
+/// Parsed `go` command.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GoCommand {
+    pub control: TimeControl,
+    /// Search until `stop`, and only then report the best move
+    pub infinite: bool,
+}
+
+struct RunningSearch {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+pub struct UciEngine {
+    board: Board,
+    options: UciOptions,
+    tt: Arc<TranspositionTable>,
+    out: Output,
+    search: Option<RunningSearch>,
+}
+
+impl UciEngine {
+    pub fn new(out: Box<dyn Write + Send>) -> Self {
+        let options = UciOptions::default();
+        UciEngine {
+            board: Board::from_fen(STARTPOS).expect("start position"),
+            options,
+            tt: Arc::new(TranspositionTable::new(options.hash_mb)),
+            out: Arc::new(Mutex::new(out)),
+            search: None,
+        }
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn options(&self) -> &UciOptions {
+        &self.options
+    }
+
+    /// Handle one line of input. Returns false once the engine should quit.
+    pub fn handle(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        let (command, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let args = args.trim();
+
+        match command {
+            "uci" => self.identify(),
+            "isready" => send(&self.out, "readyok"),
+            "ucinewgame" => {
+                self.stop_search();
+                self.tt.clear();
+                self.board = Board::from_fen(STARTPOS).expect("start position");
+            }
+            "setoption" => {
+                self.stop_search();
+                if let Err(e) = self.set_option(args) {
+                    send(&self.out, &format!("info string {}", e));
+                }
+            }
+            "position" => {
+                self.stop_search();
+                match parse_position(args) {
+                    Ok(board) => self.board = board,
+                    Err(e) => send(&self.out, &format!("info string {}", e)),
+                }
+            }
+            "go" => match parse_go(args) {
+                Ok(go) => self.go(go),
+                Err(e) => send(&self.out, &format!("info string {}", e)),
+            },
+            "stop" => self.stop_search(),
+            "quit" => {
+                self.stop_search();
+                return false;
+            }
+            // debug, register, ponderhit and anything unknown
+            _ => {}
+        }
+        true
+    }
+
+    /// Block until the running search, if any, has sent its best move.
+    pub fn wait(&mut self) {
+        if let Some(search) = self.search.take() {
+            let _ = search.handle.join();
+        }
+    }
+
+    fn stop_search(&mut self) {
+        if let Some(search) = &self.search {
+            search.stop.store(true, Ordering::Relaxed);
+        }
+        self.wait();
+    }
+
+    fn identify(&self) {
+        let lines = [
+            format!("id name devi {}", env!("CARGO_PKG_VERSION")),
+            format!("id author {}", env!("CARGO_PKG_AUTHORS")),
+            format!("option name Threads type spin default 1 min 1 max {}", MAX_THREADS),
+            format!("option name Hash type spin default {} min 1 max {}", DEFAULT_TT_MB, MAX_HASH_MB),
+            "option name CorePolicy type combo default none var none var fast var efficient var mixed".to_string(),
+            "option name ParallelMode type combo default root-split var root-split var lazy-smp var two-phase var ybwc var jamboree var abdada".to_string(),
+            "option name TwoPhase type check default false".to_string(),
+            "option name Selective type check default true".to_string(),
+            "option name EvalParams type string default <empty>".to_string(),
+            "uciok".to_string(),
+        ];
+        for line in lines {
+            send(&self.out, &line);
+        }
+    }
+
+    fn set_option(&mut self, args: &str) -> Result<(), String> {
+        let rest = args.strip_prefix("name").ok_or("setoption without name")?.trim();
+        let (name, value) = match rest.split_once(" value") {
+            Some((name, value)) => (name.trim(), value.trim()),
+            None => (rest, ""),
+        };
+
+        match name.to_ascii_lowercase().as_str() {
+            "threads" => {
+                let threads: usize = value.parse().map_err(|_| format!("bad Threads value '{}'", value))?;
+                self.options.threads = threads.clamp(1, MAX_THREADS);
+            }
+            "hash" => {
+                let mb: usize = value.parse().map_err(|_| format!("bad Hash value '{}'", value))?;
+                self.options.hash_mb = mb.clamp(1, MAX_HASH_MB);
+                self.tt = Arc::new(TranspositionTable::new(self.options.hash_mb));
+            }
+            "corepolicy" => {
+                self.options.core_policy =
+                    CorePolicy::from_str(value, true).map_err(|_| format!("bad CorePolicy value '{}'", value))?;
+            }
+            "parallelmode" => {
+                self.options.parallel_mode =
+                    ParallelMode::from_str(value, true).map_err(|_| format!("bad ParallelMode value '{}'", value))?;
+            }
+            "twophase" => {
+                self.options.two_phase = value.parse().map_err(|_| format!("bad TwoPhase value '{}'", value))?;
+            }
+            "selective" => {
+                self.options.selective = value.parse().map_err(|_| format!("bad Selective value '{}'", value))?;
+            }
+            "evalparams" => {
+                self.options.eval = match value {
+                    "" | "<empty>" => EvalParams::default(),
+                    path => EvalParams::load(path)?,
+                };
+                // Stored scores came from the old weights
+                self.tt.clear();
+            }
+            _ => return Err(format!("unknown option '{}'", name)),
+        }
+        Ok(())
+    }
+
+    fn go(&mut self, go: GoCommand) {
+        self.stop_search();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut board = self.board;
+        let options = self.options;
+        let out = Arc::clone(&self.out);
+
+        let config = SearchConfig {
+            tt_size_mb: options.hash_mb,
+            selectivity: if options.selective {
+                SelectivityConfig::all()
+            } else {
+                SelectivityConfig::default()
+            },
+            eval: options.eval,
+            ..SearchConfig::default()
+        };
+        let info_out = Arc::clone(&self.out);
+        let shared = SharedSearch::with_table(config, Arc::clone(&self.tt))
+            .with_stop(Arc::clone(&stop))
+            .with_time(TimeManager::new(&go.control, board.to_move()))
+            .with_reporter(Box::new(move |info| send(&info_out, &info_line(info))));
+        shared.tt.reset_stats();
+
+        let stop_flag = Arc::clone(&stop);
+        let handle = thread::spawn(move || {
+            let mode = if options.two_phase {
+                ParallelMode::TwoPhase
+            } else {
+                options.parallel_mode
+            };
+            let result = if options.threads > 1 || mode == ParallelMode::TwoPhase {
+                parallel_iterative_search_with_mode(&mut board, mode, options.core_policy, options.threads, MIXED_RATIO, &shared)
+            } else {
+                iterative_search(&mut board, &shared)
+            };
+
+            // An infinite search reports only when told to stop
+            if go.infinite {
+                while !stop_flag.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+            send(&out, &bestmove_line(&mut board, &result));
+        });
+
+        self.search = Some(RunningSearch { stop, handle });
+    }
+}
+
+/// Read UCI commands from stdin until `quit` or end of input, evaluating
+/// with `eval` until the GUI sets `EvalParams`.
+pub fn run_uci(eval: EvalParams) {
+    let mut engine = UciEngine::new(Box::new(io::stdout()));
+    engine.options.eval = eval;
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        if !engine.handle(&line) {
+            return;
+        }
+    }
+    engine.stop_search();
+}
+
+/// Board for the arguments of `position`: `startpos` or `fen <fen>`, then
+/// optionally `moves <move>...` in coordinate notation.
+pub fn parse_position(args: &str) -> Result<Board, String> {
+    let (setup, moves) = match args.split_once("moves") {
+        Some((setup, moves)) => (setup.trim(), moves.trim()),
+        None => (args.trim(), ""),
+    };
+
+    let mut board = if setup == "startpos" {
+        Board::from_fen(STARTPOS)?
+    } else if let Some(fen) = setup.strip_prefix("fen") {
+        Board::from_fen(fen.trim())?
+    } else {
+        return Err(format!("bad position '{}'", args));
+    };
+
+    for text in moves.split_whitespace() {
+        let mv = parse_move(&mut board, text).ok_or_else(|| format!("illegal move '{}'", text))?;
+        board.make_move(&mv);
+    }
+    Ok(board)
+}
+
+/// The legal move written as `text` (`e2e4`, `e7e8q`), if there is one.
+pub fn parse_move(board: &mut Board, text: &str) -> Option<Move> {
+    let side = board.to_move();
+    generate_legal_moves(board, side)
+        .into_iter()
+        .find(|mv| mv.to_algebraic() == text)
+}
+
+/// Limits from the arguments of `go`. `ponder`, `searchmoves` and `mate` are
+/// not supported and are ignored.
+pub fn parse_go(args: &str) -> Result<GoCommand, String> {
+    let mut go = GoCommand::default();
+    let mut tokens = args.split_whitespace();
+
+    while let Some(token) = tokens.next() {
+        let control = &mut go.control;
+        match token {
+            "infinite" => go.infinite = true,
+            "wtime" => control.wtime = Some(parse_value(token, tokens.next())?),
+            "btime" => control.btime = Some(parse_value(token, tokens.next())?),
+            "winc" => control.winc = Some(parse_value(token, tokens.next())?),
+            "binc" => control.binc = Some(parse_value(token, tokens.next())?),
+            "movestogo" => control.movestogo = Some(parse_value(token, tokens.next())?),
+            "movetime" => control.movetime = Some(parse_value(token, tokens.next())?),
+            "depth" => control.depth = Some(parse_value(token, tokens.next())?),
+            "nodes" => control.nodes = Some(parse_value(token, tokens.next())?),
+            _ => {}
+        }
+    }
+    Ok(go)
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: Option<&str>) -> Result<T, String> {
+    // Some GUIs send negative clocks when flagging; treat them as zero
+    let value = value.ok_or_else(|| format!("go {} without a value", name))?;
+    let value = if value.starts_with('-') { "0" } else { value };
+    value.parse().map_err(|_| format!("bad go {} value '{}'", name, value))
+}
+
+/// `cp <centipawns>` or `mate <moves>`, negative when devi is getting mated.
+pub fn format_score(score: i32) -> String {
+    match mate_distance(score) {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", score),
+    }
+}
+
+fn info_line(info: &IterationInfo) -> String {
+    let ms = info.elapsed.as_millis();
+    let nps = (info.total_nodes as f64 / info.elapsed.as_secs_f64().max(1e-9)) as u64;
+    format!(
+        "info depth {} score {} nodes {} nps {} time {} pv {}",
+        info.depth,
+        format_score(info.score),
+        info.total_nodes,
+        nps,
+        ms,
+        pv_string(&info.pv)
+    )
+}
+
+fn bestmove_line(board: &mut Board, result: &SearchResult) -> String {
+    let side = board.to_move();
+    if generate_legal_moves(board, side).is_empty() {
+        return "bestmove 0000".to_string();
+    }
+    match result.pv.get(1) {
+        Some(ponder) => format!("bestmove {} ponder {}", result.best_move.to_algebraic(), ponder.to_algebraic()),
+        None => format!("bestmove {}", result.best_move.to_algebraic()),
+    }
+}
+
+fn send(out: &Output, line: &str) {
+    let mut out = out.lock().unwrap();
+    let _ = writeln!(out, "{}", line);
+    let _ = out.flush();
+}
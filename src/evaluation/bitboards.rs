@@ -0,0 +1,95 @@
+//! Helpers shared by the evaluation terms: bitboards, bit n for square n
+//! (a1 = 0), square distances and colour and piece indices.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::types::*;
+
+/// White and Black pawns.
+pub(super) fn pawn_bitboards(board: &Board) -> [u64; 2] {
+    let mut pawns = [0u64; 2];
+    for idx in 0..64 {
+        if let Some(piece) = board.get_piece(Square(idx)) {
+            if piece.piece_type == PieceType::Pawn {
+                pawns[side_index(piece.color)] |= 1 << idx;
+            }
+        }
+    }
+    pawns
+}
+
+pub(super) fn squares(mut bb: u64) -> impl Iterator<Item = u8> {
+    std::iter::from_fn(move || {
+        (bb != 0).then(|| {
+            let sq = bb.trailing_zeros() as u8;
+            bb &= bb - 1;
+            sq
+        })
+    })
+}
+
+pub(super) fn file_mask(file: u8) -> u64 {
+    0x0101_0101_0101_0101 << file
+}
+
+pub(super) fn rank_mask(rank: u8) -> u64 {
+    0xFF << (8 * rank)
+}
+
+pub(super) fn adjacent_files(file: u8) -> u64 {
+    let left = if file > 0 { file_mask(file - 1) } else { 0 };
+    let right = if file < 7 { file_mask(file + 1) } else { 0 };
+    left | right
+}
+
+/// Squares a `color` pawn on `sq` attacks.
+pub(super) fn pawn_attacks(sq: u8, color: Color) -> u64 {
+    let Some(ahead) = stop_square(sq, color) else { return 0 };
+    adjacent_files(sq % 8) & rank_mask(ahead / 8)
+}
+
+pub(super) fn stop_square(sq: u8, color: Color) -> Option<u8> {
+    match color {
+        Color::White if sq < 56 => Some(sq + 8),
+        Color::Black if sq >= 8 => Some(sq - 8),
+        _ => None,
+    }
+}
+
+/// King moves between two squares.
+pub(super) fn distance(a: u8, b: u8) -> i32 {
+    let files = (a % 8).abs_diff(b % 8);
+    let ranks = (a / 8).abs_diff(b / 8);
+    files.max(ranks) as i32
+}
+
+pub(super) fn relative_rank(sq: u8, color: Color) -> usize {
+    match color {
+        Color::White => (sq / 8) as usize,
+        Color::Black => (7 - sq / 8) as usize,
+    }
+}
+
+pub(super) fn side_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+pub(super) fn piece_index(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    }
+}
+
+pub(super) fn flip(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
@@ -0,0 +1,383 @@
+//! Endgame knowledge. Dead draws are scored 0, bishop endings are scaled
+//! toward a draw, and a few won endings get their own evaluators so the
+//! search knows how to make progress: KPK from a bitbase, and KQK, KRK and
+//! KBNK by driving the lone king to the edge or the bishop's corner.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::bitboards::{distance, piece_index, relative_rank, side_index};
+use crate::evaluation::material::piece_value;
+use crate::types::*;
+use once_cell::sync::Lazy;
+
+/// Base score of a won ending, well above any material balance and well
+/// below the mate scores.
+pub const KNOWN_WIN: i32 = 10_000;
+
+/// Scale factor that leaves an evaluation unchanged.
+pub const SCALE_NORMAL: i32 = 64;
+
+// Same-side pawns with opposite-coloured bishops are hard to convert
+const SCALE_OPPOSITE_BISHOPS: i32 = 32;
+
+/// Pieces on the board, gathered in one pass.
+struct Material {
+    counts: [[u8; 6]; 2],
+    kings: [Square; 2],
+    /// Square of each side's last bishop and pawn found
+    bishop: [Option<Square>; 2],
+    pawn: [Option<Square>; 2],
+    /// Bishops on light and dark squares, both sides together
+    bishop_colors: [u8; 2],
+}
+
+impl Material {
+    fn of(board: &Board) -> Self {
+        let mut material = Material {
+            counts: [[0; 6]; 2],
+            kings: [Square(0); 2],
+            bishop: [None; 2],
+            pawn: [None; 2],
+            bishop_colors: [0; 2],
+        };
+        for idx in 0..64 {
+            let square = Square(idx);
+            if let Some(piece) = board.get_piece(square) {
+                let side = side_index(piece.color);
+                material.counts[side][piece_index(piece.piece_type)] += 1;
+                match piece.piece_type {
+                    PieceType::King => material.kings[side] = square,
+                    PieceType::Bishop => {
+                        material.bishop[side] = Some(square);
+                        material.bishop_colors[square_color(square)] += 1;
+                    }
+                    PieceType::Pawn => material.pawn[side] = Some(square),
+                    _ => {}
+                }
+            }
+        }
+        material
+    }
+
+    fn count(&self, side: usize, piece_type: PieceType) -> u8 {
+        self.counts[side][piece_index(piece_type)]
+    }
+
+    /// Whether `side` has exactly the non-king pieces in `pieces` (by type,
+    /// in `PieceType` order without the king).
+    fn has_exactly(&self, side: usize, pieces: [u8; 5]) -> bool {
+        self.counts[side][..5] == pieces
+    }
+
+    fn is_bare(&self, side: usize) -> bool {
+        self.has_exactly(side, [0; 5])
+    }
+
+    fn minors_and_majors(&self, side: usize) -> u8 {
+        self.counts[side][1..5].iter().sum()
+    }
+}
+
+/// Score of a recognised ending from White's point of view, or `None` if the
+/// general evaluation applies.
+pub fn evaluate(board: &Board) -> Option<i32> {
+    let material = Material::of(board);
+    if is_dead_draw(&material) {
+        return Some(0);
+    }
+
+    for strong in 0..2 {
+        let weak = 1 - strong;
+        if !material.is_bare(weak) {
+            continue;
+        }
+        let sign = if strong == 0 { 1 } else { -1 };
+
+        let score = if material.has_exactly(strong, [1, 0, 0, 0, 0]) {
+            evaluate_kpk(board, &material, strong)
+        } else if material.has_exactly(strong, [0, 0, 0, 0, 1]) || material.has_exactly(strong, [0, 0, 0, 1, 0]) {
+            mop_up(&material, strong, None)
+        } else if material.has_exactly(strong, [0, 1, 1, 0, 0]) {
+            mop_up(&material, strong, material.bishop[strong])
+        } else {
+            continue;
+        };
+        return Some(sign * score);
+    }
+    None
+}
+
+/// Whether neither side can possibly mate: KK, KBK, KNK, KNNK, and bishops
+/// only, all on squares of one colour.
+pub fn is_insufficient_material(board: &Board) -> bool {
+    is_dead_draw(&Material::of(board))
+}
+
+/// Out of `SCALE_NORMAL`, how much of the general evaluation to keep.
+pub fn scale_factor(board: &Board) -> i32 {
+    let material = Material::of(board);
+    let bishops_only = (0..2).all(|side| material.has_exactly(side, [material.count(side, PieceType::Pawn), 0, 1, 0, 0]));
+    if bishops_only && material.bishop_colors[0] == 1 && material.bishop_colors[1] == 1 {
+        SCALE_OPPOSITE_BISHOPS
+    } else {
+        SCALE_NORMAL
+    }
+}
+
+fn is_dead_draw(material: &Material) -> bool {
+    let pawns = material.count(0, PieceType::Pawn) + material.count(1, PieceType::Pawn);
+    let majors = (0..2)
+        .map(|side| material.count(side, PieceType::Rook) + material.count(side, PieceType::Queen))
+        .sum::<u8>();
+    if pawns > 0 || majors > 0 {
+        return false;
+    }
+
+    let knights = [material.count(0, PieceType::Knight), material.count(1, PieceType::Knight)];
+    let bishops = [material.count(0, PieceType::Bishop), material.count(1, PieceType::Bishop)];
+    let minors = [material.minors_and_majors(0), material.minors_and_majors(1)];
+
+    // A lone minor, or two knights against a bare king
+    for side in 0..2 {
+        if minors[1 - side] == 0 && (minors[side] <= 1 || (knights[side] == 2 && bishops[side] == 0)) {
+            return true;
+        }
+    }
+
+    // Bishops that all run on one colour can't cover a mating net
+    knights == [0, 0] && (material.bishop_colors[0] == 0 || material.bishop_colors[1] == 0)
+}
+
+fn evaluate_kpk(board: &Board, material: &Material, strong: usize) -> i32 {
+    let pawn = material.pawn[strong].expect("KPK has a pawn");
+    let strong_color = if strong == 0 { Color::White } else { Color::Black };
+    let strong_to_move = board.to_move() == strong_color;
+    if !kpk_is_win(material.kings[strong], material.kings[1 - strong], pawn, strong_color, strong_to_move) {
+        return 0;
+    }
+    let rank = relative_rank(pawn.0, strong_color) as i32;
+    KNOWN_WIN + piece_value(PieceType::Pawn) + 10 * rank
+}
+
+/// KQK, KRK and KBNK: drive the weak king to the edge, or with a lone
+/// bishop to a corner of its colour, and bring the strong king closer.
+fn mop_up(material: &Material, strong: usize, bishop: Option<Square>) -> i32 {
+    let strong_king = material.kings[strong];
+    let weak_king = material.kings[1 - strong];
+
+    let material_score: i32 = (0..5)
+        .map(|kind| material.counts[strong][kind] as i32 * piece_value(PIECE_TYPES[kind]))
+        .sum();
+    let edge = match bishop {
+        Some(bishop) => {
+            let corners = if square_color(bishop) == DARK { [Square(0), Square(63)] } else { [Square(7), Square(56)] };
+            let nearest = corners.iter().map(|&c| manhattan(weak_king, c)).min().unwrap();
+            20 * (14 - nearest)
+        }
+        None => 20 * center_distance(weak_king),
+    };
+    let closeness = 10 * (7 - distance(strong_king.0, weak_king.0));
+
+    KNOWN_WIN + material_score + edge + closeness
+}
+
+// KPK bitbase. Positions are stored with the pawn's side as White and the
+// pawn on files a-d; anything else is mirrored into that form first.
+
+const KPK_SIZE: usize = 24 * 64 * 64 * 2;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KpkResult {
+    Invalid,
+    Unknown,
+    Draw,
+    Win,
+}
+
+static KPK_BITBASE: Lazy<Vec<bool>> = Lazy::new(generate_kpk);
+
+/// Whether `strong`, with `strong_king` and `pawn` against `weak_king`,
+/// wins KPK.
+pub fn kpk_is_win(strong_king: Square, weak_king: Square, pawn: Square, strong: Color, strong_to_move: bool) -> bool {
+    // Turn the strong side into White ...
+    let flip = |sq: Square| match strong {
+        Color::White => sq.0,
+        Color::Black => sq.0 ^ 56,
+    };
+    let (mut wk, mut bk, mut p) = (flip(strong_king), flip(weak_king), flip(pawn));
+    // ... with the pawn on the queenside
+    if p % 8 > 3 {
+        wk ^= 7;
+        bk ^= 7;
+        p ^= 7;
+    }
+    KPK_BITBASE[kpk_index(wk, bk, p, strong_to_move)]
+}
+
+fn kpk_index(wk: u8, bk: u8, pawn: u8, white_to_move: bool) -> usize {
+    let pawn_idx = (pawn / 8 - 1) as usize * 4 + (pawn % 8) as usize;
+    ((pawn_idx * 64 + wk as usize) * 64 + bk as usize) * 2 + white_to_move as usize
+}
+
+fn generate_kpk() -> Vec<bool> {
+    let mut table = vec![KpkResult::Invalid; KPK_SIZE];
+    for pawn in kpk_pawn_squares() {
+        for wk in 0..64 {
+            for bk in 0..64 {
+                for white_to_move in [false, true] {
+                    table[kpk_index(wk, bk, pawn, white_to_move)] = kpk_initial(wk, bk, pawn, white_to_move);
+                }
+            }
+        }
+    }
+
+    // Retrograde passes until nothing changes
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for pawn in kpk_pawn_squares() {
+            for wk in 0..64 {
+                for bk in 0..64 {
+                    for white_to_move in [false, true] {
+                        let idx = kpk_index(wk, bk, pawn, white_to_move);
+                        if table[idx] != KpkResult::Unknown {
+                            continue;
+                        }
+                        let result = if white_to_move {
+                            kpk_white_result(&table, wk, bk, pawn)
+                        } else {
+                            kpk_black_result(&table, wk, bk, pawn)
+                        };
+                        if result != KpkResult::Unknown {
+                            table[idx] = result;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Whatever White can't force is a draw
+    table.into_iter().map(|r| r == KpkResult::Win).collect()
+}
+
+fn kpk_pawn_squares() -> impl Iterator<Item = u8> {
+    (1..7).flat_map(|rank| (0..4).map(move |file| rank * 8 + file))
+}
+
+fn kpk_initial(wk: u8, bk: u8, pawn: u8, white_to_move: bool) -> KpkResult {
+    if wk == bk || wk == pawn || bk == pawn || distance(wk, bk) <= 1 {
+        return KpkResult::Invalid;
+    }
+    let checked = pawn_attacks(pawn, bk);
+    if white_to_move {
+        if checked {
+            return KpkResult::Invalid;
+        }
+        // Promotes and the new queen can't be taken
+        let queen = pawn + 8;
+        if pawn / 8 == 6 && wk != queen && bk != queen && (distance(bk, queen) > 1 || distance(wk, queen) == 1) {
+            return KpkResult::Win;
+        }
+    } else {
+        let mut moves = black_king_moves(wk, bk, pawn).peekable();
+        if moves.peek().is_none() {
+            return if checked { KpkResult::Win } else { KpkResult::Draw };
+        }
+        if moves.any(|to| to == pawn) {
+            return KpkResult::Draw;
+        }
+    }
+    KpkResult::Unknown
+}
+
+fn kpk_white_result(table: &[KpkResult], wk: u8, bk: u8, pawn: u8) -> KpkResult {
+    let mut unknown = false;
+    let mut visit = |result: KpkResult| {
+        unknown |= result == KpkResult::Unknown;
+        result == KpkResult::Win
+    };
+
+    for to in king_targets(wk) {
+        if to != pawn && distance(to, bk) > 1 && visit(table[kpk_index(to, bk, pawn, false)]) {
+            return KpkResult::Win;
+        }
+    }
+    // Pushes to the 8th rank are settled by `kpk_initial`
+    let push = pawn + 8;
+    if pawn / 8 < 6 && push != wk && push != bk {
+        if visit(table[kpk_index(wk, bk, push, false)]) {
+            return KpkResult::Win;
+        }
+        let double = push + 8;
+        if pawn / 8 == 1 && double != wk && double != bk && visit(table[kpk_index(wk, bk, double, false)]) {
+            return KpkResult::Win;
+        }
+    }
+
+    if unknown {
+        KpkResult::Unknown
+    } else {
+        KpkResult::Draw
+    }
+}
+
+fn kpk_black_result(table: &[KpkResult], wk: u8, bk: u8, pawn: u8) -> KpkResult {
+    let mut unknown = false;
+    for to in black_king_moves(wk, bk, pawn) {
+        match table[kpk_index(wk, to, pawn, true)] {
+            KpkResult::Draw => return KpkResult::Draw,
+            KpkResult::Unknown => unknown = true,
+            _ => {}
+        }
+    }
+
+    if unknown {
+        KpkResult::Unknown
+    } else {
+        KpkResult::Win
+    }
+}
+
+/// Legal black king moves, capturing the pawn included.
+fn black_king_moves(wk: u8, bk: u8, pawn: u8) -> impl Iterator<Item = u8> {
+    king_targets(bk).filter(move |&to| distance(to, wk) > 1 && !pawn_attacks(pawn, to))
+}
+
+fn king_targets(from: u8) -> impl Iterator<Item = u8> {
+    let (file, rank) = ((from % 8) as i8, (from / 8) as i8);
+    KING_STEPS.iter().filter_map(move |&(df, dr)| {
+        let (f, r) = (file + df, rank + dr);
+        ((0..8).contains(&f) && (0..8).contains(&r)).then_some((r * 8 + f) as u8)
+    })
+}
+
+const KING_STEPS: [(i8, i8); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// Whether a white pawn on `pawn` attacks `square`.
+fn pawn_attacks(pawn: u8, square: u8) -> bool {
+    square / 8 == pawn / 8 + 1 && (square % 8).abs_diff(pawn % 8) == 1
+}
+
+// Helpers
+
+const PIECE_TYPES: [PieceType; 5] =
+    [PieceType::Pawn, PieceType::Knight, PieceType::Bishop, PieceType::Rook, PieceType::Queen];
+
+const DARK: usize = 0;
+
+/// `DARK` for a1, h8 and the rest of their colour, 1 for light squares.
+fn square_color(square: Square) -> usize {
+    ((square.0 / 8 + square.0 % 8) % 2 == 1) as usize
+}
+
+fn manhattan(a: Square, b: Square) -> i32 {
+    ((a.0 % 8).abs_diff(b.0 % 8) + (a.0 / 8).abs_diff(b.0 / 8)) as i32
+}
+
+/// 0 on the four centre squares, 6 in the corners.
+fn center_distance(square: Square) -> i32 {
+    let file = (square.0 % 8) as i32;
+    let rank = (square.0 / 8) as i32;
+    (3 - file).max(file - 4) + (3 - rank).max(rank - 4)
+}
@@ -0,0 +1,135 @@
+//! King safety: the pawn shield in front of the king, open files beside it
+//! and enemy attacks on the squares around it. These are middlegame concerns,
+//! so the terms carry little or no endgame weight.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::bitboards::{adjacent_files, file_mask, flip, pawn_bitboards, rank_mask, side_index};
+use crate::evaluation::score::Score;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+
+// Attack units are squared and divided by this, so lone attackers barely count
+const ATTACK_DIVISOR: i32 = 64;
+
+/// Which king safety terms are scored, and their weights.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KingSafetyConfig {
+    pub pawn_shield: bool,
+    pub open_files: bool,
+    pub king_attack: bool,
+    /// Per own pawn one and two ranks in front of the king, on its file or a neighbour
+    pub shield: [Score; 2],
+    /// Per file at the king with no pawns
+    pub open_file: Score,
+    /// Per file at the king with only enemy pawns
+    pub half_open_file: Score,
+    /// Attack units per enemy attack on a square of the king zone
+    pub zone_attack: i32,
+    /// Extra units per attack the square's defenders (king aside) don't match
+    pub zone_overload: i32,
+}
+
+impl Default for KingSafetyConfig {
+    fn default() -> Self {
+        Self {
+            pawn_shield: true,
+            open_files: true,
+            king_attack: true,
+            shield: [Score::new(18, 0), Score::new(8, 0)],
+            open_file: Score::new(-22, 0),
+            half_open_file: Score::new(-11, 0),
+            zone_attack: 4,
+            zone_overload: 6,
+        }
+    }
+}
+
+/// One side's king safety terms, positive when they favour that side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct KingSafetyTerms {
+    pub pawn_shield: Score,
+    pub open_files: Score,
+    pub king_attack: Score,
+}
+
+impl KingSafetyTerms {
+    pub fn total(&self) -> Score {
+        self.pawn_shield + self.open_files + self.king_attack
+    }
+}
+
+/// King safety score, positive when White's king is the safer.
+pub fn evaluate(board: &Board, config: &KingSafetyConfig) -> Score {
+    let [white, black] = breakdown(board, config);
+    white.total() - black.total()
+}
+
+/// King safety terms of White and Black.
+pub fn breakdown(board: &Board, config: &KingSafetyConfig) -> [KingSafetyTerms; 2] {
+    let pawns = pawn_bitboards(board);
+    let mut terms = [KingSafetyTerms::default(); 2];
+    for color in [Color::White, Color::Black] {
+        let Some(king) = board.find_king(color) else { continue };
+        let side = side_index(color);
+        let own = pawns[side];
+        let enemy = pawns[1 - side];
+        let file = king.0 % 8;
+        let files = file_mask(file) | adjacent_files(file);
+        let terms = &mut terms[side];
+
+        if config.pawn_shield {
+            for (distance, &bonus) in (1..=2).zip(config.shield.iter()) {
+                let rank = match color {
+                    Color::White => king.0 / 8 + distance,
+                    Color::Black => (king.0 / 8).wrapping_sub(distance),
+                };
+                if rank < 8 {
+                    let shield = own & files & rank_mask(rank);
+                    terms.pawn_shield += bonus * shield.count_ones() as i32;
+                }
+            }
+        }
+
+        if config.open_files {
+            for f in file.saturating_sub(1)..=(file + 1).min(7) {
+                if own & file_mask(f) != 0 {
+                    continue;
+                }
+                terms.open_files += match enemy & file_mask(f) {
+                    0 => config.open_file,
+                    _ => config.half_open_file,
+                };
+            }
+        }
+
+        if config.king_attack {
+            terms.king_attack = king_attack(board, king, color, config);
+        }
+    }
+    terms
+}
+
+/// Penalty for enemy pressure on the king and the squares next to it,
+/// counted with `count_attackers`. Grows with the square of the attack
+/// units, so several pieces joining in weigh far more than one.
+fn king_attack(board: &Board, king: Square, color: Color, config: &KingSafetyConfig) -> Score {
+    let (file, rank) = ((king.0 % 8) as i8, (king.0 / 8) as i8);
+    let mut units = 0;
+    for df in -1..=1 {
+        for dr in -1..=1 {
+            let (f, r) = (file + df, rank + dr);
+            if !(0..8).contains(&f) || !(0..8).contains(&r) {
+                continue;
+            }
+            let square = Square((r * 8 + f) as u8);
+            let attackers = board.count_attackers(square, flip(color)) as i32;
+            if attackers == 0 {
+                continue;
+            }
+            let defenders = board.count_attackers(square, color) as i32;
+            units += attackers * config.zone_attack + (attackers - defenders).max(0) * config.zone_overload;
+        }
+    }
+    Score::new(-units * units / ATTACK_DIVISOR, 0)
+}
@@ -10,27 +10,50 @@ const PIECE_VALUES: [i32; 6] = [
     20000, //KING
 ];
 
-pub fn evaluate(board: &Board) -> i32 {
-    let mut score = 0;
+/// Material balance in centipawns, positive when White is ahead.
+pub fn evaluate(board: &Board, values: &[i32; 5]) -> i32 {
+    let [white, black] = breakdown(board, values);
+    white - black
+}
+
+/// Material of White and Black, with `values` for pawn to queen. Kings
+/// count nothing.
+pub fn breakdown(board: &Board, values: &[i32; 5]) -> [i32; 2] {
+    let mut material = [0; 2];
 
     for square_idx in 0..64 {
         let square = Square(square_idx);
         if let Some(piece) = board.get_piece(square) {
-            let piece_value = match piece.piece_type {
-                PieceType::Pawn => PIECE_VALUES[0],
-                PieceType::Knight => PIECE_VALUES[1],
-                PieceType::Bishop => PIECE_VALUES[2],
-                PieceType::Rook => PIECE_VALUES[3],
-                PieceType::Queen => PIECE_VALUES[4],
-                PieceType::King => PIECE_VALUES[5],
-            };
+            if piece.piece_type == PieceType::King {
+                continue;
+            }
+            let piece_value = values[piece.piece_type as usize];
 
             match piece.color {
-                Color::White => score += piece_value,
-                Color::Black => score -= piece_value,
+                Color::White => material[0] += piece_value,
+                Color::Black => material[1] += piece_value,
             }
         }
     }
 
-    score
+    material
+}
+
+/// Default `values` for `evaluate`: pawn, knight, bishop, rook, queen.
+pub fn default_values() -> [i32; 5] {
+    let mut values = [0; 5];
+    values.copy_from_slice(&PIECE_VALUES[..5]);
+    values
+}
+
+/// Material value in centipawns.
+pub fn piece_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::Pawn => PIECE_VALUES[0],
+        PieceType::Knight => PIECE_VALUES[1],
+        PieceType::Bishop => PIECE_VALUES[2],
+        PieceType::Rook => PIECE_VALUES[3],
+        PieceType::Queen => PIECE_VALUES[4],
+        PieceType::King => PIECE_VALUES[5],
+    }
 }
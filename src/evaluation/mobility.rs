@@ -0,0 +1,68 @@
+//! Piece mobility: the squares each knight, bishop, rook and queen can move
+//! to according to `moves::piece_moves`, leaving out those an enemy pawn
+//! guards. Counts are taken relative to a typical number of moves, so a piece
+//! with average scope scores nothing.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::bitboards::{pawn_attacks, pawn_bitboards, side_index, squares};
+use crate::evaluation::score::Score;
+use crate::moves::piece_moves::{generate_bishop_moves, generate_knight_moves, generate_queen_moves, generate_rook_moves};
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+
+// Safe squares scored as zero, knight, bishop, rook, queen
+const BASELINE: [i32; 4] = [4, 7, 7, 14];
+
+/// Mobility weights per piece type. A zero weight switches that piece off.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MobilityConfig {
+    pub enabled: bool,
+    /// Per safe square above or below the baseline, knight, bishop, rook, queen
+    pub weights: [Score; 4],
+}
+
+impl Default for MobilityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            weights: [Score::new(4, 4), Score::new(5, 5), Score::new(2, 4), Score::new(1, 2)],
+        }
+    }
+}
+
+/// Mobility score, positive when White's pieces have more room.
+pub fn evaluate(board: &Board, config: &MobilityConfig) -> Score {
+    let [white, black] = breakdown(board, config);
+    white - black
+}
+
+/// Mobility of White and Black, zero when disabled.
+pub fn breakdown(board: &Board, config: &MobilityConfig) -> [Score; 2] {
+    if !config.enabled {
+        return [Score::ZERO; 2];
+    }
+    let [white_pawns, black_pawns] = pawn_bitboards(board);
+    // Squares each side's pieces can't safely move to
+    let guarded = [
+        squares(black_pawns).fold(0, |bb, sq| bb | pawn_attacks(sq, Color::Black)),
+        squares(white_pawns).fold(0, |bb, sq| bb | pawn_attacks(sq, Color::White)),
+    ];
+
+    let mut scores = [Score::ZERO; 2];
+    for idx in 0..64 {
+        let square = Square(idx);
+        let Some(piece) = board.get_piece(square) else { continue };
+        let (moves, kind) = match piece.piece_type {
+            PieceType::Knight => (generate_knight_moves(board, square, piece.color), 0),
+            PieceType::Bishop => (generate_bishop_moves(board, square, piece.color), 1),
+            PieceType::Rook => (generate_rook_moves(board, square, piece.color), 2),
+            PieceType::Queen => (generate_queen_moves(board, square, piece.color), 3),
+            PieceType::Pawn | PieceType::King => continue,
+        };
+        let side = side_index(piece.color);
+        let safe = moves.iter().filter(|mv| guarded[side] & (1 << mv.to.0) == 0).count() as i32;
+        scores[side] += config.weights[kind] * (safe - BASELINE[kind]);
+    }
+    scores
+}
@@ -1,2 +1,97 @@
+mod bitboards;
+pub mod endgame;
+pub mod king_safety;
 pub mod material;
-pub use material::evaluate;
+pub mod mobility;
+pub mod params;
+pub mod pawns;
+pub mod positional;
+pub mod score;
+pub mod see;
+pub mod trace;
+
+pub use endgame::{is_insufficient_material, KNOWN_WIN};
+pub use king_safety::KingSafetyConfig;
+pub use material::piece_value;
+pub use mobility::MobilityConfig;
+pub use params::EvalParams;
+pub use positional::{game_phase, MAX_PHASE};
+pub use score::Score;
+pub use see::see;
+pub use trace::{EvalTrace, SideTrace};
+
+use crate::board::{Board, BoardRepresentation};
+use crate::types::Color;
+use once_cell::sync::Lazy;
+
+static DEFAULT_PARAMS: Lazy<EvalParams> = Lazy::new(EvalParams::default);
+
+/// Static evaluation in centipawns from the side to move's point of view, as
+/// negamax expects: material plus tapered piece-square tables, pawn
+/// structure, king safety and mobility, unless the position is a draw or an
+/// ending `endgame` knows better.
+pub fn evaluate(board: &Board) -> i32 {
+    evaluate_with(board, &DEFAULT_PARAMS)
+}
+
+/// `evaluate` with the weights and switches of `params`.
+pub fn evaluate_with(board: &Board, params: &EvalParams) -> i32 {
+    evaluate_terms(board, params).score
+}
+
+/// `evaluate`, keeping every term that went into the score.
+pub fn evaluate_traced(board: &Board) -> EvalTrace {
+    evaluate_traced_with(board, &DEFAULT_PARAMS)
+}
+
+/// `evaluate_with`, keeping every term that went into the score, plus what
+/// each side has en prise, which the score leaves to the search.
+pub fn evaluate_traced_with(board: &Board, params: &EvalParams) -> EvalTrace {
+    EvalTrace {
+        en_prise: see::en_prise(board),
+        ..evaluate_terms(board, params)
+    }
+}
+
+// The evaluator itself; `evaluate_with` only drops the trace
+fn evaluate_terms(board: &Board, params: &EvalParams) -> EvalTrace {
+    let mut trace = EvalTrace {
+        phase: game_phase(board),
+        scale: endgame::SCALE_NORMAL,
+        endgame: endgame::evaluate(board),
+        ..EvalTrace::default()
+    };
+
+    let white = match trace.endgame {
+        Some(score) => score,
+        None => {
+            let material = material::breakdown(board, &params.piece_values);
+            let psqt = positional::breakdown(board, &params.psqt);
+            let pawns = pawns::breakdown(board, &params.pawns);
+            let king_safety = king_safety::breakdown(board, &params.king_safety);
+            let mobility = mobility::breakdown(board, &params.mobility);
+            for side in 0..2 {
+                trace.sides[side] = SideTrace {
+                    material: material[side],
+                    psqt: psqt[side],
+                    pawns: pawns[side],
+                    king_safety: king_safety[side],
+                    mobility: mobility[side],
+                };
+            }
+
+            let [white, black] = trace.sides;
+            let positional = white.positional() - black.positional();
+            trace.unscaled = white.material - black.material + positional.taper(trace.phase);
+            trace.scale = endgame::scale_factor(board);
+            trace.unscaled * trace.scale / endgame::SCALE_NORMAL
+        }
+    };
+
+    trace.white_score = white;
+    trace.score = match board.to_move() {
+        Color::White => white,
+        Color::Black => -white,
+    };
+    trace
+}
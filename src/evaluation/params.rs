@@ -0,0 +1,100 @@
+//! Every weight the evaluator reads, in one struct that can be saved to and
+//! loaded from JSON. Missing fields take their defaults, so a file only
+//! needs the weights it changes.
+
+use crate::evaluation::king_safety::KingSafetyConfig;
+use crate::evaluation::material;
+use crate::evaluation::mobility::MobilityConfig;
+use crate::evaluation::pawns::PawnParams;
+use crate::evaluation::positional::PieceSquareTables;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvalParams {
+    /// Pawn, knight, bishop, rook, queen
+    pub piece_values: [i32; 5],
+    pub psqt: PieceSquareTables,
+    pub pawns: PawnParams,
+    pub king_safety: KingSafetyConfig,
+    pub mobility: MobilityConfig,
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        Self {
+            piece_values: material::default_values(),
+            psqt: PieceSquareTables::default(),
+            pawns: PawnParams::default(),
+            king_safety: KingSafetyConfig::default(),
+            mobility: MobilityConfig::default(),
+        }
+    }
+}
+
+impl EvalParams {
+    /// Material, piece-square tables and pawn structure only.
+    pub fn none() -> Self {
+        Self {
+            king_safety: KingSafetyConfig {
+                pawn_shield: false,
+                open_files: false,
+                king_attack: false,
+                ..KingSafetyConfig::default()
+            },
+            mobility: MobilityConfig {
+                enabled: false,
+                ..MobilityConfig::default()
+            },
+            ..Self::default()
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        serde_json::from_str(&json).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Every tunable weight, middlegame before endgame for each `Score`.
+    /// Switches are left out.
+    pub fn weights_mut(&mut self) -> Vec<&mut i32> {
+        let mut weights: Vec<&mut i32> = self.piece_values.iter_mut().collect();
+        for table in self.psqt.0.iter_mut() {
+            for score in table.0.iter_mut() {
+                weights.extend([&mut score.mg, &mut score.eg]);
+            }
+        }
+
+        let pawns = &mut self.pawns;
+        for score in [&mut pawns.doubled, &mut pawns.isolated, &mut pawns.backward] {
+            weights.extend([&mut score.mg, &mut score.eg]);
+        }
+        for table in [
+            &mut pawns.connected,
+            &mut pawns.passed,
+            &mut pawns.passed_free_path,
+            &mut pawns.passed_safe_path,
+        ] {
+            for score in table.iter_mut() {
+                weights.extend([&mut score.mg, &mut score.eg]);
+            }
+        }
+
+        let king = &mut self.king_safety;
+        for score in king.shield.iter_mut().chain([&mut king.open_file, &mut king.half_open_file]) {
+            weights.extend([&mut score.mg, &mut score.eg]);
+        }
+        weights.extend([&mut king.zone_attack, &mut king.zone_overload]);
+
+        for score in self.mobility.weights.iter_mut() {
+            weights.extend([&mut score.mg, &mut score.eg]);
+        }
+        weights
+    }
+}
@@ -0,0 +1,268 @@
+//! Pawn structure: doubled, isolated, backward, connected and passed pawns.
+//! What depends on the pawns alone is counted once per structure and cached
+//! per thread under the pawn-only Zobrist key, then weighted on every call so
+//! cached entries stay valid whatever the weights. Passed pawns are found once
+//! per structure too, but their path bonus depends on the other pieces.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::bitboards::{
+    adjacent_files, file_mask, flip, pawn_attacks, pawn_bitboards, rank_mask, relative_rank, squares, stop_square,
+};
+use crate::evaluation::score::Score;
+use crate::types::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+const PAWN_TABLE_ENTRIES: usize = 1 << 13;
+
+/// Pawn structure weights. Per-rank tables are indexed by rank from the
+/// pawn's own side, 0 = first rank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PawnParams {
+    /// Per pawn with an own pawn in front of it
+    pub doubled: Score,
+    /// Per pawn with no own pawns on the neighbouring files
+    pub isolated: Score,
+    /// Per pawn its neighbours have left behind, with its stop square guarded
+    pub backward: Score,
+    /// Per pawn defended by or beside another
+    pub connected: [Score; 8],
+    pub passed: [Score; 8],
+    /// Extra for a passed pawn with nothing standing in front of it
+    pub passed_free_path: [Score; 8],
+    /// Extra again when the opponent doesn't attack the path either
+    pub passed_safe_path: [Score; 8],
+}
+
+impl Default for PawnParams {
+    fn default() -> Self {
+        Self {
+            doubled: Score::new(-11, -28),
+            isolated: Score::new(-6, -14),
+            backward: Score::new(-9, -12),
+            connected: [
+                Score::new(0, 0),
+                Score::new(7, 2),
+                Score::new(8, 4),
+                Score::new(12, 8),
+                Score::new(29, 18),
+                Score::new(48, 36),
+                Score::new(86, 60),
+                Score::new(0, 0),
+            ],
+            passed: [
+                Score::new(0, 0),
+                Score::new(5, 10),
+                Score::new(10, 15),
+                Score::new(15, 25),
+                Score::new(35, 45),
+                Score::new(60, 80),
+                Score::new(100, 130),
+                Score::new(0, 0),
+            ],
+            passed_free_path: [
+                Score::new(0, 0),
+                Score::new(0, 0),
+                Score::new(0, 0),
+                Score::new(2, 8),
+                Score::new(5, 15),
+                Score::new(10, 30),
+                Score::new(15, 50),
+                Score::new(0, 0),
+            ],
+            passed_safe_path: [
+                Score::new(0, 0),
+                Score::new(0, 0),
+                Score::new(0, 0),
+                Score::new(2, 5),
+                Score::new(4, 10),
+                Score::new(8, 20),
+                Score::new(12, 35),
+                Score::new(0, 0),
+            ],
+        }
+    }
+}
+
+/// One side's pawn terms, positive when they favour that side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct PawnTerms {
+    pub doubled: Score,
+    pub isolated: Score,
+    pub backward: Score,
+    pub connected: Score,
+    pub passed: Score,
+}
+
+impl PawnTerms {
+    pub fn total(&self) -> Score {
+        self.doubled + self.isolated + self.backward + self.connected + self.passed
+    }
+}
+
+/// One side's structural features, before weighting.
+#[derive(Clone, Copy, Debug, Default)]
+struct PawnCounts {
+    doubled: i32,
+    isolated: i32,
+    backward: i32,
+    /// Connected pawns per relative rank
+    connected: [i32; 8],
+}
+
+/// Cached analysis of one pawn structure.
+#[derive(Clone, Copy, Debug, Default)]
+struct PawnEntry {
+    key: u64,
+    counts: [PawnCounts; 2],
+    /// Passed pawns of each side, bit `n` for square `n`
+    passed: [u64; 2],
+}
+
+/// Per-thread cache of pawn structures. Each search worker has its own, so
+/// there is no locking; an empty entry stands for the pawnless structure.
+struct PawnTable {
+    entries: Vec<PawnEntry>,
+    probes: u64,
+    hits: u64,
+}
+
+impl PawnTable {
+    fn new() -> Self {
+        PawnTable {
+            entries: vec![PawnEntry::default(); PAWN_TABLE_ENTRIES],
+            probes: 0,
+            hits: 0,
+        }
+    }
+}
+
+thread_local! {
+    static PAWN_TABLE: RefCell<PawnTable> = RefCell::new(PawnTable::new());
+}
+
+/// Pawn structure score, positive when White stands better.
+pub fn evaluate(board: &Board, params: &PawnParams) -> Score {
+    let [white, black] = breakdown(board, params);
+    white.total() - black.total()
+}
+
+/// Pawn terms of White and Black.
+pub fn breakdown(board: &Board, params: &PawnParams) -> [PawnTerms; 2] {
+    let key = board.pawn_key();
+    let entry = PAWN_TABLE.with(|table| {
+        let mut table = table.borrow_mut();
+        let slot = (key as usize) & (PAWN_TABLE_ENTRIES - 1);
+        table.probes += 1;
+        if table.entries[slot].key == key {
+            table.hits += 1;
+            return table.entries[slot];
+        }
+        let entry = analyse(board, key);
+        table.entries[slot] = entry;
+        entry
+    });
+
+    let mut terms = [PawnTerms::default(); 2];
+    for (side, color) in [(0, Color::White), (1, Color::Black)] {
+        let counts = &entry.counts[side];
+        terms[side] = PawnTerms {
+            doubled: params.doubled * counts.doubled,
+            isolated: params.isolated * counts.isolated,
+            backward: params.backward * counts.backward,
+            connected: (0..8).fold(Score::ZERO, |sum, rank| sum + params.connected[rank] * counts.connected[rank]),
+            passed: passed_bonus(board, entry.passed[side], color, params),
+        };
+    }
+    terms
+}
+
+/// Pawn table probes and hits on the calling thread.
+pub fn pawn_table_stats() -> (u64, u64) {
+    PAWN_TABLE.with(|table| {
+        let table = table.borrow();
+        (table.probes, table.hits)
+    })
+}
+
+/// Empty the calling thread's pawn table.
+pub fn clear_pawn_table() {
+    PAWN_TABLE.with(|table| *table.borrow_mut() = PawnTable::new());
+}
+
+fn analyse(board: &Board, key: u64) -> PawnEntry {
+    let pawns = pawn_bitboards(board);
+    let mut entry = PawnEntry { key, ..PawnEntry::default() };
+    for (side, color) in [(0, Color::White), (1, Color::Black)] {
+        let own = pawns[side];
+        let enemy = pawns[1 - side];
+        let counts = &mut entry.counts[side];
+
+        for sq in squares(own) {
+            let file = sq % 8;
+            let rank = relative_rank(sq, color);
+            let neighbours = adjacent_files(file);
+
+            let supported = own & pawn_attacks(sq, flip(color)) != 0;
+            let phalanx = own & neighbours & rank_mask(sq / 8) != 0;
+            let isolated = own & neighbours == 0;
+
+            if own & forward_file(sq, color) != 0 {
+                counts.doubled += 1;
+            } else if enemy & passed_span(sq, color) == 0 {
+                entry.passed[side] |= 1 << sq;
+            }
+            if isolated {
+                counts.isolated += 1;
+            }
+            if supported || phalanx {
+                counts.connected[rank] += 1;
+            } else if !isolated && own & neighbours & !forward_ranks(sq, color) == 0 {
+                // Every neighbour has gone past, and an enemy pawn guards the stop square
+                if let Some(stop) = stop_square(sq, color) {
+                    if enemy & pawn_attacks(stop, color) != 0 {
+                        counts.backward += 1;
+                    }
+                }
+            }
+        }
+    }
+    entry
+}
+
+fn passed_bonus(board: &Board, passed: u64, color: Color, params: &PawnParams) -> Score {
+    let mut bonus = Score::ZERO;
+    for sq in squares(passed) {
+        let rank = relative_rank(sq, color);
+        bonus += params.passed[rank];
+
+        let path: Vec<u8> = squares(forward_file(sq, color)).collect();
+        if path.iter().all(|&s| board.is_empty(Square(s))) {
+            bonus += params.passed_free_path[rank];
+            if path.iter().all(|&s| !board.is_square_attacked(Square(s), flip(color))) {
+                bonus += params.passed_safe_path[rank];
+            }
+        }
+    }
+    bonus
+}
+
+/// Ranks strictly in front of `sq` from `color`'s side.
+fn forward_ranks(sq: u8, color: Color) -> u64 {
+    let rank = sq / 8;
+    match color {
+        Color::White if rank < 7 => !0u64 << (8 * (rank + 1)),
+        Color::Black => (1u64 << (8 * rank)) - 1,
+        _ => 0,
+    }
+}
+
+fn forward_file(sq: u8, color: Color) -> u64 {
+    forward_ranks(sq, color) & file_mask(sq % 8)
+}
+
+/// Squares an enemy pawn would have to stand on to stop `sq` from queening.
+fn passed_span(sq: u8, color: Color) -> u64 {
+    forward_ranks(sq, color) & (file_mask(sq % 8) | adjacent_files(sq % 8))
+}
@@ -0,0 +1,181 @@
+//! Static exchange evaluation. A capture is scored by playing out every
+//! recapture on its target square, cheapest attacker first, with each side
+//! free to stop once going on would cost it. Sliders lined up behind a piece
+//! that has moved onto the square join in (x-rays). Pins are ignored.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::material::piece_value;
+use crate::evaluation::bitboards::{flip, side_index};
+use crate::types::*;
+
+const KNIGHT_STEPS: [(i8, i8); 8] = [(1, 2), (2, 1), (2, -1), (1, -2), (-1, -2), (-2, -1), (-2, 1), (-1, 2)];
+const KING_STEPS: [(i8, i8); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+// Longest exchange: every piece on the board taking in turn
+const MAX_EXCHANGE: usize = 32;
+
+/// Material `mv` wins for the side making it, in centipawns, once the
+/// exchange it starts on the target square has been played out. Negative
+/// when the mover loses more than it takes.
+pub fn see(board: &Board, mv: Move) -> i32 {
+    let Some(mover) = board.get_piece(mv.from) else {
+        return 0;
+    };
+    let mut occupied = occupancy(board) & !bit(mv.from.0);
+
+    let mut gain = board.get_piece(mv.to).map_or(0, |p| piece_value(p.piece_type));
+    if mv.special_move == Some(SpecialMove::EnPassant) {
+        // The captured pawn stands behind the target square
+        let captured = match mover.color {
+            Color::White => mv.to.0 - 8,
+            Color::Black => mv.to.0 + 8,
+        };
+        occupied &= !bit(captured);
+        gain = piece_value(PieceType::Pawn);
+    }
+    let mut on_square = piece_value(mover.piece_type);
+    if let Some(promotion) = mv.promotion {
+        gain += piece_value(promotion) - piece_value(PieceType::Pawn);
+        on_square = piece_value(promotion);
+    }
+
+    exchange(board, mv.to, occupied, gain, on_square, flip(mover.color))
+}
+
+/// For White then Black, the most material the other side wins by taking
+/// one of its pieces, with the cheapest attacker going first. Zero when
+/// nothing is en prise.
+pub fn en_prise(board: &Board) -> [i32; 2] {
+    let occupied = occupancy(board);
+    let mut lost = [0; 2];
+    for square in 0..64 {
+        let Some(piece) = board.get_piece(Square(square)) else {
+            continue;
+        };
+        if piece.piece_type == PieceType::King {
+            continue;
+        }
+        let attacker_color = flip(piece.color);
+        let Some((from, attacker)) = least_valuable_attacker(board, Square(square), attacker_color, occupied) else {
+            continue;
+        };
+        let last_rank = match attacker_color {
+            Color::White => 7,
+            Color::Black => 0,
+        };
+        let promotion = (attacker == PieceType::Pawn && square / 8 == last_rank).then_some(PieceType::Queen);
+        let value = see(board, Move::new(Square(from), Square(square), None, promotion));
+        lost[side_index(piece.color)] = lost[side_index(piece.color)].max(value);
+    }
+    lost
+}
+
+// `gains[i]` is what the side making capture `i` is up if the exchange ends
+// there; negamax back down the list gives the value of the first capture
+fn exchange(board: &Board, target: Square, mut occupied: u64, gain: i32, mut on_square: i32, mut side: Color) -> i32 {
+    let mut gains = [0; MAX_EXCHANGE];
+    gains[0] = gain;
+    let mut depth = 0;
+
+    while depth + 1 < MAX_EXCHANGE {
+        let Some((from, piece)) = least_valuable_attacker(board, target, side, occupied) else {
+            break;
+        };
+        // A king can only take when nothing can take it back
+        if piece == PieceType::King
+            && least_valuable_attacker(board, target, flip(side), occupied & !bit(from)).is_some()
+        {
+            break;
+        }
+        depth += 1;
+        gains[depth] = on_square - gains[depth - 1];
+        occupied &= !bit(from);
+        on_square = piece_value(piece);
+        side = flip(side);
+    }
+
+    while depth > 0 {
+        gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+        depth -= 1;
+    }
+    gains[0]
+}
+
+// Cheapest piece of `side` attacking `target`, looking only through the
+// squares still in `occupied`
+fn least_valuable_attacker(board: &Board, target: Square, side: Color, occupied: u64) -> Option<(u8, PieceType)> {
+    let mut best: Option<(u8, PieceType)> = None;
+    let mut consider = |square: u8, piece: PieceType| {
+        if best.is_none_or(|(_, b)| piece_value(piece) < piece_value(b)) {
+            best = Some((square, piece));
+        }
+    };
+    let piece_at = |square: u8| -> Option<PieceType> {
+        if occupied & bit(square) == 0 {
+            return None;
+        }
+        board.get_piece(Square(square)).filter(|p| p.color == side).map(|p| p.piece_type)
+    };
+
+    // A pawn attacks the target from one rank behind it
+    let pawn_rank = match side {
+        Color::White => -1,
+        Color::Black => 1,
+    };
+    for file_step in [-1, 1] {
+        if let Some(square) = offset(target.0, file_step, pawn_rank) {
+            if piece_at(square) == Some(PieceType::Pawn) {
+                consider(square, PieceType::Pawn);
+            }
+        }
+    }
+    for (file_step, rank_step) in KNIGHT_STEPS {
+        if let Some(square) = offset(target.0, file_step, rank_step) {
+            if piece_at(square) == Some(PieceType::Knight) {
+                consider(square, PieceType::Knight);
+            }
+        }
+    }
+    for (directions, slider) in [(ROOK_DIRECTIONS, PieceType::Rook), (BISHOP_DIRECTIONS, PieceType::Bishop)] {
+        for (file_step, rank_step) in directions {
+            let mut square = target.0;
+            while let Some(next) = offset(square, file_step, rank_step) {
+                square = next;
+                if occupied & bit(square) == 0 {
+                    continue;
+                }
+                if let Some(piece) = piece_at(square) {
+                    if piece == slider || piece == PieceType::Queen {
+                        consider(square, piece);
+                    }
+                }
+                break;
+            }
+        }
+    }
+    for (file_step, rank_step) in KING_STEPS {
+        if let Some(square) = offset(target.0, file_step, rank_step) {
+            if piece_at(square) == Some(PieceType::King) {
+                consider(square, PieceType::King);
+            }
+        }
+    }
+    best
+}
+
+fn occupancy(board: &Board) -> u64 {
+    (0..64)
+        .filter(|&square| !board.is_empty(Square(square)))
+        .fold(0, |occupied, square| occupied | bit(square))
+}
+
+fn offset(square: u8, file_step: i8, rank_step: i8) -> Option<u8> {
+    let file = (square % 8) as i8 + file_step;
+    let rank = (square / 8) as i8 + rank_step;
+    ((0..8).contains(&file) && (0..8).contains(&rank)).then(|| (rank * 8 + file) as u8)
+}
+
+fn bit(square: u8) -> u64 {
+    1 << square
+}
@@ -0,0 +1,96 @@
+//! Breakdown of a static evaluation into its terms, filled in by
+//! `evaluate_traced` as it scores the position.
+
+use crate::evaluation::endgame::SCALE_NORMAL;
+use crate::evaluation::king_safety::KingSafetyTerms;
+use crate::evaluation::pawns::PawnTerms;
+use crate::evaluation::positional::MAX_PHASE;
+use crate::evaluation::score::Score;
+use serde::Serialize;
+use std::fmt;
+
+/// One side's evaluation terms, positive when they favour that side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SideTrace {
+    pub material: i32,
+    pub psqt: Score,
+    pub pawns: PawnTerms,
+    pub king_safety: KingSafetyTerms,
+    pub mobility: Score,
+}
+
+impl SideTrace {
+    /// Everything but material, before tapering.
+    pub fn positional(&self) -> Score {
+        self.psqt + self.pawns.total() + self.king_safety.total() + self.mobility
+    }
+}
+
+/// Every term of one evaluation. Scores are White's point of view unless
+/// noted. When `endgame` is set it replaced the terms, which stay zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct EvalTrace {
+    /// White's terms, then Black's
+    pub sides: [SideTrace; 2],
+    /// From `MAX_PHASE` with all pieces on the board down to 0
+    pub phase: i32,
+    /// Material plus tapered positional terms
+    pub unscaled: i32,
+    /// Out of `SCALE_NORMAL`; below it for drawish material
+    pub scale: i32,
+    /// Score of a recognised ending or drawn material
+    pub endgame: Option<i32>,
+    pub white_score: i32,
+    /// From the side to move's point of view, as `evaluate` returns it
+    pub score: i32,
+    /// Material White, then Black, stands to lose to the best capture
+    /// against it, by SEE. Not part of the score
+    pub en_prise: [i32; 2],
+}
+
+// Table rows: label and how to read the term off a side
+type Row = (&'static str, fn(&SideTrace) -> Score);
+
+const ROWS: [Row; 11] = [
+    ("Material", |s| Score::new(s.material, s.material)),
+    ("Piece-square", |s| s.psqt),
+    ("Doubled pawns", |s| s.pawns.doubled),
+    ("Isolated pawns", |s| s.pawns.isolated),
+    ("Backward pawns", |s| s.pawns.backward),
+    ("Connected pawns", |s| s.pawns.connected),
+    ("Passed pawns", |s| s.pawns.passed),
+    ("Pawn shield", |s| s.king_safety.pawn_shield),
+    ("Open files", |s| s.king_safety.open_files),
+    ("King attack", |s| s.king_safety.king_attack),
+    ("Mobility", |s| s.mobility),
+];
+
+impl fmt::Display for EvalTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [white, black] = &self.sides;
+
+        writeln!(f, "{:<16}{:>14}{:>14}{:>14}", "Term", "White", "Black", "Total")?;
+        writeln!(f, "{:<16}{:>7}{:>7}{:>7}{:>7}{:>7}{:>7}", "", "mg", "eg", "mg", "eg", "mg", "eg")?;
+        for (name, term) in ROWS {
+            let (w, b) = (term(white), term(black));
+            let total = w - b;
+            writeln!(
+                f,
+                "{:<16}{:>7}{:>7}{:>7}{:>7}{:>7}{:>7}",
+                name, w.mg, w.eg, b.mg, b.eg, total.mg, total.eg
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "Phase:      {} / {}", self.phase, MAX_PHASE)?;
+        match self.endgame {
+            Some(score) => writeln!(f, "Endgame:    {} (replaces the terms above)", score)?,
+            None => {
+                writeln!(f, "Tapered:    {}", self.unscaled)?;
+                writeln!(f, "Scale:      {} / {}", self.scale, SCALE_NORMAL)?;
+            }
+        }
+        writeln!(f, "En prise:   {} (White), {} (Black)", self.en_prise[0], self.en_prise[1])?;
+        write!(f, "Score:      {} (White), {} (side to move)", self.white_score, self.score)
+    }
+}
@@ -5,5 +5,6 @@ pub mod evaluation;
 pub mod moves;
 pub mod scheduling;
 pub mod search;
+pub mod tuning;
 pub mod types;
 pub mod utils;
@@ -1,14 +1,33 @@
 use devi::cli;
 
 fn main() {
+    let args = cli::parse_args();
+    cli::commands::configure_scheduling(&args);
+
+    // The GUI expects nothing but protocol output
+    if args.uci {
+        cli::run_uci(cli::commands::eval_params(&args));
+        return;
+    }
+
+    // Plain output, so the JSON form can be piped
+    if args.eval {
+        cli::commands::run_eval(&args);
+        return;
+    }
+
     println!("devi Chess Engine v{}", env!("CARGO_PKG_VERSION"));
     println!("------------------------");
 
-    let args = cli::parse_args();
     run_command(&args);
 }
 
 fn run_command(args: &cli::Cli) {
+    if args.tune.is_some() {
+        cli::commands::run_tune(args);
+        return;
+    }
+
     if args.thread_recovery {
         cli::commands::run_recovery_analysis(&args);
         return;
@@ -1,10 +1,20 @@
 use crate::board::{Board, BoardRepresentation};
-use crate::moves::generate_moves;
+use crate::moves::{generate_captures, generate_moves};
 use crate::types::*;
 
 pub fn generate_legal_moves(board: &mut Board, color: Color) -> Vec<Move> {
-    let mut legal_moves = Vec::new();
     let pseudo_moves: Vec<Move> = generate_moves(board, color);
+    retain_legal(board, color, pseudo_moves)
+}
+
+pub fn generate_legal_captures(board: &mut Board, color: Color) -> Vec<Move> {
+    let pseudo_moves = generate_captures(board, color);
+    retain_legal(board, color, pseudo_moves)
+}
+
+/// Keep the pseudo-legal moves that don't leave `color` in check.
+pub fn retain_legal(board: &mut Board, color: Color, pseudo_moves: Vec<Move>) -> Vec<Move> {
+    let mut legal_moves = Vec::new();
 
     for _move in pseudo_moves {
         let undo_info = board.make_move(&_move);
@@ -3,7 +3,7 @@ pub mod movegen;
 pub mod perft;
 pub mod piece_moves;
 
-pub use legal_moves::generate_legal_moves;
+pub use legal_moves::{generate_legal_captures, generate_legal_moves, retain_legal};
 pub use movegen::*;
 pub use perft::{perft, perft_detailed, perft_detailed_parallel, perft_divide, perft_parallel, perft_verify, verify_positions, verify_positions_detailed, PerftStats};
 pub use piece_moves::*;
@@ -118,3 +118,55 @@ pub fn generate_moves(board: &Board, color: Color) -> Vec<Move> {
 
     moves
 }
+
+/// Pseudo-legal captures only, including en passant and capturing promotions.
+/// Quiescence search uses this in place of `generate_moves`.
+pub fn generate_captures(board: &Board, color: Color) -> Vec<Move> {
+    let mut moves = Vec::new();
+
+    for square_idx in 0..64 {
+        let square = Square(square_idx);
+        if let Some(piece) = board.get_piece(square) {
+            if piece.color == color {
+                let piece_moves = match piece.piece_type {
+                    PieceType::Pawn => {
+                        generate_pawn_capture(board, square, color, &mut moves);
+                        continue;
+                    }
+                    PieceType::Knight => generate_knight_moves(board, square, color),
+                    PieceType::King => generate_king_moves(board, square, color),
+                    PieceType::Rook => generate_rook_moves(board, square, color),
+                    PieceType::Bishop => generate_bishop_moves(board, square, color),
+                    PieceType::Queen => generate_queen_moves(board, square, color),
+                };
+                // Piece generators never target own pieces
+                moves.extend(piece_moves.into_iter().filter(|mv| !board.is_empty(mv.to)));
+            }
+        }
+    }
+
+    moves
+}
+
+/// Pseudo-legal pawn pushes that promote. Capturing promotions come from
+/// `generate_captures`.
+pub fn generate_promotions(board: &Board, color: Color) -> Vec<Move> {
+    let promotion_rank = match color {
+        Color::White => 48..56,
+        Color::Black => 8..16,
+    };
+
+    let mut moves = Vec::new();
+    for square_idx in promotion_rank {
+        let square = Square(square_idx);
+        if board.get_piece(square) == Some(Piece { piece_type: PieceType::Pawn, color }) {
+            moves.extend(
+                generate_pawn_moves(board, square, color)
+                    .into_iter()
+                    .filter(|mv| board.is_empty(mv.to)),
+            );
+        }
+    }
+
+    moves
+}
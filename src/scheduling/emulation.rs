@@ -0,0 +1,74 @@
+//! Emulated efficiency cores for machines where every core is alike. Workers
+//! that would land on E-cores are duty-cycled instead: after each stretch of
+//! work they sleep long enough to run `slowdown` times slower, the way the
+//! M1 Pro E-cores trail its P-cores (measured at 12.8x). The search checks in
+//! at every stop poll, so a slow worker computes the same result, later.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::thread;
+use std::time::Instant;
+
+/// Gap between P- and E-cores measured on the M1 Pro.
+pub const M1_PRO_SLOWDOWN: f32 = 12.8;
+
+// f32 bits; 1.0 means no emulation
+static SLOWDOWN: AtomicU32 = AtomicU32::new(0x3f80_0000);
+
+thread_local! {
+    static DUTY_CYCLE: Cell<Option<DutyCycle>> = const { Cell::new(None) };
+}
+
+#[derive(Clone, Copy)]
+struct DutyCycle {
+    slowdown: f32,
+    since: Instant,
+}
+
+/// Run E-core workers of pools built from now on `slowdown` times slower.
+/// 1.0 or less turns emulation off.
+pub fn set_emulated_slowdown(slowdown: f32) {
+    SLOWDOWN.store(slowdown.max(1.0).to_bits(), Ordering::Relaxed);
+}
+
+pub fn emulated_slowdown() -> f32 {
+    f32::from_bits(SLOWDOWN.load(Ordering::Relaxed))
+}
+
+pub fn emulation_enabled() -> bool {
+    emulated_slowdown() > 1.0
+}
+
+/// Make the calling thread an emulated E-core, if emulation is on.
+pub fn mark_slow_worker() {
+    let slowdown = emulated_slowdown();
+    if slowdown > 1.0 {
+        DUTY_CYCLE.with(|d| d.set(Some(DutyCycle { slowdown, since: Instant::now() })));
+    }
+}
+
+pub fn is_slow_worker() -> bool {
+    DUTY_CYCLE.with(|d| d.get().is_some())
+}
+
+/// Start timing a piece of work, so time the worker sat idle in the pool
+/// isn't slept off.
+pub fn start_work() {
+    DUTY_CYCLE.with(|d| {
+        if let Some(cycle) = d.get() {
+            d.set(Some(DutyCycle { since: Instant::now(), ..cycle }));
+        }
+    });
+}
+
+/// Sleep off the share of the time since the last call that an emulated
+/// E-core would have needed on top. Free on every other thread.
+pub fn throttle() {
+    DUTY_CYCLE.with(|d| {
+        if let Some(cycle) = d.get() {
+            let worked = cycle.since.elapsed();
+            thread::sleep(worked.mul_f32(cycle.slowdown - 1.0));
+            d.set(Some(DutyCycle { since: Instant::now(), ..cycle }));
+        }
+    });
+}
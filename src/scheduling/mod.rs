@@ -1,14 +1,34 @@
 //! Thread scheduling policies for heterogeneous architectures
-//! Implements Apple QoS biasing on macOS, graceful fallback elsewhere
+//! Implements Apple QoS biasing on macOS and CPU affinity on Linux,
+//! graceful fallback elsewhere. E-core workers can also be emulated by
+//! throttling, see `emulation`.
 // QOS_CLASS_USER_INITIATED biases toward P-cores (~90% effective)
-// TODO: Linux version with pthread_setaffinity would be cleaner
+
+pub mod emulation;
+pub mod topology;
+
+pub use topology::{CoreSets, CoreSource, CpuSet};
 
 use clap::ValueEnum;
 #[cfg(target_os = "macos")]
 use libc::{pthread_set_qos_class_self_np, qos_class_t};
+use once_cell::sync::OnceCell;
 use rayon::{ThreadPool, ThreadPoolBuilder};
 use std::error::Error;
 
+static CORE_SETS: OnceCell<CoreSets> = OnceCell::new();
+
+/// Use `sets` for every pool built from now on instead of the discovered
+/// topology. Fails once a pool has already been built.
+pub fn set_core_sets(sets: CoreSets) -> Result<(), CoreSets> {
+    CORE_SETS.set(sets)
+}
+
+/// The P/E split pools are pinned to, discovered on first use.
+pub fn core_sets() -> &'static CoreSets {
+    CORE_SETS.get_or_init(CoreSets::discover)
+}
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum CorePolicy {
     #[value(name = "none")]
@@ -48,6 +68,13 @@ impl HeterogeneousScheduler {
                 #[cfg(target_os = "macos")]
                 apply_qos_for_thread(policy_copy, index, threads_copy, ratio_copy);
 
+                #[cfg(target_os = "linux")]
+                pin_worker(cpus_for_worker(policy_copy, index, threads_copy, ratio_copy));
+
+                if worker_is_efficient(policy_copy, index, threads_copy, ratio_copy) {
+                    emulation::mark_slow_worker();
+                }
+
                 if index == 0 {
                     eprintln!(
                         "Applied policy {:?} to {} threads",
@@ -86,6 +113,65 @@ fn apply_qos_for_thread(
     }
 }
 
+/// Whether `policy` sends a worker to the E-cores.
+pub fn worker_is_efficient(
+    policy: CorePolicy,
+    worker_index: usize,
+    total_threads: usize,
+    mixed_ratio: f32,
+) -> bool {
+    match policy {
+        CorePolicy::None | CorePolicy::FastBias => false,
+        CorePolicy::EfficientBias => true,
+        CorePolicy::Mixed => worker_index >= (total_threads as f32 * mixed_ratio) as usize,
+    }
+}
+
+/// CPUs a worker runs on under `policy`, `None` to leave it unpinned.
+pub fn cpus_for_worker(
+    policy: CorePolicy,
+    worker_index: usize,
+    total_threads: usize,
+    mixed_ratio: f32,
+) -> Option<&'static CpuSet> {
+    let sets = core_sets();
+    match policy {
+        CorePolicy::None => None,
+        _ if worker_is_efficient(policy, worker_index, total_threads, mixed_ratio) => sets.efficient(),
+        _ => sets.fast(),
+    }
+}
+
+/// Restrict the calling thread to `cpus`.
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpus: &CpuSet) -> std::io::Result<()> {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus.cpus().iter().filter(|&&cpu| cpu < libc::CPU_SETSIZE as usize) {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_cpus: &CpuSet) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+// A CPU set naming offline or nonexistent CPUs only costs the pinning
+#[cfg(target_os = "linux")]
+fn pin_worker(cpus: Option<&CpuSet>) {
+    if let Some(cpus) = cpus {
+        if let Err(e) = pin_current_thread(cpus) {
+            eprintln!("Warning, Failed to pin worker to CPUs {}: {}", cpus, e);
+        }
+    }
+}
+
 pub fn create_pool_for_policy(
     policy: CorePolicy,
     threads: usize,
@@ -113,7 +199,7 @@ pub fn create_pool_for_policy_simple(policy: CorePolicy, threads: usize) -> rayo
     create_pool_for_policy(policy, threads, 0.80) // Default 80% fast cores
 }
 
-/// Create P-core biased pool (high QoS)
+/// Create P-core biased pool (high QoS, pinned to the P-cores on Linux)
 pub fn create_p_core_pool(num_threads: usize) -> Result<rayon::ThreadPool, rayon::ThreadPoolBuildError> {
     ThreadPoolBuilder::new()
         .num_threads(num_threads)
@@ -130,6 +216,8 @@ pub fn create_p_core_pool(num_threads: usize) -> Result<rayon::ThreadPool, rayon
                     const QOS_CLASS_USER_INITIATED: c_uint = 0x19;
                     unsafe { pthread_set_qos_class_self_np(QOS_CLASS_USER_INITIATED, 0); }
                 }
+                #[cfg(target_os = "linux")]
+                pin_worker(core_sets().fast());
                 thread.run();
             });
             Ok(())
@@ -137,7 +225,8 @@ pub fn create_p_core_pool(num_threads: usize) -> Result<rayon::ThreadPool, rayon
         .build()
 }
 
-/// Create E-core biased pool (background QoS)
+/// Create E-core biased pool (background QoS, pinned to the E-cores on Linux,
+/// throttled when E-cores are emulated)
 pub fn create_e_core_pool(num_threads: usize) -> Result<rayon::ThreadPool, rayon::ThreadPoolBuildError> {
     ThreadPoolBuilder::new()
         .num_threads(num_threads)
@@ -153,6 +242,9 @@ pub fn create_e_core_pool(num_threads: usize) -> Result<rayon::ThreadPool, rayon
                     const QOS_CLASS_BACKGROUND: c_uint = 0x09;
                     unsafe { pthread_set_qos_class_self_np(QOS_CLASS_BACKGROUND, 0); }
                 }
+                #[cfg(target_os = "linux")]
+                pin_worker(core_sets().efficient());
+                emulation::mark_slow_worker();
                 thread.run();
             });
             Ok(())
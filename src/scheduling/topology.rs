@@ -0,0 +1,202 @@
+//! Which CPUs are fast (P) and which are efficient (E). On Linux this is read
+//! from sysfs: the `cpu_core`/`cpu_atom` PMUs of Intel hybrid parts, else
+//! `cpu_capacity` (ARM big.LITTLE), else each core's maximum frequency. The
+//! sets can also be given on the command line.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Logical CPU ids, written in the kernel's cpulist format (`0-7,12`).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CpuSet(Vec<usize>);
+
+impl CpuSet {
+    pub fn new(mut cpus: Vec<usize>) -> Self {
+        cpus.sort_unstable();
+        cpus.dedup();
+        CpuSet(cpus)
+    }
+
+    pub fn cpus(&self) -> &[usize] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for CpuSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cpus = Vec::new();
+        for part in s.trim().split(',').filter(|p| !p.is_empty()) {
+            let parse = |n: &str| n.trim().parse::<usize>().map_err(|_| format!("bad CPU '{}' in '{}'", n, s));
+            match part.split_once('-') {
+                Some((first, last)) => {
+                    let (first, last) = (parse(first)?, parse(last)?);
+                    if first > last {
+                        return Err(format!("bad CPU range '{}'", part));
+                    }
+                    cpus.extend(first..=last);
+                }
+                None => cpus.push(parse(part)?),
+            }
+        }
+        Ok(CpuSet::new(cpus))
+    }
+}
+
+impl fmt::Display for CpuSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i < self.0.len() {
+            let first = self.0[i];
+            while i + 1 < self.0.len() && self.0[i + 1] == self.0[i] + 1 {
+                i += 1;
+            }
+            let last = self.0[i];
+            ranges.push(if first == last { first.to_string() } else { format!("{}-{}", first, last) });
+            i += 1;
+        }
+        write!(f, "{}", ranges.join(","))
+    }
+}
+
+/// Where the core classes came from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CoreSource {
+    /// `--p-core-set` / `--e-core-set`
+    Explicit,
+    /// `cpu_core` and `cpu_atom` PMUs (Intel hybrid)
+    HybridPmu,
+    /// Per-CPU `cpu_capacity`
+    Capacity,
+    /// Per-CPU `cpufreq/cpuinfo_max_freq`
+    Frequency,
+    /// All CPUs look alike
+    Homogeneous,
+    /// Nothing readable, e.g. not Linux
+    #[default]
+    Unknown,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoreSets {
+    pub p_cores: CpuSet,
+    pub e_cores: CpuSet,
+    pub source: CoreSource,
+}
+
+impl CoreSets {
+    /// Core classes of this machine.
+    pub fn discover() -> Self {
+        Self::discover_in(Path::new("/sys/devices"))
+    }
+
+    /// Core classes from a sysfs `devices` tree rooted at `sys_devices`.
+    pub fn discover_in(sys_devices: &Path) -> Self {
+        let cpu_dir = sys_devices.join("system/cpu");
+
+        let core = read_cpu_list(&sys_devices.join("cpu_core/cpus"));
+        let atom = read_cpu_list(&sys_devices.join("cpu_atom/cpus"));
+        if let (Some(p_cores), Some(e_cores)) = (core, atom) {
+            if !p_cores.is_empty() && !e_cores.is_empty() {
+                return CoreSets { p_cores, e_cores, source: CoreSource::HybridPmu };
+            }
+        }
+
+        let online = read_cpu_list(&cpu_dir.join("online")).unwrap_or_else(|| list_cpu_dirs(&cpu_dir));
+        if online.is_empty() {
+            return CoreSets::default();
+        }
+
+        let probes = [
+            (CoreSource::Capacity, "cpu_capacity"),
+            (CoreSource::Frequency, "cpufreq/cpuinfo_max_freq"),
+        ];
+        for (source, file) in probes {
+            let ratings: Option<Vec<u64>> = online
+                .cpus()
+                .iter()
+                .map(|cpu| read_number(&cpu_dir.join(format!("cpu{}/{}", cpu, file))))
+                .collect();
+            if let Some(sets) = ratings.and_then(|r| split_by_rating(&online, &r, source)) {
+                return sets;
+            }
+        }
+
+        CoreSets { p_cores: online, e_cores: CpuSet::default(), source: CoreSource::Homogeneous }
+    }
+
+    /// Replace the discovered classes with whichever sets were given.
+    pub fn with_overrides(mut self, p_cores: Option<CpuSet>, e_cores: Option<CpuSet>) -> Self {
+        if p_cores.is_none() && e_cores.is_none() {
+            return self;
+        }
+        if let Some(p_cores) = p_cores {
+            self.p_cores = p_cores;
+        }
+        if let Some(e_cores) = e_cores {
+            self.e_cores = e_cores;
+        }
+        self.source = CoreSource::Explicit;
+        self
+    }
+
+    /// Set to pin fast-biased workers to, if there is one to choose.
+    pub fn fast(&self) -> Option<&CpuSet> {
+        self.pinnable(&self.p_cores)
+    }
+
+    /// Set to pin efficiency-biased workers to, if there is one to choose.
+    pub fn efficient(&self) -> Option<&CpuSet> {
+        self.pinnable(&self.e_cores)
+    }
+
+    // Pinning on a machine with one class of core only takes CPUs away
+    fn pinnable<'a>(&self, set: &'a CpuSet) -> Option<&'a CpuSet> {
+        let useful = self.source == CoreSource::Explicit || (!self.p_cores.is_empty() && !self.e_cores.is_empty());
+        (useful && !set.is_empty()).then_some(set)
+    }
+}
+
+/// Fast cores are those rated above the midpoint of the slowest and fastest,
+/// so the middle tier of a three-tier ARM part counts as fast.
+fn split_by_rating(online: &CpuSet, ratings: &[u64], source: CoreSource) -> Option<CoreSets> {
+    let (min, max) = (*ratings.iter().min()?, *ratings.iter().max()?);
+    if min == max {
+        return None;
+    }
+    let midpoint = min + (max - min) / 2;
+    let (fast, slow): (Vec<_>, Vec<_>) = online.cpus().iter().zip(ratings).partition(|(_, r)| **r > midpoint);
+    Some(CoreSets {
+        p_cores: CpuSet::new(fast.into_iter().map(|(cpu, _)| *cpu).collect()),
+        e_cores: CpuSet::new(slow.into_iter().map(|(cpu, _)| *cpu).collect()),
+        source,
+    })
+}
+
+fn read_cpu_list(path: &Path) -> Option<CpuSet> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+fn read_number(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn list_cpu_dirs(cpu_dir: &Path) -> CpuSet {
+    let Ok(entries) = fs::read_dir(cpu_dir) else { return CpuSet::default() };
+    let cpus = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.strip_prefix("cpu")?.parse().ok())
+        .collect();
+    CpuSet::new(cpus)
+}
@@ -0,0 +1,189 @@
+//! ABDADA (alpha-beta distribué avec droit d'aînesse). Every worker searches
+//! the whole tree, as in Lazy SMP, but they keep out of each other's way: a
+//! worker marks the nodes it is inside, and at a node whose younger brother
+//! another worker is already in, it puts that brother off until the rest
+//! are done. By then the other worker has usually left a table entry that
+//! settles it. The eldest brother has the right of the first-born and is
+//! always searched straight away.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::scheduling::{create_pool_for_policy, CorePolicy};
+use crate::search::iterative::SearchResult;
+use crate::search::lazy_smp::shared_tree_search;
+use crate::search::minimax::{alphabeta_with_context, SearchContext, SharedSearch, INFINITY};
+use crate::search::split::{open_node, Node};
+use crate::types::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Remaining depth from which brothers are put off. Below it the serial
+/// search takes over.
+pub const MIN_DEFER_DEPTH: u32 = 3;
+
+// Slots in the table of busy nodes; two nodes sharing one only puts off a
+// move that didn't need it
+const BUSY_SLOTS: usize = 1 << 16;
+
+/// How many workers are inside each node, by hash. ABDADA proper keeps the
+/// count in the transposition table entry; the packed entries here have no
+/// room, so it sits in a table of its own.
+struct Busy {
+    counts: Vec<AtomicU32>,
+}
+
+impl Busy {
+    fn new() -> Self {
+        Busy {
+            counts: (0..BUSY_SLOTS).map(|_| AtomicU32::new(0)).collect(),
+        }
+    }
+
+    fn slot(&self, hash: u64) -> &AtomicU32 {
+        &self.counts[hash as usize & (BUSY_SLOTS - 1)]
+    }
+
+    fn enter(&self, hash: u64) {
+        self.slot(hash).fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn leave(&self, hash: u64) {
+        self.slot(hash).fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn is_busy(&self, hash: u64) -> bool {
+        self.slot(hash).load(Ordering::Relaxed) > 0
+    }
+}
+
+/// ABDADA search to `depth`; table stats and node counts (helpers included)
+/// end up in `shared`.
+pub fn abdada_search_shared(
+    board: &mut Board,
+    depth: u32,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> (Move, i32) {
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+    let busy = Busy::new();
+    let result = shared_tree_search(board, Some(depth), &pool, shared, false, |board, moves, depth, shared| {
+        abdada_root(board, moves, depth, shared, &busy)
+    });
+    (result.best_move, result.score)
+}
+
+/// ABDADA with the main worker deepening until the limits attached to
+/// `shared` say stop. Returns its deepest completed iteration.
+pub fn abdada_iterative_search(
+    board: &mut Board,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> SearchResult {
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+    let busy = Busy::new();
+    shared_tree_search(board, None, &pool, shared, false, |board, moves, depth, shared| {
+        abdada_root(board, moves, depth, shared, &busy)
+    })
+}
+
+// One pass over the ordered root moves: score and PV of the best, `None` if
+// the search was stopped
+fn abdada_root(board: &mut Board, moves: &[Move], depth: u32, shared: &SharedSearch, busy: &Busy) -> Option<(i32, Vec<Move>)> {
+    let mut ctx = SearchContext::new(shared);
+    // The root is on the hinted line by definition
+    ctx.follow_pv = true;
+
+    let (score, _) = search_moves(board, moves.to_vec(), depth, -INFINITY, INFINITY, &mut ctx, busy);
+    let result = (!ctx.stopped).then(|| (score, ctx.pv().to_vec()));
+    ctx.finish();
+    result
+}
+
+fn abdada(board: &mut Board, depth: u32, alpha: i32, beta: i32, ctx: &mut SearchContext, busy: &Busy) -> i32 {
+    if depth < MIN_DEFER_DEPTH {
+        return alphabeta_with_context(board, depth, alpha, beta, ctx);
+    }
+
+    let (hash, moves) = match open_node(board, depth, alpha, beta, ctx) {
+        Node::Done(score) => return score,
+        Node::Open { hash, moves, .. } => (hash, moves),
+    };
+
+    let (best_score, best_move) = search_moves(board, moves, depth, alpha, beta, ctx, busy);
+    if ctx.stopped {
+        return 0;
+    }
+    ctx.store_node(hash, depth, alpha, beta, best_score, best_move);
+    best_score
+}
+
+// Principal variation search over the ordered `moves` of the node at
+// `ctx.ply`, putting off younger brothers another worker is in. Returns the
+// best score and move and leaves the best line in `ctx`
+fn search_moves(
+    board: &mut Board,
+    moves: Vec<Move>,
+    depth: u32,
+    mut alpha: i32,
+    beta: i32,
+    ctx: &mut SearchContext,
+    busy: &Busy,
+) -> (i32, Option<Move>) {
+    let pv_move = ctx.pv_move();
+    let mut best_score = -INFINITY;
+    let mut best_move = None;
+
+    // A move can be put off once; it then goes at the back of the queue
+    let mut queue: Vec<(Move, bool)> = moves.into_iter().map(|mv| (mv, true)).collect();
+    let mut i = 0;
+    while i < queue.len() {
+        let (mv, may_defer) = queue[i];
+        let eldest = i == 0;
+        i += 1;
+
+        let undo = board.make_move(&mv);
+        let child = board.hash();
+        if !eldest && may_defer && busy.is_busy(child) {
+            board.unmake_move(&mv, undo);
+            queue.push((mv, false));
+            continue;
+        }
+
+        busy.enter(child);
+        ctx.follow_pv = pv_move == Some(mv);
+        ctx.ply += 1;
+        let score = if eldest {
+            -abdada(board, depth - 1, -beta, -alpha, ctx, busy)
+        } else {
+            // Prove the move is no better than alpha; search properly if it is
+            let score = -abdada(board, depth - 1, -alpha - 1, -alpha, ctx, busy);
+            if score > alpha && score < beta && !ctx.stopped {
+                -abdada(board, depth - 1, -beta, -alpha, ctx, busy)
+            } else {
+                score
+            }
+        };
+        ctx.ply -= 1;
+        busy.leave(child);
+        board.unmake_move(&mv, undo);
+        if ctx.stopped {
+            return (0, None);
+        }
+
+        if score > best_score {
+            best_score = score;
+            best_move = Some(mv);
+            if score > alpha {
+                alpha = score;
+                ctx.update_pv(mv);
+            }
+        }
+        if alpha >= beta {
+            ctx.orderer.record_cutoff(board, &mv, ctx.ply, depth);
+            break; // opponent won't allow this line
+        }
+    }
+    (best_score, best_move)
+}
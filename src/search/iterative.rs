@@ -0,0 +1,124 @@
+//! Iterative deepening. The root is searched at depth 1, 2, 3... until the
+//! limits on `SharedSearch` say stop. Each iteration starts with the previous
+//! best move and follows the previous principal variation first, so the
+//! deeper pass cuts off sooner.
+
+use crate::search::minimax::SharedSearch;
+use crate::types::*;
+use std::time::{Duration, Instant};
+
+/// One completed iteration.
+#[derive(Clone, Debug)]
+pub struct IterationInfo {
+    pub depth: u32,
+    pub score: i32,
+    pub pv: Vec<Move>,
+    /// Nodes searched by this iteration alone
+    pub nodes: u64,
+    /// Nodes searched since the search started
+    pub total_nodes: u64,
+    /// Time since the search started
+    pub elapsed: Duration,
+}
+
+/// Outcome of an iterative search. Describes the deepest completed
+/// iteration; `iterations` keeps the earlier ones.
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub best_move: Move,
+    pub score: i32,
+    /// Depth of the last completed iteration, 0 if none completed
+    pub depth: u32,
+    /// Principal variation, `best_move` first. May end early where a
+    /// transposition cut the search.
+    pub pv: Vec<Move>,
+    pub iterations: Vec<IterationInfo>,
+    pub nodes: u64,
+    pub nps: u64,
+    pub elapsed: Duration,
+}
+
+impl SearchResult {
+    /// Result for a position without legal moves, scored `score`.
+    pub fn no_moves(score: i32) -> Self {
+        SearchResult {
+            best_move: Move::new(Square(0), Square(0), None, None),
+            score,
+            depth: 0,
+            pv: Vec::new(),
+            iterations: Vec::new(),
+            nodes: 0,
+            nps: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    /// The PV in coordinate notation, separated by spaces.
+    pub fn pv_string(&self) -> String {
+        pv_string(&self.pv)
+    }
+}
+
+pub fn pv_string(pv: &[Move]) -> String {
+    pv.iter().map(|mv| mv.to_algebraic()).collect::<Vec<_>>().join(" ")
+}
+
+/// Run `pass` at increasing depths over `moves` (non-empty, ordered) while
+/// `shared` allows another iteration, reporting each one to `shared`.
+/// `pass(moves, depth)` returns the score and PV of its best root move, or
+/// `None` if it was stopped.
+pub fn iterate<F>(mut moves: Vec<Move>, shared: &SharedSearch, mut pass: F) -> SearchResult
+where
+    F: FnMut(&[Move], u32) -> Option<(i32, Vec<Move>)>,
+{
+    let start = Instant::now();
+    let start_nodes = shared.nodes();
+    let mut iterations: Vec<IterationInfo> = Vec::new();
+
+    let mut depth = 1;
+    while shared.may_start_iteration(depth) {
+        if let Some(last) = iterations.last() {
+            // Previous best move goes first, the rest keep their order
+            if let Some(pos) = moves.iter().position(|mv| *mv == last.pv[0]) {
+                moves[..=pos].rotate_right(1);
+            }
+            shared.set_pv_hint(&last.pv);
+        }
+
+        let before = shared.nodes();
+        let Some((score, pv)) = pass(&moves, depth) else { break };
+        let info = IterationInfo {
+            depth,
+            score,
+            pv,
+            nodes: shared.nodes() - before,
+            total_nodes: shared.nodes() - start_nodes,
+            elapsed: start.elapsed(),
+        };
+        shared.report(&info);
+        iterations.push(info);
+        depth += 1;
+    }
+    shared.set_pv_hint(&[]);
+
+    let elapsed = start.elapsed();
+    let nodes = shared.nodes() - start_nodes;
+    let nps = (nodes as f64 / elapsed.as_secs_f64().max(1e-9)) as u64;
+
+    // Something to play even if depth 1 got cut off
+    let (best_move, score, depth, pv) = match iterations.last() {
+        Some(last) => (last.pv[0], last.score, last.depth, last.pv.clone()),
+        None => (moves[0], 0, 0, vec![moves[0]]),
+    };
+
+    SearchResult {
+        best_move,
+        score,
+        depth,
+        pv,
+        iterations,
+        nodes,
+        nps,
+        elapsed,
+    }
+}
@@ -0,0 +1,146 @@
+//! Jamboree search. Like YBWC the eldest brother is searched first and alone,
+//! but the younger brothers are then only *tested* in parallel: a null
+//! window at the bound the eldest set shows which of them could be better.
+//! Those that pass are searched properly one after another, in order, with
+//! the window narrowing as they go. The tests are the parallel part; a test
+//! that fails high past beta aborts the rest of them.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::scheduling::CorePolicy;
+use crate::search::iterative::SearchResult;
+use crate::search::minimax::{SearchContext, SharedSearch};
+use crate::search::split::{split_iterative_search, split_node, split_search_shared, split_stopped, start_task, Abort};
+use crate::types::*;
+use rayon::prelude::*;
+
+/// Jamboree search to `depth`; table stats and node counts end up in
+/// `shared`. If `shared` is stopped before the search finishes, the first
+/// ordered move is returned with a score of 0.
+pub fn jamboree_search_shared(
+    board: &mut Board,
+    depth: u32,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> (Move, i32) {
+    split_search_shared(board, depth, policy, threads, mixed_ratio, shared, jamboree_split)
+}
+
+/// Iterative deepening with Jamboree passes, bounded by the limits attached
+/// to `shared`. Returns the deepest completed iteration.
+pub fn jamboree_iterative_search(
+    board: &mut Board,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> SearchResult {
+    split_iterative_search(board, policy, threads, mixed_ratio, shared, jamboree_split)
+}
+
+// The eldest here, then the younger brothers tested in parallel and the
+// survivors searched here (a `SplitFn`)
+fn jamboree_split(
+    board: &mut Board,
+    moves: &[Move],
+    depth: u32,
+    mut alpha: i32,
+    beta: i32,
+    pv_move: Option<Move>,
+    ctx: &mut SearchContext,
+) -> (i32, Option<Move>) {
+    let shared = ctx.shared().expect("split points need a shared search");
+    let eldest = moves[0];
+    let undo = board.make_move(&eldest);
+    ctx.follow_pv = pv_move == Some(eldest);
+    ctx.ply += 1;
+    let eldest_score = -split_node(board, depth - 1, -beta, -alpha, ctx, jamboree_split);
+    ctx.ply -= 1;
+    board.unmake_move(&eldest, undo);
+    if ctx.stopped {
+        return (0, None);
+    }
+    if eldest_score > alpha {
+        alpha = eldest_score;
+        ctx.update_pv(eldest);
+    }
+    if eldest_score >= beta {
+        ctx.orderer.record_cutoff(board, &eldest, ctx.ply, depth);
+        return (eldest_score, Some(eldest));
+    }
+
+    let abort = Abort::new(ctx.abort());
+    let node = *board;
+    let ply = ctx.ply;
+    let orderer = &ctx.orderer;
+    let tested = alpha;
+
+    // Every test runs against the same bound, so together they are one
+    // parallel step whatever the order they finish in
+    let tests: Vec<Option<(i32, Move, Vec<Move>)>> = moves[1..]
+        .par_iter()
+        .map(|&mv| {
+            if abort.is_raised() {
+                return None;
+            }
+            let (mut task, mut child) = start_task(shared, &abort, orderer, &node, mv, ply);
+            let score = -split_node(&mut child, depth - 1, -tested - 1, -tested, &mut task, jamboree_split);
+            let stopped = task.stopped;
+            let line = task.line().to_vec();
+            task.finish();
+            if stopped {
+                return None;
+            }
+
+            if score >= beta {
+                abort.raise();
+            }
+            Some((score, mv, line))
+        })
+        .collect();
+
+    if split_stopped(ctx, shared) {
+        return (0, None);
+    }
+
+    let (mut best_score, mut best_move) = (eldest_score, eldest);
+    // A brother that failed high past beta settles the node
+    if let Some((score, mv, line)) = tests.iter().flatten().find(|(score, _, _)| *score >= beta) {
+        ctx.set_pv(*mv, line);
+        ctx.orderer.record_cutoff(board, mv, ctx.ply, depth);
+        return (*score, Some(*mv));
+    }
+
+    for (score, mv, _) in tests.into_iter().flatten() {
+        // Failed low: no better than the bound it was tested against
+        let score = if score <= tested {
+            score
+        } else {
+            let undo = board.make_move(&mv);
+            ctx.follow_pv = pv_move == Some(mv);
+            ctx.ply += 1;
+            let score = -split_node(board, depth - 1, -beta, -alpha, ctx, jamboree_split);
+            ctx.ply -= 1;
+            board.unmake_move(&mv, undo);
+            if ctx.stopped {
+                return (0, None);
+            }
+            score
+        };
+
+        if score > best_score {
+            best_score = score;
+            best_move = mv;
+            if score > alpha {
+                alpha = score;
+                ctx.update_pv(mv);
+            }
+        }
+        if alpha >= beta {
+            ctx.orderer.record_cutoff(board, &mv, ctx.ply, depth);
+            break;
+        }
+    }
+    (best_score, Some(best_move))
+}
@@ -0,0 +1,130 @@
+//! Lazy SMP. Every worker runs iterative deepening on the whole root and the
+//! workers share nothing but the transposition table. Helpers start on
+//! alternating depths with the root moves rotated, so they wander into
+//! different parts of the tree and leave entries and best moves that the
+//! main worker then picks up. Only the main worker's result counts; helpers
+//! are stopped as soon as it is done. ABDADA (`abdada.rs`) runs its workers
+//! the same way.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::moves::generate_legal_moves;
+use crate::scheduling::{create_pool_for_policy, CorePolicy};
+use crate::search::iterative::{iterate, SearchResult};
+use crate::search::minimax::{search_root, SharedSearch, MATE_SCORE};
+use crate::search::ordering::MoveOrderer;
+use crate::search::time_control::MAX_SEARCH_DEPTH;
+use crate::types::*;
+use rayon::ThreadPool;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Lazy SMP search to `depth`; table stats and node counts (helpers
+/// included) end up in `shared`.
+pub fn lazy_smp_search_shared(
+    board: &mut Board,
+    depth: u32,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> (Move, i32) {
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+    let result = shared_tree_search(board, Some(depth), &pool, shared, true, search_root);
+    (result.best_move, result.score)
+}
+
+/// Lazy SMP with the main worker deepening until the limits attached to
+/// `shared` say stop. Returns its deepest completed iteration.
+pub fn lazy_smp_iterative_search(
+    board: &mut Board,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> SearchResult {
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+    shared_tree_search(board, None, &pool, shared, true, search_root)
+}
+
+/// Every worker of `pool` deepens over the whole root with `pass`, sharing
+/// `shared`'s table; the main worker stops at `max_depth` or the limits on
+/// `shared` and its result is the search's. With `perturb`, helpers rotate
+/// the root moves and odd ones start a ply deeper.
+pub(crate) fn shared_tree_search<P>(
+    board: &mut Board,
+    max_depth: Option<u32>,
+    pool: &ThreadPool,
+    shared: &SharedSearch,
+    perturb: bool,
+    pass: P,
+) -> SearchResult
+where
+    P: Fn(&mut Board, &[Move], u32, &SharedSearch) -> Option<(i32, Vec<Move>)> + Sync,
+{
+    let current_color = board.to_move();
+    let mut moves = generate_legal_moves(board, current_color);
+
+    if moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return SearchResult::no_moves(score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+
+    // Helpers stop on their own signal: stopping `shared` would also stop
+    // whoever is waiting on it, such as a UCI `go infinite`
+    let helpers = SharedSearch::with_table(shared.config, Arc::clone(&shared.tt))
+        .with_stop(Arc::new(AtomicBool::new(false)));
+    let root = *board;
+    let threads = pool.current_num_threads();
+
+    let mut result = pool.install(|| {
+        rayon::scope(|scope| {
+            for id in 1..threads {
+                let (moves, helpers, pass) = (&moves, &helpers, &pass);
+                scope.spawn(move |_| helper(&root, moves, id, perturb, helpers, pass));
+            }
+
+            let result = iterate(moves.clone(), shared, |moves, depth| {
+                if max_depth.is_some_and(|max| depth > max) {
+                    return None;
+                }
+                pass(board, moves, depth, shared)
+            });
+            helpers.stop();
+            result
+        })
+    });
+
+    shared.add_nodes_from(&helpers);
+    result.nodes += helpers.nodes();
+    result.nps = (result.nodes as f64 / result.elapsed.as_secs_f64().max(1e-9)) as u64;
+    result
+}
+
+// Deepen over `moves` until stopped. Perturbed, odd helpers skip depth 1, so
+// at any moment half of them are a ply ahead of the other half
+fn helper<P>(board: &Board, moves: &[Move], id: usize, perturb: bool, helpers: &SharedSearch, pass: &P)
+where
+    P: Fn(&mut Board, &[Move], u32, &SharedSearch) -> Option<(i32, Vec<Move>)>,
+{
+    let mut board = *board;
+    let mut moves = moves.to_vec();
+    let mut depth = 1;
+    if perturb {
+        let shift = id % moves.len();
+        moves.rotate_left(shift);
+        depth += (id % 2) as u32;
+    }
+
+    while depth <= MAX_SEARCH_DEPTH && !helpers.is_stopped() {
+        if pass(&mut board, &moves, depth, helpers).is_none() {
+            break;
+        }
+        depth += 1;
+    }
+}
@@ -1,13 +1,507 @@
 use crate::board::{Board, BoardRepresentation};
-use crate::evaluation::evaluate;
-use crate::moves::generate_legal_moves;
+use crate::evaluation::{evaluate, evaluate_with, piece_value, see, EvalParams};
+use crate::moves::{generate_legal_captures, generate_legal_moves, generate_promotions, retain_legal};
+use crate::scheduling::emulation;
+use crate::search::iterative::{iterate, IterationInfo, SearchResult};
+use crate::search::ordering::{is_quiet, MoveOrderer, OrderingConfig, MAX_PLY};
+use crate::search::selectivity::{self, SelectivityConfig};
+use crate::search::split::Abort;
+use crate::search::time_control::{TimeManager, MAX_SEARCH_DEPTH};
+use crate::search::transposition::{Bound, Probe, TTEntry, TTStats, TranspositionTable, DEFAULT_TT_MB};
 use crate::types::*;
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub const INFINITY: i32 = 1_000_000;
 pub const MATE_SCORE: i32 = 100_000; // high but not Max to allow mate in N moves scoring
+/// Scores at or beyond this are mates: `MATE_SCORE - ply` for mating in `ply` half-moves
+pub const MATE_BOUND: i32 = MATE_SCORE - MAX_PLY as i32;
 
-// Non pruning basic minimax.
-pub fn minimax(board: &mut Board, depth: u32, maximizing_player: bool) -> i32 {
+// Nodes between checks of the stop flag and the clock
+const POLL_INTERVAL: u64 = 1024;
+
+// Slack for positional swings when delta pruning a capture
+const DELTA_MARGIN: i32 = 200;
+
+/// What quiescence search extends at the horizon. Disabled, depth 0 returns
+/// the static evaluation as before.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct QuiescenceConfig {
+    pub enabled: bool,
+    /// Also search pawn pushes that promote to a queen
+    pub promotions: bool,
+    /// When in check, search every evasion instead of standing pat
+    pub evasions: bool,
+    /// Skip captures that can't bring the score back to alpha
+    pub delta_pruning: bool,
+    /// Skip captures that lose material by SEE
+    pub see_pruning: bool,
+}
+
+impl Default for QuiescenceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            promotions: true,
+            evasions: false,
+            delta_pruning: true,
+            see_pruning: true,
+        }
+    }
+}
+
+impl QuiescenceConfig {
+    /// Static evaluation at depth 0.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+}
+
+/// Score for the side to move being checkmated `ply` half-moves from the root.
+pub fn mated_in(ply: usize) -> i32 {
+    -MATE_SCORE + ply as i32
+}
+
+/// Moves until mate for a mate score: positive if the side to move mates,
+/// negative if it gets mated. `None` for ordinary scores.
+pub fn mate_distance(score: i32) -> Option<i32> {
+    if score >= MATE_BOUND {
+        Some((MATE_SCORE - score + 1) / 2)
+    } else if score <= -MATE_BOUND {
+        Some(-(MATE_SCORE + score) / 2)
+    } else {
+        None
+    }
+}
+
+/// Search knobs, read by every worker. Benchmarks flip these to measure each feature.
+#[derive(Clone, Copy, Debug)]
+pub struct SearchConfig {
+    pub tt_size_mb: usize,
+    pub ordering: OrderingConfig,
+    pub quiescence: QuiescenceConfig,
+    pub selectivity: SelectivityConfig,
+    pub eval: EvalParams,
+    /// Root-split workers search against the best root score found so far
+    pub root_bound: bool,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            tt_size_mb: DEFAULT_TT_MB,
+            ordering: OrderingConfig::default(),
+            quiescence: QuiescenceConfig::default(),
+            selectivity: SelectivityConfig::default(),
+            eval: EvalParams::default(),
+            root_bound: true,
+        }
+    }
+}
+
+/// Called with each completed iteration of an iterative search.
+pub type IterationReporter = Box<dyn Fn(&IterationInfo) + Send + Sync>;
+
+/// Everything the workers of one search share: the table, the config, the
+/// node total, the stop signal and the PV to search first.
+pub struct SharedSearch {
+    pub tt: Arc<TranspositionTable>,
+    pub config: SearchConfig,
+    nodes: AtomicU64,
+    qnodes: AtomicU64,
+    stop: Arc<AtomicBool>,
+    time: Option<TimeManager>,
+    pv_hint: Mutex<Vec<Move>>,
+    reporter: Option<IterationReporter>,
+}
+
+impl SharedSearch {
+    pub fn new(config: SearchConfig) -> Self {
+        Self::with_table(config, Arc::new(TranspositionTable::new(config.tt_size_mb)))
+    }
+
+    /// Search with an existing table, e.g. one kept between the moves of a
+    /// game. `config.tt_size_mb` is ignored.
+    pub fn with_table(config: SearchConfig, tt: Arc<TranspositionTable>) -> Self {
+        SharedSearch {
+            tt,
+            config,
+            nodes: AtomicU64::new(0),
+            qnodes: AtomicU64::new(0),
+            stop: Arc::new(AtomicBool::new(false)),
+            time: None,
+            pv_hint: Mutex::new(Vec::new()),
+            reporter: None,
+        }
+    }
+
+    /// Stop through `stop` instead of a flag of our own, so the caller can keep
+    /// the handle before the search is built.
+    pub fn with_stop(mut self, stop: Arc<AtomicBool>) -> Self {
+        self.stop = stop;
+        self
+    }
+
+    /// Have iterative searches pass every completed iteration to `reporter`.
+    pub fn with_reporter(mut self, reporter: IterationReporter) -> Self {
+        self.reporter = Some(reporter);
+        self
+    }
+
+    pub fn report(&self, info: &IterationInfo) {
+        if let Some(reporter) = &self.reporter {
+            reporter(info);
+        }
+    }
+
+    /// Bound the search by `time`'s deadlines and depth limit.
+    pub fn with_time(mut self, time: TimeManager) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    pub fn time(&self) -> Option<&TimeManager> {
+        self.time.as_ref()
+    }
+
+    /// Ask every worker to abandon the current iteration.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Handle for stopping the search from another thread.
+    pub fn stop_handle(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// True once stopped or past the hard deadline or node limit. Polled by searchers.
+    pub fn should_stop(&self) -> bool {
+        if self.is_stopped() {
+            return true;
+        }
+        if self
+            .time
+            .is_some_and(|t| t.hard_limit_reached() || t.node_limit_reached(self.nodes()))
+        {
+            self.stop();
+            return true;
+        }
+        false
+    }
+
+    /// Whether iterative deepening may start another iteration at `depth`.
+    pub fn may_start_iteration(&self, depth: u32) -> bool {
+        match &self.time {
+            Some(time) => {
+                depth <= time.max_depth()
+                    && !time.soft_limit_reached()
+                    && !time.node_limit_reached(self.nodes())
+                    && !self.is_stopped()
+            }
+            None => depth <= MAX_SEARCH_DEPTH && !self.is_stopped(),
+        }
+    }
+
+    /// Line for new workers to search first, root move first. Set between
+    /// iterations by iterative deepening.
+    pub fn set_pv_hint(&self, pv: &[Move]) {
+        *self.pv_hint.lock().unwrap() = pv.to_vec();
+    }
+
+    /// Nodes reported by the workers so far. Running workers report every
+    /// few thousand nodes.
+    pub fn nodes(&self) -> u64 {
+        self.nodes.load(Ordering::Relaxed)
+    }
+
+    /// The part of `nodes` searched by quiescence.
+    pub fn qnodes(&self) -> u64 {
+        self.qnodes.load(Ordering::Relaxed)
+    }
+
+    /// Count the nodes of `other`, e.g. helpers that searched this table
+    /// under a stop signal of their own.
+    pub fn add_nodes_from(&self, other: &SharedSearch) {
+        self.nodes.fetch_add(other.nodes(), Ordering::Relaxed);
+        self.qnodes.fetch_add(other.qnodes(), Ordering::Relaxed);
+    }
+
+    pub fn tt_stats(&self) -> TTStats {
+        self.tt.stats()
+    }
+}
+
+/// Per-worker search state. The shared table is borrowed; ordering tables and
+/// counters are local and folded into the shared totals by `finish`.
+pub struct SearchContext<'a> {
+    shared: Option<&'a SharedSearch>,
+    pub orderer: MoveOrderer,
+    pub quiescence: QuiescenceConfig,
+    pub selectivity: SelectivityConfig,
+    pub eval: EvalParams,
+    /// Distance from the root of the node being searched
+    pub ply: usize,
+    pub nodes: u64,
+    pub qnodes: u64,
+    // Part of `nodes` already added to the shared total
+    reported_nodes: u64,
+    pub tt_stats: TTStats,
+    /// Set once the shared stop signal was seen; the current result is garbage
+    pub stopped: bool,
+    // Triangular PV table: pv[ply] is the best line found from the node at ply
+    pv: Vec<Vec<Move>>,
+    pv_hint: Vec<Move>,
+    // Still on the path of `pv_hint`
+    pub(crate) follow_pv: bool,
+    // Ply of the node that last passed with a null move, so its child can't
+    null_ply: Option<usize>,
+    // Split point this worker searches under, if any
+    abort: Option<&'a Abort<'a>>,
+    // Best root score of a root-split search, which bounds beta at ply 1
+    root_bound: Option<&'a AtomicI32>,
+}
+
+impl<'a> SearchContext<'a> {
+    pub fn new(shared: &'a SharedSearch) -> Self {
+        emulation::start_work();
+        SearchContext {
+            shared: Some(shared),
+            orderer: MoveOrderer::new(shared.config.ordering),
+            quiescence: shared.config.quiescence,
+            selectivity: shared.config.selectivity,
+            eval: shared.config.eval,
+            ply: 0,
+            nodes: 0,
+            qnodes: 0,
+            reported_nodes: 0,
+            tt_stats: TTStats::default(),
+            stopped: false,
+            pv: vec![Vec::new(); MAX_PLY + 1],
+            pv_hint: shared.pv_hint.lock().unwrap().clone(),
+            follow_pv: false,
+            null_ply: None,
+            abort: None,
+            root_bound: None,
+        }
+    }
+
+    pub fn without_tt() -> Self {
+        emulation::start_work();
+        SearchContext {
+            shared: None,
+            orderer: MoveOrderer::new(OrderingConfig::default()),
+            quiescence: QuiescenceConfig::default(),
+            selectivity: SelectivityConfig::default(),
+            eval: EvalParams::default(),
+            ply: 0,
+            nodes: 0,
+            qnodes: 0,
+            reported_nodes: 0,
+            tt_stats: TTStats::default(),
+            stopped: false,
+            pv: vec![Vec::new(); MAX_PLY + 1],
+            pv_hint: Vec::new(),
+            follow_pv: false,
+            null_ply: None,
+            abort: None,
+            root_bound: None,
+        }
+    }
+
+    /// Stop as soon as `abort` (or a split point above it) is raised.
+    pub(crate) fn with_abort(mut self, abort: &'a Abort<'a>) -> Self {
+        self.abort = Some(abort);
+        self
+    }
+
+    /// Tighten beta at ply 1 as other root moves raise `bound`, the best root
+    /// score so far.
+    pub(crate) fn with_root_bound(mut self, bound: &'a AtomicI32) -> Self {
+        self.root_bound = Some(bound);
+        self
+    }
+
+    // `beta` at the current node, lowered at ply 1 to what the root bound
+    // leaves of the root move
+    fn root_beta(&self, beta: i32) -> i32 {
+        match self.root_bound {
+            Some(bound) if self.ply == 1 => beta.min(-bound.load(Ordering::Relaxed)),
+            _ => beta,
+        }
+    }
+
+    /// The search this context works for; `None` without a table.
+    pub(crate) fn shared(&self) -> Option<&'a SharedSearch> {
+        self.shared
+    }
+
+    pub(crate) fn abort(&self) -> Option<&'a Abort<'a>> {
+        self.abort
+    }
+
+    /// Prepare to search the position after root move `mv`.
+    pub fn start_root_move(&mut self, mv: Move) {
+        self.ply = 1;
+        self.follow_pv = self.pv_hint.first() == Some(&mv);
+    }
+
+    /// Best line from the node the search was started at.
+    pub fn pv(&self) -> &[Move] {
+        &self.pv[0]
+    }
+
+    /// Principal variation of the root move just searched, `mv` first.
+    pub fn root_pv(&self, mv: Move) -> Vec<Move> {
+        let mut line = vec![mv];
+        line.extend_from_slice(&self.pv[1]);
+        line
+    }
+
+    /// Best line from the node at the current ply.
+    pub(crate) fn line(&self) -> &[Move] {
+        self.pv.get(self.ply).map_or(&[], |line| line.as_slice())
+    }
+
+    /// Best line at this ply becomes `mv` followed by `rest`.
+    pub(crate) fn set_pv(&mut self, mv: Move, rest: &[Move]) {
+        if let Some(line) = self.pv.get_mut(self.ply) {
+            line.clear();
+            line.push(mv);
+            line.extend_from_slice(rest);
+        }
+    }
+
+    // The hint's move at this ply, while the search is still on the hinted line
+    pub(crate) fn pv_move(&mut self) -> Option<Move> {
+        if !self.follow_pv {
+            return None;
+        }
+        let mv = self.pv_hint.get(self.ply).copied();
+        self.follow_pv = mv.is_some();
+        mv
+    }
+
+    pub(crate) fn clear_pv(&mut self) {
+        if let Some(line) = self.pv.get_mut(self.ply) {
+            line.clear();
+        }
+    }
+
+    // Best line at this ply becomes `mv` followed by the child's line
+    pub(crate) fn update_pv(&mut self, mv: Move) {
+        let ply = self.ply;
+        if ply + 1 >= self.pv.len() {
+            return;
+        }
+        let (head, tail) = self.pv.split_at_mut(ply + 1);
+        head[ply].clear();
+        head[ply].push(mv);
+        head[ply].extend_from_slice(&tail[0]);
+    }
+
+    pub(crate) fn poll_stop(&mut self) -> bool {
+        if !self.stopped && self.nodes.is_multiple_of(POLL_INTERVAL) {
+            emulation::throttle();
+            if let Some(shared) = self.shared {
+                // Keep the shared count current for node limits and `info` output
+                shared.nodes.fetch_add(self.nodes - self.reported_nodes, Ordering::Relaxed);
+                self.reported_nodes = self.nodes;
+                self.stopped = shared.should_stop() || self.abort.is_some_and(|a| a.is_raised());
+            }
+        }
+        self.stopped
+    }
+
+    /// Look this node up: the score if the table settles it outright, and
+    /// the stored best move either way.
+    pub(crate) fn probe_node(&mut self, hash: u64, depth: u32, alpha: i32, beta: i32) -> (Option<i32>, Option<Move>) {
+        let Some(entry) = self.probe(hash) else {
+            return (None, None);
+        };
+        // Only same-depth entries cut: a deeper score would make results depend on
+        // which thread wrote first, and parallel runs must match serial ones
+        if entry.depth as u32 != depth {
+            return (None, entry.best_move);
+        }
+        let score = score_from_tt(entry.score, self.ply);
+        let cutoff = match entry.bound {
+            Bound::Exact => {
+                if let (Some(mv), Some(line)) = (entry.best_move, self.pv.get_mut(self.ply)) {
+                    line.push(mv);
+                }
+                true
+            }
+            Bound::Lower => score >= beta,
+            Bound::Upper => score <= alpha,
+        };
+        (cutoff.then_some(score), entry.best_move)
+    }
+
+    /// Store the result of a node searched with the window
+    /// `original_alpha..beta`.
+    pub(crate) fn store_node(&mut self, hash: u64, depth: u32, original_alpha: i32, beta: i32, best_score: i32, best_move: Option<Move>) {
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.store(hash, depth, bound, score_to_tt(best_score, self.ply), best_move);
+    }
+
+    fn probe(&mut self, hash: u64) -> Option<TTEntry> {
+        let tt = &self.shared?.tt;
+        self.tt_stats.probes += 1;
+        match tt.probe(hash) {
+            Probe::Hit(entry) => {
+                self.tt_stats.hits += 1;
+                Some(entry)
+            }
+            Probe::Miss => {
+                self.tt_stats.misses += 1;
+                None
+            }
+            Probe::Collision => {
+                self.tt_stats.misses += 1;
+                self.tt_stats.collisions += 1;
+                None
+            }
+        }
+    }
+
+    fn store(&mut self, hash: u64, depth: u32, bound: Bound, score: i32, best_move: Option<Move>) {
+        if let Some(shared) = self.shared {
+            shared.tt.store(hash, depth, bound, score, best_move);
+            self.tt_stats.stores += 1;
+        }
+    }
+
+    /// Flush the local counters into the shared totals.
+    pub fn finish(self) {
+        emulation::throttle();
+        if let Some(shared) = self.shared {
+            shared.tt.record(&self.tt_stats);
+            shared.nodes.fetch_add(self.nodes - self.reported_nodes, Ordering::Relaxed);
+            shared.qnodes.fetch_add(self.qnodes, Ordering::Relaxed);
+        }
+    }
+}
+
+// Non pruning basic negamax.
+pub fn minimax(board: &mut Board, depth: u32) -> i32 {
+    minimax_at(board, depth, 0)
+}
+
+fn minimax_at(board: &mut Board, depth: u32, ply: usize) -> i32 {
     if depth == 0 {
         return evaluate(board); // leaf
     }
@@ -17,95 +511,359 @@ pub fn minimax(board: &mut Board, depth: u32, maximizing_player: bool) -> i32 {
 
     if moves.is_empty() {
         if board.is_in_check(current_color) {
-            // Checkmate - return negative score for losing side
-            return if maximizing_player {
-                -MATE_SCORE
-            } else {
-                MATE_SCORE
-            };
+            // Checkmate - side to move lost
+            return mated_in(ply);
         } else {
             // Stalemate - draw
             return 0;
         }
     }
 
-    if maximizing_player {
-        let mut max_eval = i32::MIN;
-        for mv in moves {
-            let undo = board.make_move(&mv);
-            let eval = minimax(board, depth - 1, false); // Opp wants min
-            board.unmake_move(&mv, undo);
-            max_eval = max_eval.max(eval); // keep best score
-        }
-        max_eval
-    } else {
-        let mut min_eval = i32::MAX;
-        for mv in moves {
-            let undo = board.make_move(&mv);
-            let eval = minimax(board, depth - 1, true); // my turn, want max
-            board.unmake_move(&mv, undo);
-            min_eval = min_eval.min(eval); // keep worst score
-        }
-        min_eval
+    let mut best = -INFINITY;
+    for mv in moves {
+        let undo = board.make_move(&mv);
+        let eval = -minimax_at(board, depth - 1, ply + 1); // opponent's best is our worst
+        board.unmake_move(&mv, undo);
+        best = best.max(eval);
     }
+    best
 }
 
-pub fn alphabeta(
+pub fn alphabeta(board: &mut Board, depth: u32, alpha: i32, beta: i32) -> i32 {
+    alphabeta_with_context(board, depth, alpha, beta, &mut SearchContext::without_tt())
+}
+
+/// Negamax principal variation search. Scores are from the side to move's
+/// point of view and may fall outside `alpha..beta` (fail-soft). The first
+/// move gets the full window; the rest are searched with a null window and
+/// only re-searched if they beat alpha. `ctx.selectivity` adds pruning,
+/// reductions and extensions on top.
+pub fn alphabeta_with_context(
     board: &mut Board,
     depth: u32,
     mut alpha: i32,
     mut beta: i32,
-    maximizing_player: bool,
+    ctx: &mut SearchContext,
 ) -> i32 {
+    let selectivity = ctx.selectivity;
+    let in_check = selectivity.any() && board.is_in_check(board.to_move());
+    let depth = if selectivity.check_extensions && in_check && ctx.ply < MAX_PLY / 2 {
+        depth + 1
+    } else {
+        depth
+    };
+
+    if depth == 0 && ctx.quiescence.enabled {
+        return quiescence(board, alpha, beta, ctx);
+    }
+
+    ctx.nodes += 1;
+    ctx.clear_pv();
+    if ctx.poll_stop() {
+        return 0; // discarded by the caller
+    }
     if depth == 0 {
-        return evaluate(board);
+        return evaluate_with(board, &ctx.eval);
+    }
+
+    let hash = board.hash();
+
+    let (cutoff, tt_move) = ctx.probe_node(hash, depth, alpha, beta);
+    if let Some(score) = cutoff {
+        return score;
     }
 
     let current_color = board.to_move();
-    let moves = generate_legal_moves(board, current_color);
+    let pv_node = beta.saturating_sub(alpha) > 1;
+    // Only the zero-window nodes that make up most of the tree are pruned
+    let static_eval = if selectivity.any() && !pv_node && !in_check {
+        Some(evaluate_with(board, &ctx.eval))
+    } else {
+        None
+    };
+
+    if let Some(eval) = static_eval {
+        if selectivity.reverse_futility
+            && depth <= selectivity::REVERSE_FUTILITY_DEPTH
+            && beta.abs() < MATE_BOUND
+            && eval - selectivity::reverse_futility_margin(depth) >= beta
+        {
+            return eval;
+        }
+
+        if selectivity.null_move
+            && depth >= selectivity::NULL_MOVE_MIN_DEPTH
+            && eval >= beta
+            && ctx.ply > 0
+            && ctx.null_ply != Some(ctx.ply - 1)
+            && selectivity::has_non_pawn_material(board, current_color)
+        {
+            if let Some(score) = null_move_search(board, depth, beta, ctx) {
+                return score;
+            }
+            if ctx.stopped {
+                return 0;
+            }
+        }
+    }
+
+    let mut moves = generate_legal_moves(board, current_color);
 
     if moves.is_empty() {
         if board.is_in_check(current_color) {
-            //Checkmate - return negative score for losing side
-            return if maximizing_player {
-                -MATE_SCORE
-            } else {
-                MATE_SCORE
-            };
+            //Checkmate - the sooner the worse
+            return mated_in(ctx.ply);
         } else {
             //Stalemate - draw
             return 0;
         }
     }
 
-    if maximizing_player {
-        for mv in moves {
-            let undo = board.make_move(&mv);
-            let eval = alphabeta(board, depth - 1, alpha, beta, false); // false = other player's turn
+    let pv_move = ctx.pv_move();
+    ctx.orderer.order_moves(board, &mut moves, ctx.ply, pv_move.or(tt_move));
+
+    let original_alpha = alpha;
+    let mut best_score = -INFINITY;
+    let mut best_move = None;
+
+    // Quiet moves that can't lift the static evaluation back to alpha
+    let futile = selectivity.futility
+        && depth <= selectivity::FUTILITY_DEPTH
+        && alpha.abs() < MATE_BOUND
+        && static_eval.is_some_and(|eval| eval + selectivity::futility_margin(depth) <= alpha);
+
+    for (i, mv) in moves.into_iter().enumerate() {
+        if i > 0 {
+            // Another root move may have raised the bound since the last move
+            let root_beta = ctx.root_beta(beta);
+            if best_score >= root_beta {
+                beta = root_beta;
+                break;
+            }
+            beta = root_beta.max(alpha + 1);
+        }
+        let quiet = is_quiet(board, &mv);
+        let undo = board.make_move(&mv);
+        let gives_check = selectivity.any() && quiet && board.is_in_check(board.to_move());
+        let ordinary = quiet && !gives_check && !in_check;
+        if futile && i > 0 && ordinary {
             board.unmake_move(&mv, undo);
-            alpha = alpha.max(eval);
-            if beta <= alpha {
-                break; // stop searching, prune, opp won't let us reach here.
+            continue;
+        }
+        let reduction = if selectivity.lmr
+            && depth >= selectivity::LMR_MIN_DEPTH
+            && i >= selectivity::LMR_MIN_MOVE
+            && ordinary
+        {
+            // PV nodes are reduced a ply less
+            selectivity::lmr_reduction(depth, i).saturating_sub(pv_node as u32)
+        } else {
+            0
+        };
+
+        ctx.follow_pv = pv_move == Some(mv);
+        ctx.ply += 1;
+        let score = if i == 0 {
+            -alphabeta_with_context(board, depth - 1, -beta, -alpha, ctx)
+        } else {
+            // Prove the move is no better than alpha; search properly if it is
+            let mut score = -alphabeta_with_context(board, depth - 1 - reduction, -alpha - 1, -alpha, ctx);
+            if reduction > 0 && score > alpha {
+                score = -alphabeta_with_context(board, depth - 1, -alpha - 1, -alpha, ctx);
+            }
+            if score > alpha && score < beta {
+                -alphabeta_with_context(board, depth - 1, -beta, -alpha, ctx)
+            } else {
+                score
             }
+        };
+        ctx.ply -= 1;
+        board.unmake_move(&mv, undo);
+        if ctx.stopped {
+            return 0;
         }
-        alpha
+
+        if score > best_score {
+            best_score = score;
+            best_move = Some(mv);
+            if score > alpha {
+                alpha = score;
+                ctx.update_pv(mv);
+            }
+        }
+        if alpha >= beta {
+            ctx.orderer.record_cutoff(board, &mv, ctx.ply, depth);
+            break; // opponent won't allow this line
+        }
+    }
+
+    ctx.store_node(hash, depth, original_alpha, beta, best_score, best_move);
+
+    best_score
+}
+
+// Pass, and search the opponent's reply shallower with a null window at
+// beta. If even two moves in a row don't get them under beta, the node fails
+// high. Returns the cutoff score, if there is one
+fn null_move_search(board: &Board, depth: u32, beta: i32, ctx: &mut SearchContext) -> Option<i32> {
+    let mut child = *board;
+    child.set_en_passant(None);
+    child.set_to_move(match board.to_move() {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    });
+
+    let follow_pv = std::mem::replace(&mut ctx.follow_pv, false);
+    let null_ply = ctx.null_ply.replace(ctx.ply);
+    ctx.ply += 1;
+    let reduced = (depth - 1).saturating_sub(selectivity::null_move_reduction(depth));
+    let score = -alphabeta_with_context(&mut child, reduced, -beta, -beta + 1, ctx);
+    ctx.ply -= 1;
+    ctx.null_ply = null_ply;
+    ctx.follow_pv = follow_pv;
+
+    // Mates found after a pass aren't real
+    (!ctx.stopped && score >= beta).then(|| score.min(MATE_BOUND - 1))
+}
+
+/// Search captures (and per `ctx.quiescence`, promotions and check evasions)
+/// until the position is quiet, so the horizon never falls mid-exchange. The
+/// side to move may stand pat on the static evaluation instead.
+pub fn quiescence(board: &mut Board, mut alpha: i32, beta: i32, ctx: &mut SearchContext) -> i32 {
+    ctx.nodes += 1;
+    ctx.qnodes += 1;
+    ctx.clear_pv();
+    if ctx.poll_stop() {
+        return 0; // discarded by the caller
+    }
+
+    let config = ctx.quiescence;
+    let current_color = board.to_move();
+    let evading = config.evasions && board.is_in_check(current_color);
+    let stand_pat = evaluate_with(board, &ctx.eval);
+
+    if ctx.ply >= MAX_PLY {
+        return stand_pat;
+    }
+
+    let mut best_score = -INFINITY;
+    let mut moves = if evading {
+        generate_legal_moves(board, current_color)
     } else {
-        for mv in moves {
-            let undo = board.make_move(&mv);
-            let eval = alphabeta(board, depth - 1, alpha, beta, true); // true = other player's turn
-            board.unmake_move(&mv, undo);
-            beta = beta.min(eval);
-            if beta <= alpha {
-                break; // stop searching, prune, we won't let opp reach here.
+        // Standing pat is a lower bound for the side to move
+        if stand_pat >= beta {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+        best_score = stand_pat;
+
+        let mut moves = generate_legal_captures(board, current_color);
+        if config.promotions {
+            let promotions = generate_promotions(board, current_color);
+            moves.extend(retain_legal(board, current_color, promotions));
+        }
+        // Underpromotions are never the quiet-making move
+        moves.retain(|mv| mv.promotion.is_none_or(|p| p == PieceType::Queen));
+        moves
+    };
+
+    if moves.is_empty() {
+        if evading {
+            return mated_in(ctx.ply);
+        }
+        return stand_pat;
+    }
+
+    // Always MVV-LVA: unordered captures blow up the tree whatever the main
+    // search ordering is set to
+    moves.sort_by_cached_key(|mv| {
+        let attacker = board.get_piece(mv.from).map_or(0, |p| piece_value(p.piece_type));
+        Reverse((material_gain(board, mv), -attacker))
+    });
+
+    for mv in moves {
+        if config.delta_pruning && !evading && stand_pat + material_gain(board, &mv) + DELTA_MARGIN <= alpha {
+            continue;
+        }
+        if config.see_pruning && !evading && loses_material(board, &mv) {
+            continue;
+        }
+        let undo = board.make_move(&mv);
+        ctx.ply += 1;
+        let score = -quiescence(board, -beta, -alpha, ctx);
+        ctx.ply -= 1;
+        board.unmake_move(&mv, undo);
+        if ctx.stopped {
+            return 0;
+        }
+
+        if score > best_score {
+            best_score = score;
+            if score > alpha {
+                alpha = score;
+                ctx.update_pv(mv);
             }
         }
-        beta
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    best_score
+}
+
+// Material the mover wins with `mv`: the captured piece plus any promotion gain
+fn material_gain(board: &Board, mv: &Move) -> i32 {
+    let captured = if mv.special_move == Some(SpecialMove::EnPassant) {
+        piece_value(PieceType::Pawn)
+    } else {
+        board.get_piece(mv.to).map_or(0, |p| piece_value(p.piece_type))
+    };
+    let promotion = mv.promotion.map_or(0, |p| piece_value(p) - piece_value(PieceType::Pawn));
+    captured + promotion
+}
+
+// Only a piece worth more than what it took can be lost at a profit, so the
+// full exchange is skipped for the rest
+fn loses_material(board: &Board, mv: &Move) -> bool {
+    let mover = board.get_piece(mv.from).map_or(0, |p| piece_value(p.piece_type));
+    let on_square = mv.promotion.map_or(mover, piece_value);
+    on_square > material_gain(board, mv) && see(board, *mv) < 0
+}
+
+// The table is shared by nodes at different plies, so mate scores are stored
+// as distance from the node rather than from the root
+fn score_to_tt(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score + ply as i32
+    } else if score <= -MATE_BOUND {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn score_from_tt(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score - ply as i32
+    } else if score <= -MATE_BOUND {
+        score + ply as i32
+    } else {
+        score
     }
 }
 
 pub fn search(board: &mut Board, depth: u32) -> (Move, i32) {
+    let shared = SharedSearch::new(SearchConfig::default());
+    search_shared(board, depth, &shared)
+}
+
+/// Serial root search; table stats and node counts end up in `shared`.
+/// If `shared` is stopped before the search finishes, the first ordered move is
+/// returned with a score of 0.
+pub fn search_shared(board: &mut Board, depth: u32, shared: &SharedSearch) -> (Move, i32) {
     let current_color = board.to_move();
-    let moves = generate_legal_moves(board, current_color);
+    let mut moves = generate_legal_moves(board, current_color);
 
     if moves.is_empty() {
         let dummy_move = Move::new(Square(0), Square(0), None, None);
@@ -117,19 +875,70 @@ pub fn search(board: &mut Board, depth: u32) -> (Move, i32) {
         return (dummy_move, score);
     }
 
-    let mut best_move = moves[0];
-    let mut best_score = i32::MIN;
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+    search_root(board, &moves, depth, shared)
+        .map(|(score, pv)| (pv[0], score))
+        .unwrap_or((moves[0], 0))
+}
 
-    for mv in moves {
+/// Iterative deepening bounded by the limits attached to `shared` (unbounded
+/// until stopped without them). Returns the deepest completed iteration.
+pub fn search_timed(board: &mut Board, shared: &SharedSearch) -> (Move, i32) {
+    let result = iterative_search(board, shared);
+    (result.best_move, result.score)
+}
+
+/// Serial iterative deepening with the PV and per-depth record of the search.
+pub fn iterative_search(board: &mut Board, shared: &SharedSearch) -> SearchResult {
+    let current_color = board.to_move();
+    let mut moves = generate_legal_moves(board, current_color);
+
+    if moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE //We're checkmated
+        } else {
+            0 //Stalemate
+        };
+        return SearchResult::no_moves(score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+    iterate(moves, shared, |moves, depth| search_root(board, moves, depth, shared))
+}
+
+/// One fixed-depth pass over ordered root moves: score and PV of the best.
+/// `None` if the search was stopped.
+pub(crate) fn search_root(board: &mut Board, moves: &[Move], depth: u32, shared: &SharedSearch) -> Option<(i32, Vec<Move>)> {
+    let mut ctx = SearchContext::new(shared);
+
+    let mut best_pv = vec![moves[0]];
+    let mut best_score = -INFINITY;
+
+    for (i, &mv) in moves.iter().enumerate() {
         let undo = board.make_move(&mv);
-        let score = -alphabeta(board, depth - 1, -i32::MAX, i32::MAX, false);
+        ctx.start_root_move(mv);
+        let mut score = if i == 0 {
+            -INFINITY
+        } else {
+            -alphabeta_with_context(board, depth - 1, -best_score - 1, -best_score, &mut ctx)
+        };
+        // First move, or one that beat the best so far: get its exact score
+        if i == 0 || (score > best_score && !ctx.stopped) {
+            ctx.start_root_move(mv);
+            score = -alphabeta_with_context(board, depth - 1, -INFINITY, -best_score, &mut ctx);
+        }
         board.unmake_move(&mv, undo);
 
+        if ctx.stopped {
+            ctx.finish();
+            return None;
+        }
         if score > best_score {
             best_score = score;
-            best_move = mv;
+            best_pv = ctx.root_pv(mv);
         }
     }
 
-    (best_move, best_score)
+    ctx.finish();
+    Some((best_score, best_pv))
 }
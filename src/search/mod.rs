@@ -1,12 +1,29 @@
+pub mod abdada;
 pub mod fault_tolerant;
+pub mod iterative;
+pub mod jamboree;
+pub mod lazy_smp;
 pub mod minimax;
 pub mod ordering;
 pub mod parallel;
 pub mod probe;
 pub mod recovery;
+pub mod selectivity;
+pub mod split;
 pub mod time_control;
 pub mod transposition;
+pub mod work_stealing;
+pub mod ybwc;
 
+pub use abdada::{abdada_iterative_search, abdada_search_shared};
 pub use fault_tolerant::{should_inject_panic, with_recovery};
-pub use minimax::{alphabeta, search};
-pub use parallel::{parallel_search, parallel_search_with_fault, parallel_search_with_policy, two_phase_search, TwoPhaseConfig, TwoPhaseMetrics, two_phase_search_with_metrics};
+pub use iterative::{IterationInfo, SearchResult};
+pub use minimax::{alphabeta, iterative_search, quiescence, search, search_shared, search_timed, QuiescenceConfig, SearchConfig, SearchContext, SharedSearch};
+pub use ordering::OrderingConfig;
+pub use selectivity::SelectivityConfig;
+pub use jamboree::{jamboree_iterative_search, jamboree_search_shared};
+pub use lazy_smp::{lazy_smp_iterative_search, lazy_smp_search_shared};
+pub use parallel::{parallel_search, parallel_search_with_mode, parallel_iterative_search_with_mode, two_phase_search_shared, ParallelMode, parallel_search_with_fault, parallel_search_with_policy, parallel_search_shared, parallel_search_timed, parallel_iterative_search, two_phase_search, two_phase_iterative_search, two_phase_search_timed, TwoPhaseConfig, TwoPhaseMetrics, two_phase_search_with_metrics};
+pub use ybwc::{ybwc_iterative_search, ybwc_search_shared};
+pub use time_control::{TimeControl, TimeManager};
+pub use transposition::{TTStats, TranspositionTable, DEFAULT_TT_MB};
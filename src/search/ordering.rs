@@ -1 +1,168 @@
+//! Move ordering for alpha-beta. Moves are scored and sorted best-first:
+//! TT move, then captures by MVV-LVA, promotions, killers, quiet moves by
+//! butterfly history and finally captures that lose material by SEE. Each
+//! heuristic can be switched off through `OrderingConfig` so benchmarks can
+//! measure what it buys.
 
+use crate::board::{Board, BoardRepresentation};
+use crate::evaluation::{piece_value, see};
+use crate::types::*;
+use std::cmp::Reverse;
+
+/// Deepest ply that keeps killer moves
+pub const MAX_PLY: usize = 128;
+
+const TT_MOVE_SCORE: i32 = 1_000_000;
+// Clear of promotions even when the king captures a pawn
+const CAPTURE_SCORE: i32 = 200_000;
+// Below every quiet move
+const BAD_CAPTURE_SCORE: i32 = -100_000;
+const PROMOTION_SCORE: i32 = 90_000;
+const KILLER_SCORE: i32 = 80_000;
+// History is halved once any entry passes this, so it stays below the killers
+const HISTORY_MAX: u32 = 60_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OrderingConfig {
+    pub tt_move: bool,
+    pub mvv_lva: bool,
+    pub killers: bool,
+    pub history: bool,
+    /// Move captures that lose material by SEE behind the quiet moves.
+    /// Only applies with `mvv_lva`
+    pub see: bool,
+}
+
+impl Default for OrderingConfig {
+    fn default() -> Self {
+        Self {
+            tt_move: true,
+            mvv_lva: true,
+            killers: true,
+            history: true,
+            see: true,
+        }
+    }
+}
+
+impl OrderingConfig {
+    /// Raw `generate_legal_moves` order, as before move ordering existed.
+    pub fn none() -> Self {
+        Self {
+            tt_move: false,
+            mvv_lva: false,
+            killers: false,
+            history: false,
+            see: false,
+        }
+    }
+}
+
+/// Per-thread killer and history tables. Not shared: each worker learns from
+/// its own subtree.
+#[derive(Clone)]
+pub struct MoveOrderer {
+    config: OrderingConfig,
+    killers: Vec<[Option<Move>; 2]>,
+    history: Box<[[[u32; 64]; 64]; 2]>, // [color][from][to]
+}
+
+impl MoveOrderer {
+    pub fn new(config: OrderingConfig) -> Self {
+        MoveOrderer {
+            config,
+            killers: vec![[None; 2]; MAX_PLY],
+            history: Box::new([[[0; 64]; 64]; 2]),
+        }
+    }
+
+    pub fn config(&self) -> &OrderingConfig {
+        &self.config
+    }
+
+    pub fn score_move(&self, board: &Board, mv: &Move, ply: usize, tt_move: Option<Move>) -> i32 {
+        if self.config.tt_move && tt_move == Some(*mv) {
+            return TT_MOVE_SCORE;
+        }
+
+        if self.config.mvv_lva {
+            if let Some(victim) = captured_piece(board, mv) {
+                let attacker = board.get_piece(mv.from).map_or(PieceType::Pawn, |p| p.piece_type);
+                // Most valuable victim first, cheapest attacker breaks ties
+                let mvv_lva = piece_value(victim) * 10 - piece_value(attacker);
+                // Only a capture with a dearer piece can lose material
+                if self.config.see && piece_value(attacker) > piece_value(victim) && see(board, *mv) < 0 {
+                    return BAD_CAPTURE_SCORE + mvv_lva;
+                }
+                return CAPTURE_SCORE + mvv_lva;
+            }
+            if let Some(promo) = mv.promotion {
+                return PROMOTION_SCORE + piece_value(promo);
+            }
+        }
+
+        if self.config.killers && ply < MAX_PLY {
+            if self.killers[ply][0] == Some(*mv) {
+                return KILLER_SCORE;
+            }
+            if self.killers[ply][1] == Some(*mv) {
+                return KILLER_SCORE - 1;
+            }
+        }
+
+        if self.config.history {
+            return self.history[color_index(board.to_move())][mv.from.0 as usize][mv.to.0 as usize] as i32;
+        }
+
+        0
+    }
+
+    /// Sort `moves` best-first. Equal scores keep generation order.
+    pub fn order_moves(&self, board: &Board, moves: &mut [Move], ply: usize, tt_move: Option<Move>) {
+        moves.sort_by_cached_key(|mv| Reverse(self.score_move(board, mv, ply, tt_move)));
+    }
+
+    /// Remember a quiet move that caused a beta cutoff.
+    pub fn record_cutoff(&mut self, board: &Board, mv: &Move, ply: usize, depth: u32) {
+        if !is_quiet(board, mv) {
+            return;
+        }
+
+        if self.config.killers && ply < MAX_PLY && self.killers[ply][0] != Some(*mv) {
+            self.killers[ply][1] = self.killers[ply][0];
+            self.killers[ply][0] = Some(*mv);
+        }
+
+        if self.config.history {
+            let side = color_index(board.to_move());
+            let entry = &mut self.history[side][mv.from.0 as usize][mv.to.0 as usize];
+            *entry += depth * depth;
+            if *entry > HISTORY_MAX {
+                for from in self.history[side].iter_mut() {
+                    for value in from.iter_mut() {
+                        *value /= 2;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Neither a capture nor a promotion.
+pub fn is_quiet(board: &Board, mv: &Move) -> bool {
+    mv.promotion.is_none() && captured_piece(board, mv).is_none()
+}
+
+fn captured_piece(board: &Board, mv: &Move) -> Option<PieceType> {
+    if mv.special_move == Some(SpecialMove::EnPassant) {
+        return Some(PieceType::Pawn);
+    }
+    board.get_piece(mv.to).map(|p| p.piece_type)
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
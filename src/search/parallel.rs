@@ -1,24 +1,55 @@
 use crate::board::{Board, BoardRepresentation};
+use crate::search::iterative::{iterate, SearchResult};
 use crate::moves::generate_legal_moves;
 use crate::scheduling::{create_e_core_pool, create_p_core_pool, create_pool_for_policy, CorePolicy};
+use crate::search::abdada::{abdada_iterative_search, abdada_search_shared};
 use crate::search::fault_tolerant::should_inject_panic;
-use crate::search::minimax::alphabeta;
-use crate::search::minimax::MATE_SCORE;
+use crate::search::jamboree::{jamboree_iterative_search, jamboree_search_shared};
+use crate::search::lazy_smp::{lazy_smp_iterative_search, lazy_smp_search_shared};
+use crate::search::minimax::{alphabeta, alphabeta_with_context, SearchConfig, SearchContext, SharedSearch};
+use crate::search::minimax::{INFINITY, MATE_SCORE};
+use crate::search::ordering::MoveOrderer;
+use crate::search::split::Abort;
 use crate::search::probe::{classify_moves_with_config, probe_root_moves, ClassificationConfig};
+use crate::search::transposition::TTStats;
+use crate::search::ybwc::{ybwc_iterative_search, ybwc_search_shared};
+use crate::search::work_stealing::adaptive_search;
 use crate::types::*;
+use clap::ValueEnum;
 use rayon::prelude::*;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use rayon::ThreadPool;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
 use std::time::Instant;
 
 static MOVE_COUNTER: AtomicUsize = AtomicUsize::new(0);
 static SHOULD_PANIC: AtomicBool = AtomicBool::new(false);
 
+/// How a multi-threaded search splits its work.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ParallelMode {
+    /// Each root move is a task of its own, searched with a full window
+    #[default]
+    RootSplit,
+    /// Every thread deepens the whole tree; they share only the table
+    LazySmp,
+    /// Heavy root moves on P-cores, then light ones on E-cores
+    TwoPhase,
+    /// Every node searches its first move, then splits the rest with its bound
+    Ybwc,
+    /// As YBWC, but the rest are tested in parallel and searched one by one
+    Jamboree,
+    /// As Lazy SMP, but a thread puts off moves another thread is inside
+    Abdada,
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct TwoPhaseConfig {
     pub probe_depth: u8,
     pub p_core_threads: usize,
     pub e_core_threads: usize,
     pub classification: ClassificationConfig,
+    /// Settings for the table and ordering shared by both phases
+    pub search: SearchConfig,
 }
 
 impl Default for TwoPhaseConfig {
@@ -28,6 +59,7 @@ impl Default for TwoPhaseConfig {
             p_core_threads: 8,
             e_core_threads: 2,
             classification: ClassificationConfig::default(),
+            search: SearchConfig::default(),
         }
     }
 }
@@ -39,12 +71,12 @@ pub fn should_use_two_phase(legal_move_count: usize) -> Option<TwoPhaseConfig> {
         0..=10 => None, // Skip two-phase: too few moves to classify
         11..=25 => Some(TwoPhaseConfig {
             probe_depth: 2,
-            classification: ClassificationConfig { heavy_ratio: 0.6, light_threshold: 0.3 },
+            classification: ClassificationConfig { heavy_ratio: 0.6, light_threshold: 0.3, ..Default::default() },
             ..Default::default()
         }),
         _ => Some(TwoPhaseConfig {
             probe_depth: 1,
-            classification: ClassificationConfig { heavy_ratio: 0.8, light_threshold: 0.3 },
+            classification: ClassificationConfig { heavy_ratio: 0.8, light_threshold: 0.3, ..Default::default() },
             ..Default::default()
         }),
     }
@@ -61,6 +93,12 @@ pub struct TwoPhaseMetrics {
     pub light_move_count: usize,
     pub best_move: String,
     pub score: i32,
+    pub tt_stats: TTStats,
+    pub nodes: u64,
+    /// Moves P-core workers took from the light queue or over from E-core workers
+    pub migrated_to_p: usize,
+    /// Heavy moves E-core workers took from the P-core queue
+    pub migrated_to_e: usize,
 }
 
 pub fn parallel_search(board: &mut Board, depth: u32) -> (Move, i32) {
@@ -73,8 +111,15 @@ pub fn parallel_search_with_policy(board: &mut Board,depth: u32,policy: CorePoli
 }
 
 pub fn parallel_search_with_fault(board: &mut Board,depth: u32,policy: CorePolicy,threads: usize,mixed_ratio: f32,inject_panic_at: Option<usize>) -> (Move, i32) {
+    let shared = SharedSearch::new(SearchConfig::default());
+    parallel_search_shared(board, depth, policy, threads, mixed_ratio, inject_panic_at, &shared)
+}
+
+/// Root-split search where every worker shares `shared`; table stats and node counts end up there.
+/// If `shared` is stopped before the search finishes, the first ordered move is returned with a score of 0.
+pub fn parallel_search_shared(board: &mut Board,depth: u32,policy: CorePolicy,threads: usize,mixed_ratio: f32,inject_panic_at: Option<usize>,shared: &SharedSearch) -> (Move, i32) {
     let current_color = board.to_move();
-    let moves = generate_legal_moves(board, current_color);
+    let mut moves = generate_legal_moves(board, current_color);
 
     if moves.is_empty() {
         let dummy_move = Move::new(Square(0), Square(0), None, None);
@@ -86,6 +131,9 @@ pub fn parallel_search_with_fault(board: &mut Board,depth: u32,policy: CorePolic
         return (dummy_move, score);
     }
 
+    // Likely-best moves are handed to the pool first
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+
     // Reset counter
     MOVE_COUNTER.store(0, Ordering::SeqCst);
 
@@ -97,46 +145,165 @@ pub fn parallel_search_with_fault(board: &mut Board,depth: u32,policy: CorePolic
 
     let pool = create_pool_for_policy(policy, threads, mixed_ratio);
 
-    pool.install(|| {
-        let results: Vec<(Move, i32)> = moves
-            .par_iter()
-            .map(|mv| {
-                let move_num = MOVE_COUNTER.fetch_add(1, Ordering::SeqCst);
-
-                // Check if we should panic at this move
-                if let Some(panic_at) = inject_panic_at {
-                    if move_num == panic_at && SHOULD_PANIC.swap(false, Ordering::SeqCst) {
-                        // Do real work first (2-ply search)
-                        let mut temp_board = board.clone();
-                        let temp_undo = temp_board.make_move(mv);
-                        let _ = alphabeta(&mut temp_board, 2, i32::MIN + 1, i32::MAX - 1, false);
-                        temp_board.unmake_move(mv, temp_undo);
-
-                        panic!("Injected fault at move {} after real work", move_num);
-                    }
+    pool.install(|| root_split(board, &moves, depth, inject_panic_at, shared))
+        .map(|(score, pv)| (pv[0], score))
+        .unwrap_or((moves[0], 0))
+}
+
+/// Search to `depth` with `threads` workers split by `mode`; table stats and
+/// node counts end up in `shared`. Two-phase runs `threads` P-core workers
+/// and the default E-core pool.
+pub fn parallel_search_with_mode(board: &mut Board, depth: u32, mode: ParallelMode, policy: CorePolicy, threads: usize, mixed_ratio: f32, shared: &SharedSearch) -> (Move, i32) {
+    match mode {
+        ParallelMode::RootSplit => parallel_search_shared(board, depth, policy, threads, mixed_ratio, None, shared),
+        ParallelMode::LazySmp => lazy_smp_search_shared(board, depth, policy, threads, mixed_ratio, shared),
+        ParallelMode::TwoPhase => two_phase_search_shared(board, depth, &two_phase_config(threads, shared), shared),
+        ParallelMode::Ybwc => ybwc_search_shared(board, depth, policy, threads, mixed_ratio, shared),
+        ParallelMode::Jamboree => jamboree_search_shared(board, depth, policy, threads, mixed_ratio, shared),
+        ParallelMode::Abdada => abdada_search_shared(board, depth, policy, threads, mixed_ratio, shared),
+    }
+}
+
+/// Iterative deepening with `threads` workers split by `mode`, bounded by the
+/// limits attached to `shared`.
+pub fn parallel_iterative_search_with_mode(board: &mut Board, mode: ParallelMode, policy: CorePolicy, threads: usize, mixed_ratio: f32, shared: &SharedSearch) -> SearchResult {
+    match mode {
+        ParallelMode::RootSplit => parallel_iterative_search(board, policy, threads, mixed_ratio, shared),
+        ParallelMode::LazySmp => lazy_smp_iterative_search(board, policy, threads, mixed_ratio, shared),
+        ParallelMode::TwoPhase => two_phase_iterative_search(board, &two_phase_config(threads, shared), shared),
+        ParallelMode::Ybwc => ybwc_iterative_search(board, policy, threads, mixed_ratio, shared),
+        ParallelMode::Jamboree => jamboree_iterative_search(board, policy, threads, mixed_ratio, shared),
+        ParallelMode::Abdada => abdada_iterative_search(board, policy, threads, mixed_ratio, shared),
+    }
+}
+
+fn two_phase_config(threads: usize, shared: &SharedSearch) -> TwoPhaseConfig {
+    TwoPhaseConfig {
+        p_core_threads: threads,
+        search: shared.config,
+        ..TwoPhaseConfig::default()
+    }
+}
+
+/// Iterative deepening with root-split parallel passes, bounded by the limits
+/// attached to `shared`. Returns the deepest completed iteration.
+pub fn parallel_search_timed(board: &mut Board, policy: CorePolicy, threads: usize, mixed_ratio: f32, shared: &SharedSearch) -> (Move, i32) {
+    let result = parallel_iterative_search(board, policy, threads, mixed_ratio, shared);
+    (result.best_move, result.score)
+}
+
+/// `parallel_search_timed` with the PV and per-depth record of the search.
+pub fn parallel_iterative_search(board: &mut Board, policy: CorePolicy, threads: usize, mixed_ratio: f32, shared: &SharedSearch) -> SearchResult {
+    let current_color = board.to_move();
+    let mut moves = generate_legal_moves(board, current_color);
+
+    if moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return SearchResult::no_moves(score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+
+    // One pool for every iteration
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+
+    pool.install(|| iterate(moves, shared, |moves, depth| root_split(board, moves, depth, None, shared)))
+}
+
+/// Search each root move as its own task on the current pool: score and PV of
+/// the best. `None` if the search was stopped.
+fn root_split(board: &Board, moves: &[Move], depth: u32, inject_panic_at: Option<usize>, shared: &SharedSearch) -> Option<(i32, Vec<Move>)> {
+    let bound = AtomicI32::new(-INFINITY);
+    let results: Vec<Option<(i32, Vec<Move>)>> = moves
+        .par_iter()
+        .map(|mv| {
+            let move_num = MOVE_COUNTER.fetch_add(1, Ordering::SeqCst);
+
+            // Check if we should panic at this move
+            if let Some(panic_at) = inject_panic_at {
+                if move_num == panic_at && SHOULD_PANIC.swap(false, Ordering::SeqCst) {
+                    // Do real work first (2-ply search)
+                    let mut temp_board = board.clone();
+                    let temp_undo = temp_board.make_move(mv);
+                    let _ = alphabeta(&mut temp_board, 2, -INFINITY, INFINITY);
+                    temp_board.unmake_move(mv, temp_undo);
+
+                    panic!("Injected fault at move {} after real work", move_num);
                 }
+            }
 
-                // Normal evaluation
-                let mut local_board = board.clone();
-                let undo = local_board.make_move(mv);
-                let score = -alphabeta(
-                    &mut local_board,
-                    depth.saturating_sub(1),
-                    i32::MIN + 1,
-                    i32::MAX - 1,
-                    false,
-                );
-                local_board.unmake_move(mv, undo);
-
-                (*mv, score)
-            })
-            .collect();
+            search_root_move(board, *mv, depth, &bound, shared, None)
+        })
+        .collect();
 
-        results
-            .into_iter()
-            .max_by_key(|&(_, score)| score)
-            .expect("moves non-empty")
-    })
+    if shared.is_stopped() {
+        return None;
+    }
+
+    results
+        .into_iter()
+        .flatten()
+        .max_by_key(|(score, _)| *score)
+}
+
+/// Search `mv` from the root against `bound`, the best root score any worker
+/// has found so far, and raise it if `mv` beats it. The move is first proved
+/// no better with a null window; if that fails high it is searched again
+/// above the bound as it stands then. Both searches keep reading the bound
+/// between moves at ply 1, so a sibling raising it mid-search narrows the
+/// window there. Score and PV if the move beat the bound, `None` if not (its
+/// score is then only an upper bound). With `root_bound` off the bound stays
+/// where it started and there is no null window. `None` too if the search
+/// was stopped or `abort` raised.
+pub(crate) fn search_root_move(
+    board: &Board,
+    mv: Move,
+    depth: u32,
+    bound: &AtomicI32,
+    shared: &SharedSearch,
+    abort: Option<&Abort>,
+) -> Option<(i32, Vec<Move>)> {
+    let root_bound = shared.config.root_bound;
+    let mut child = *board;
+    let mut ctx = SearchContext::new(shared);
+    if let Some(abort) = abort {
+        ctx = ctx.with_abort(abort);
+    }
+    if root_bound {
+        ctx = ctx.with_root_bound(bound);
+    }
+    let undo = child.make_move(&mv);
+    let depth = depth.saturating_sub(1);
+
+    let alpha = bound.load(Ordering::Relaxed);
+    let mut score = INFINITY;
+    if root_bound && alpha > -INFINITY {
+        ctx.start_root_move(mv);
+        score = -alphabeta_with_context(&mut child, depth, -alpha - 1, -alpha, &mut ctx);
+    }
+    let alpha = bound.load(Ordering::Relaxed);
+    if score > alpha && !ctx.stopped {
+        ctx.start_root_move(mv);
+        score = -alphabeta_with_context(&mut child, depth, -INFINITY, -alpha, &mut ctx);
+    }
+    child.unmake_move(&mv, undo);
+    let pv = ctx.root_pv(mv);
+    let stopped = ctx.stopped;
+    ctx.finish();
+
+    // A window narrowed at ply 1 only proves the move no better than the
+    // bound that narrowed it
+    if stopped || score <= alpha || score <= bound.load(Ordering::Relaxed) {
+        return None;
+    }
+    if root_bound {
+        bound.fetch_max(score, Ordering::Relaxed);
+    }
+    Some((score, pv))
 }
 
 pub fn two_phase_search(board: &mut Board, depth: u32, config: &TwoPhaseConfig) -> (Move, i32) {
@@ -154,7 +321,7 @@ pub fn two_phase_search_with_metrics(
     let mut metrics = TwoPhaseMetrics::default();
     
     let current_color = board.to_move();
-    let legal_moves = generate_legal_moves(board, current_color);
+    let mut legal_moves = generate_legal_moves(board, current_color);
     
     if legal_moves.is_empty() {
         let dummy = Move::default();
@@ -167,98 +334,193 @@ pub fn two_phase_search_with_metrics(
         return (dummy, score, metrics);
     }
     
+    // Classification keeps this order within each phase
+    MoveOrderer::new(config.search.ordering).order_moves(board, &mut legal_moves, 0, None);
+
+    let p_pool = create_p_core_pool(config.p_core_threads)
+        .expect("Failed to create P-core pool");
+
+    // One table for both phases, so E-cores can reuse what P-cores stored
+    let shared = SharedSearch::new(config.search);
+
+    let (best_move, best_score) = two_phase_pass(board, &legal_moves, depth, config, &shared, &p_pool, &mut metrics)
+        .map(|(score, pv)| (pv[0], score))
+        .unwrap_or((legal_moves[0], 0));
+    
+    metrics.total_time_ms = total_start.elapsed().as_secs_f64() * 1000.0;
+    metrics.best_move = best_move.to_algebraic();
+    metrics.score = best_score;
+    metrics.tt_stats = shared.tt_stats();
+    metrics.nodes = shared.nodes();
+    
+    (best_move, best_score, metrics)
+}
+
+/// Two-phase search to `depth` with both phases sharing `shared`
+/// (`config.search` is not used).
+pub fn two_phase_search_shared(board: &mut Board, depth: u32, config: &TwoPhaseConfig, shared: &SharedSearch) -> (Move, i32) {
+    let current_color = board.to_move();
+    let mut legal_moves = generate_legal_moves(board, current_color);
+
+    if legal_moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return (Move::default(), score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut legal_moves, 0, None);
+
+    let p_pool = create_p_core_pool(config.p_core_threads)
+        .expect("Failed to create P-core pool");
+    let mut metrics = TwoPhaseMetrics::default();
+
+    two_phase_pass(board, &legal_moves, depth, config, shared, &p_pool, &mut metrics)
+        .map(|(score, pv)| (pv[0], score))
+        .unwrap_or((legal_moves[0], 0))
+}
+
+/// Iterative deepening where every iteration probes, classifies and runs both
+/// phases. Table, ordering and limits come from `shared` (`config.search` is
+/// not used). Returns the deepest completed iteration.
+pub fn two_phase_search_timed(board: &mut Board, config: &TwoPhaseConfig, shared: &SharedSearch) -> (Move, i32) {
+    let result = two_phase_iterative_search(board, config, shared);
+    (result.best_move, result.score)
+}
+
+/// `two_phase_search_timed` with the PV and per-depth record of the search.
+pub fn two_phase_iterative_search(board: &mut Board, config: &TwoPhaseConfig, shared: &SharedSearch) -> SearchResult {
+    let current_color = board.to_move();
+    let mut legal_moves = generate_legal_moves(board, current_color);
+
+    if legal_moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return SearchResult::no_moves(score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut legal_moves, 0, None);
+
+    let p_pool = create_p_core_pool(config.p_core_threads)
+        .expect("Failed to create P-core pool");
+    let mut metrics = TwoPhaseMetrics::default();
+
+    iterate(legal_moves, shared, |moves, depth| {
+        two_phase_pass(board, moves, depth, config, shared, &p_pool, &mut metrics)
+    })
+}
+
+/// Probe, classify and search one depth: score and PV of the best move. `None`
+/// if the search was stopped.
+fn two_phase_pass(
+    board: &Board,
+    legal_moves: &[Move],
+    depth: u32,
+    config: &TwoPhaseConfig,
+    shared: &SharedSearch,
+    p_pool: &ThreadPool,
+    metrics: &mut TwoPhaseMetrics,
+) -> Option<(i32, Vec<Move>)> {
     // Probe phase
     let probe_start = Instant::now();
-    let probed = probe_root_moves(board, &legal_moves, config.probe_depth);
+    let probed = probe_root_moves(board, legal_moves, config.probe_depth);
     metrics.probe_time_ms = probe_start.elapsed().as_secs_f64() * 1000.0;
     
     let (heavy_moves, light_moves) = classify_moves_with_config(probed, &config.classification);
     metrics.heavy_move_count = heavy_moves.len();
     metrics.light_move_count = light_moves.len();
     
-    let p_pool = create_p_core_pool(config.p_core_threads)
-        .expect("Failed to create P-core pool");
-    
-    // Phase 1: Heavy moves on P-cores
-    let phase1_start = Instant::now();
-    let (phase1_best_move, phase1_best_score) = if !heavy_moves.is_empty() {
-        p_pool.install(|| {
-            search_moves_parallel(board, &heavy_moves, depth, i32::MIN + 1)
-        })
-    } else {
-        (Move::default(), i32::MIN + 1)
-    };
-    metrics.phase1_time_ms = phase1_start.elapsed().as_secs_f64() * 1000.0;
-    
-    // Phase 2: Light moves on E-cores
-    let phase2_start = Instant::now();
-    let (phase2_best_move, phase2_best_score) = if !light_moves.is_empty() {
+    let (best_score, best_pv) = if config.classification.adaptive {
+        // Both pools at once, each taking the other's moves when idle
         let e_pool = create_e_core_pool(config.e_core_threads)
             .expect("Failed to create E-core pool");
-        
-        let alpha = if phase1_best_score > i32::MIN + 1 {
-            phase1_best_score
+        let adaptive = adaptive_search(board, &heavy_moves, &light_moves, depth, shared, p_pool, &e_pool);
+        metrics.phase1_time_ms = adaptive.p_core_ms;
+        metrics.phase2_time_ms = adaptive.e_core_ms;
+        metrics.migrated_to_p = adaptive.migrated_to_p;
+        metrics.migrated_to_e = adaptive.migrated_to_e;
+        adaptive.best.unwrap_or((i32::MIN + 1, Vec::new()))
+    } else {
+        // Phase 1: Heavy moves on P-cores
+        let phase1_start = Instant::now();
+        let (phase1_best_score, phase1_pv) = if !heavy_moves.is_empty() {
+            p_pool.install(|| {
+                search_moves_parallel(board, &heavy_moves, depth, i32::MIN + 1, shared)
+            })
         } else {
-            i32::MIN + 1
+            (i32::MIN + 1, Vec::new())
         };
-        
-        e_pool.install(|| {
-            search_moves_parallel(board, &light_moves, depth, alpha)
-        })
-    } else {
-        (Move::default(), i32::MIN + 1)
-    };
-    metrics.phase2_time_ms = phase2_start.elapsed().as_secs_f64() * 1000.0;
-    
-    let (best_move, best_score) = if phase2_best_score > phase1_best_score {
-        (phase2_best_move, phase2_best_score)
-    } else {
-        (phase1_best_move, phase1_best_score)
+        metrics.phase1_time_ms = phase1_start.elapsed().as_secs_f64() * 1000.0;
+
+        // Phase 2: Light moves on E-cores
+        let phase2_start = Instant::now();
+        let (phase2_best_score, phase2_pv) = if !light_moves.is_empty() {
+            let e_pool = create_e_core_pool(config.e_core_threads)
+                .expect("Failed to create E-core pool");
+
+            let alpha = if phase1_best_score > i32::MIN + 1 {
+                phase1_best_score
+            } else {
+                i32::MIN + 1
+            };
+
+            e_pool.install(|| {
+                search_moves_parallel(board, &light_moves, depth, alpha, shared)
+            })
+        } else {
+            (i32::MIN + 1, Vec::new())
+        };
+        metrics.phase2_time_ms = phase2_start.elapsed().as_secs_f64() * 1000.0;
+
+        if phase2_best_score > phase1_best_score {
+            (phase2_best_score, phase2_pv)
+        } else {
+            (phase1_best_score, phase1_pv)
+        }
     };
     
     // Fallback if no valid move found
-    let (best_move, best_score) = if best_move.from.0 == 0 && best_move.to.0 == 0 {
+    let (best_score, best_pv) = if best_pv.is_empty() {
         let mut fallback_board = board.clone();
+        let mut ctx = SearchContext::new(shared);
+        ctx.start_root_move(legal_moves[0]);
         let undo = fallback_board.make_move(&legal_moves[0]);
-        let score = -alphabeta(&mut fallback_board, depth.saturating_sub(1), i32::MIN + 1, i32::MAX - 1, false);
+        let score = -alphabeta_with_context(&mut fallback_board, depth.saturating_sub(1), -INFINITY, INFINITY, &mut ctx);
         fallback_board.unmake_move(&legal_moves[0], undo);
-        (legal_moves[0], score)
+        let pv = ctx.root_pv(legal_moves[0]);
+        ctx.finish();
+        (score, pv)
     } else {
-        (best_move, best_score)
+        (best_score, best_pv)
     };
-    
-    metrics.total_time_ms = total_start.elapsed().as_secs_f64() * 1000.0;
-    metrics.best_move = best_move.to_algebraic();
-    metrics.score = best_score;
-    
-    (best_move, best_score, metrics)
+
+    if shared.is_stopped() {
+        return None;
+    }
+    Some((best_score, best_pv))
 }
 
-/// Search a set of classified moves in parallel, returning best move and score
-fn search_moves_parallel(board: &Board, moves: &[ClassifiedMove], depth: u32, alpha: i32) -> (Move, i32) {
+/// Search a set of classified moves in parallel against a bound starting at
+/// `alpha`, returning the best score and its PV (empty if no move beat it)
+fn search_moves_parallel(board: &Board, moves: &[ClassifiedMove], depth: u32, alpha: i32, shared: &SharedSearch) -> (i32, Vec<Move>) {
     if moves.is_empty() {
-        return (Move::default(), i32::MIN + 1);
+        return (i32::MIN + 1, Vec::new());
     }
-    
-    let results: Vec<(Move, i32)> = moves
+
+    let bound = AtomicI32::new(alpha.max(-INFINITY));
+    let results: Vec<Option<(i32, Vec<Move>)>> = moves
         .par_iter()
-        .map(|cm| {
-            let mut new_board = board.clone();
-            let undo = new_board.make_move(&cm.mv);
-            let score = -alphabeta(
-                &mut new_board,
-                depth.saturating_sub(1),
-                -i32::MAX + 1,
-                -alpha,
-                false,
-            );
-            new_board.unmake_move(&cm.mv, undo);
-            (cm.mv, score)
-        })
+        .map(|cm| search_root_move(board, cm.mv, depth, &bound, shared, None))
         .collect();
-    
+
     results
         .into_iter()
-        .max_by_key(|(_, score)| *score)
-        .unwrap_or((Move::default(), i32::MIN + 1))
+        .flatten()
+        .max_by_key(|(score, _)| *score)
+        .unwrap_or((i32::MIN + 1, Vec::new()))
 }
\ No newline at end of file
@@ -9,6 +9,9 @@ pub struct ClassificationConfig {
     pub heavy_ratio: f32,
     /// If light moves exceed this % of total nodes, move all to P-cores (default 0.3)
     pub light_threshold: f32,
+    /// Treat the split as a starting point only: idle workers take moves
+    /// meant for the other pool while the search runs (default true)
+    pub adaptive: bool,
 }
 
 impl Default for ClassificationConfig {
@@ -16,6 +19,7 @@ impl Default for ClassificationConfig {
         Self {
             heavy_ratio: 0.6,
             light_threshold: 0.3,
+            adaptive: true,
         }
     }
 }
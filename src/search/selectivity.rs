@@ -0,0 +1,107 @@
+//! Selective search: null-move pruning, late-move reductions, reverse
+//! futility and futility pruning, and check extensions. All of it trades
+//! exactness for depth, so unlike the ordering and quiescence knobs it is
+//! off by default: a full-width search gives the same score whatever the
+//! move order or number of threads, which the parallel searches are checked
+//! against.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::types::*;
+use once_cell::sync::Lazy;
+
+/// Depth from which a null move is tried
+pub const NULL_MOVE_MIN_DEPTH: u32 = 3;
+/// Remaining depth up to which reverse futility pruning applies
+pub const REVERSE_FUTILITY_DEPTH: u32 = 3;
+/// Remaining depth up to which quiet moves are futility pruned
+pub const FUTILITY_DEPTH: u32 = 2;
+/// Depth and move number from which late moves are reduced
+pub const LMR_MIN_DEPTH: u32 = 3;
+pub const LMR_MIN_MOVE: usize = 3;
+
+// Static eval this far above beta per ply of depth is taken as a fail-high
+const REVERSE_FUTILITY_MARGIN: i32 = 120;
+// How far below alpha a quiet move may start and still be searched, by depth
+const FUTILITY_MARGIN: [i32; FUTILITY_DEPTH as usize + 1] = [0, 200, 450];
+const LMR_TABLE_SIZE: usize = 64;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectivityConfig {
+    /// Let the opponent move twice; if they still can't reach beta, cut
+    pub null_move: bool,
+    /// Search quiet moves late in the ordering shallower, re-searching any
+    /// that beat alpha
+    pub lmr: bool,
+    /// Cut near the leaves when the static evaluation is far above beta
+    pub reverse_futility: bool,
+    /// Skip quiet moves near the leaves when the static evaluation is too
+    /// far below alpha for them to matter
+    pub futility: bool,
+    /// Search one ply deeper when in check
+    pub check_extensions: bool,
+}
+
+impl SelectivityConfig {
+    /// Every technique on.
+    pub fn all() -> Self {
+        Self {
+            null_move: true,
+            lmr: true,
+            reverse_futility: true,
+            futility: true,
+            check_extensions: true,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.null_move || self.lmr || self.reverse_futility || self.futility || self.check_extensions
+    }
+}
+
+/// Depth of the null-move search below `depth`: 2, or 3 once deep enough
+/// that the saving is worth the risk.
+pub fn null_move_reduction(depth: u32) -> u32 {
+    if depth > 6 {
+        3
+    } else {
+        2
+    }
+}
+
+/// Margin over beta for reverse futility pruning at `depth`.
+pub fn reverse_futility_margin(depth: u32) -> i32 {
+    REVERSE_FUTILITY_MARGIN * depth as i32
+}
+
+/// Margin under alpha for futility pruning at `depth` (at most `FUTILITY_DEPTH`).
+pub fn futility_margin(depth: u32) -> i32 {
+    FUTILITY_MARGIN[depth.min(FUTILITY_DEPTH) as usize]
+}
+
+// Grows with the log of both the depth and the move number
+static LMR_TABLE: Lazy<[[u8; LMR_TABLE_SIZE]; LMR_TABLE_SIZE]> = Lazy::new(|| {
+    let mut table = [[0; LMR_TABLE_SIZE]; LMR_TABLE_SIZE];
+    for (depth, row) in table.iter_mut().enumerate().skip(1) {
+        for (move_number, reduction) in row.iter_mut().enumerate().skip(1) {
+            *reduction = (0.75 + (depth as f64).ln() * (move_number as f64).ln() / 2.25) as u8;
+        }
+    }
+    table
+});
+
+/// Plies to take off the search of the `move_number`th move (from 0) at
+/// `depth`, always leaving at least one.
+pub fn lmr_reduction(depth: u32, move_number: usize) -> u32 {
+    let reduction = LMR_TABLE[(depth as usize).min(LMR_TABLE_SIZE - 1)][move_number.min(LMR_TABLE_SIZE - 1)] as u32;
+    reduction.min(depth.saturating_sub(2))
+}
+
+/// Whether `color` has a piece other than pawns and king. Without one,
+/// zugzwang is common enough that passing is no lower bound.
+pub fn has_non_pawn_material(board: &Board, color: Color) -> bool {
+    (0..64).any(|square| {
+        board
+            .get_piece(Square(square))
+            .is_some_and(|p| p.color == color && !matches!(p.piece_type, PieceType::Pawn | PieceType::King))
+    })
+}
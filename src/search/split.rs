@@ -0,0 +1,213 @@
+//! Pieces shared by the searches that work on the tree below the root
+//! together: YBWC and Jamboree, which hand the brothers of a node to the pool
+//! as tasks, and ABDADA, whose workers steer around each other inside it.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::moves::generate_legal_moves;
+use crate::scheduling::{create_pool_for_policy, CorePolicy};
+use crate::search::iterative::{iterate, SearchResult};
+use crate::search::minimax::{alphabeta_with_context, mated_in, SearchContext, SharedSearch, INFINITY, MATE_SCORE};
+use crate::search::ordering::MoveOrderer;
+use crate::types::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Remaining depth from which a node hands its brothers to the pool. Every
+/// task costs a search context of its own, which shallower subtrees don't
+/// repay.
+pub const MIN_SPLIT_DEPTH: u32 = 4;
+
+/// Searches the ordered `moves` of the node at `ctx.ply` with the window
+/// `alpha..beta`, given the hinted move. Returns the best score and move and
+/// leaves the best line in `ctx`; garbage once `ctx.stopped`.
+pub(crate) type SplitFn = fn(&mut Board, &[Move], u32, i32, i32, Option<Move>, &mut SearchContext) -> (i32, Option<Move>);
+
+/// Abort signal of a split point, raised when one of its brothers fails
+/// high. Tasks under nested split points see it through the chain.
+pub(crate) struct Abort<'a> {
+    raised: AtomicBool,
+    parent: Option<&'a Abort<'a>>,
+}
+
+impl<'a> Abort<'a> {
+    pub(crate) fn new(parent: Option<&'a Abort<'a>>) -> Self {
+        Abort {
+            raised: AtomicBool::new(false),
+            parent,
+        }
+    }
+
+    pub(crate) fn raise(&self) {
+        self.raised.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether this split point or one above it was aborted.
+    pub(crate) fn is_raised(&self) -> bool {
+        self.raised.load(Ordering::Relaxed) || self.parent.is_some_and(|p| p.is_raised())
+    }
+}
+
+/// How the start of a node went.
+pub(crate) enum Node {
+    /// Settled by the table, mate or stalemate (or garbage once stopped)
+    Done(i32),
+    /// Still to search: its hash, its ordered moves and the hinted move
+    Open {
+        hash: u64,
+        moves: Vec<Move>,
+        pv_move: Option<Move>,
+    },
+}
+
+/// Count the node and settle it from the table or its lack of moves if
+/// possible, as the serial search does; otherwise order its moves.
+pub(crate) fn open_node(board: &mut Board, depth: u32, alpha: i32, beta: i32, ctx: &mut SearchContext) -> Node {
+    ctx.nodes += 1;
+    ctx.clear_pv();
+    if ctx.poll_stop() {
+        return Node::Done(0); // discarded by the caller
+    }
+
+    let hash = board.hash();
+    let (cutoff, tt_move) = ctx.probe_node(hash, depth, alpha, beta);
+    if let Some(score) = cutoff {
+        return Node::Done(score);
+    }
+
+    let current_color = board.to_move();
+    let mut moves = generate_legal_moves(board, current_color);
+    if moves.is_empty() {
+        return Node::Done(if board.is_in_check(current_color) {
+            mated_in(ctx.ply)
+        } else {
+            0
+        });
+    }
+
+    let pv_move = ctx.pv_move();
+    ctx.orderer.order_moves(board, &mut moves, ctx.ply, pv_move.or(tt_move));
+    Node::Open { hash, moves, pv_move }
+}
+
+/// Context and board for a task searching `mv` from `node` at `ply`. The
+/// task stops with `abort` and starts from the node's ordering tables.
+pub(crate) fn start_task<'a>(
+    shared: &'a SharedSearch,
+    abort: &'a Abort<'a>,
+    orderer: &MoveOrderer,
+    node: &Board,
+    mv: Move,
+    ply: usize,
+) -> (SearchContext<'a>, Board) {
+    let mut task = SearchContext::new(shared).with_abort(abort);
+    task.orderer = orderer.clone();
+    task.ply = ply + 1;
+    let mut child = *node;
+    child.make_move(&mv);
+    (task, child)
+}
+
+/// Once the tasks of a split point have joined: whether the node is garbage
+/// because the search was stopped or a split point above it aborted. The
+/// split point's own abort only means a brother failed high. Marks `ctx`
+/// stopped if so.
+pub(crate) fn split_stopped(ctx: &mut SearchContext, shared: &SharedSearch) -> bool {
+    if shared.is_stopped() || ctx.abort().is_some_and(|a| a.is_raised()) {
+        ctx.stopped = true;
+    }
+    ctx.stopped
+}
+
+/// Search to `depth` with `split` at every node from `MIN_SPLIT_DEPTH` up,
+/// on a pool of `threads`. If `shared` is stopped before the search
+/// finishes, the first ordered move is returned with a score of 0.
+pub(crate) fn split_search_shared(
+    board: &mut Board,
+    depth: u32,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+    split: SplitFn,
+) -> (Move, i32) {
+    let current_color = board.to_move();
+    let mut moves = generate_legal_moves(board, current_color);
+
+    if moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return (Move::default(), score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+
+    pool.install(|| split_root(board, &moves, depth, shared, split))
+        .map(|(score, pv)| (pv[0], score))
+        .unwrap_or((moves[0], 0))
+}
+
+/// Iterative deepening with `split` passes, bounded by the limits attached
+/// to `shared`. Returns the deepest completed iteration.
+pub(crate) fn split_iterative_search(
+    board: &mut Board,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+    split: SplitFn,
+) -> SearchResult {
+    let current_color = board.to_move();
+    let mut moves = generate_legal_moves(board, current_color);
+
+    if moves.is_empty() {
+        let score = if board.is_in_check(current_color) {
+            -MATE_SCORE
+        } else {
+            0
+        };
+        return SearchResult::no_moves(score);
+    }
+
+    MoveOrderer::new(shared.config.ordering).order_moves(board, &mut moves, 0, None);
+    let pool = create_pool_for_policy(policy, threads, mixed_ratio);
+
+    pool.install(|| iterate(moves, shared, |moves, depth| split_root(board, moves, depth, shared, split)))
+}
+
+// One pass over the ordered root moves: score and PV of the best, `None` if
+// the search was stopped
+fn split_root(board: &Board, moves: &[Move], depth: u32, shared: &SharedSearch, split: SplitFn) -> Option<(i32, Vec<Move>)> {
+    let mut board = *board;
+    let mut ctx = SearchContext::new(shared);
+    // The root is on the hinted line by definition
+    ctx.follow_pv = true;
+    let pv_move = ctx.pv_move();
+
+    let (score, _) = split(&mut board, moves, depth, -INFINITY, INFINITY, pv_move, &mut ctx);
+    let result = (!ctx.stopped).then(|| (score, ctx.pv().to_vec()));
+    ctx.finish();
+    result
+}
+
+/// Search a node with `split` if it is deep enough, with the serial search
+/// otherwise. Without a shared search there is no one to split with.
+pub(crate) fn split_node(board: &mut Board, depth: u32, alpha: i32, beta: i32, ctx: &mut SearchContext, split: SplitFn) -> i32 {
+    if depth < MIN_SPLIT_DEPTH || ctx.shared().is_none() {
+        return alphabeta_with_context(board, depth, alpha, beta, ctx);
+    }
+
+    let (hash, moves, pv_move) = match open_node(board, depth, alpha, beta, ctx) {
+        Node::Done(score) => return score,
+        Node::Open { hash, moves, pv_move } => (hash, moves, pv_move),
+    };
+
+    let (best_score, best_move) = split(board, &moves, depth, alpha, beta, pv_move, ctx);
+    if ctx.stopped {
+        return 0;
+    }
+    ctx.store_node(hash, depth, alpha, beta, best_score, best_move);
+    best_score
+}
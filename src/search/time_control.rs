@@ -1 +1,134 @@
+//! Clock handling for timed searches. `TimeManager` turns the UCI-style limits
+//! in `TimeControl` into two deadlines:
+//! - soft: past this, don't start another iteration
+//! - hard: past this, abort the iteration in progress
+//!
+//! Searchers poll the hard deadline through `SharedSearch::should_stop`, so a
+//! timed search always returns the last iteration that finished.
 
+use crate::types::Color;
+use std::time::{Duration, Instant};
+
+/// Deepest iteration a timed search will start
+pub const MAX_SEARCH_DEPTH: u32 = 64;
+
+// Reserved for move transmission and GUI lag
+const MOVE_OVERHEAD_MS: u64 = 30;
+// Assumed moves left when the GUI doesn't send movestogo
+const DEFAULT_MOVES_TO_GO: u64 = 30;
+
+/// Search limits, as sent with a UCI `go`; times in milliseconds. All `None`
+/// searches until stopped.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeControl {
+    pub wtime: Option<u64>,
+    pub btime: Option<u64>,
+    pub winc: Option<u64>,
+    pub binc: Option<u64>,
+    pub movestogo: Option<u32>,
+    pub movetime: Option<u64>,
+    pub depth: Option<u32>,
+    /// Stop after about this many nodes
+    pub nodes: Option<u64>,
+}
+
+impl TimeControl {
+    pub fn movetime(ms: u64) -> Self {
+        TimeControl {
+            movetime: Some(ms),
+            ..Default::default()
+        }
+    }
+
+    pub fn depth(depth: u32) -> Self {
+        TimeControl {
+            depth: Some(depth),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TimeManager {
+    start: Instant,
+    soft: Option<Duration>,
+    hard: Option<Duration>,
+    max_depth: u32,
+    max_nodes: Option<u64>,
+}
+
+impl TimeManager {
+    /// Budget the clock of `side`. The clock starts now.
+    pub fn new(control: &TimeControl, side: Color) -> Self {
+        let (time_left, increment) = match side {
+            Color::White => (control.wtime, control.winc.unwrap_or(0)),
+            Color::Black => (control.btime, control.binc.unwrap_or(0)),
+        };
+
+        let (soft, hard) = if let Some(movetime) = control.movetime {
+            let budget = movetime.saturating_sub(MOVE_OVERHEAD_MS).max(1);
+            (Some(budget), Some(budget))
+        } else if let Some(time_left) = time_left {
+            let usable = time_left.saturating_sub(MOVE_OVERHEAD_MS).max(1);
+            let moves_to_go = control
+                .movestogo
+                .map_or(DEFAULT_MOVES_TO_GO, |m| m.max(1) as u64);
+
+            let soft = (usable / moves_to_go + increment * 3 / 4).min(usable);
+            // Let one iteration overrun the soft target, never past half the clock
+            let hard = (soft * 4).min(usable / 2).max(soft);
+            (Some(soft), Some(hard))
+        } else {
+            (None, None)
+        };
+
+        TimeManager {
+            start: Instant::now(),
+            soft: soft.map(Duration::from_millis),
+            hard: hard.map(Duration::from_millis),
+            max_depth: control.depth.unwrap_or(MAX_SEARCH_DEPTH).clamp(1, MAX_SEARCH_DEPTH),
+            max_nodes: control.nodes,
+        }
+    }
+
+    /// No clock, only a depth limit.
+    pub fn fixed_depth(depth: u32) -> Self {
+        Self::new(&TimeControl::depth(depth), Color::White)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    pub fn soft_limit(&self) -> Option<Duration> {
+        self.soft
+    }
+
+    pub fn hard_limit(&self) -> Option<Duration> {
+        self.hard
+    }
+
+    pub fn hard_deadline(&self) -> Option<Instant> {
+        self.hard.map(|hard| self.start + hard)
+    }
+
+    pub fn max_depth(&self) -> u32 {
+        self.max_depth
+    }
+
+    pub fn max_nodes(&self) -> Option<u64> {
+        self.max_nodes
+    }
+
+    pub fn node_limit_reached(&self, nodes: u64) -> bool {
+        self.max_nodes.is_some_and(|max| nodes >= max)
+    }
+
+    pub fn soft_limit_reached(&self) -> bool {
+        self.soft.is_some_and(|soft| self.elapsed() >= soft)
+    }
+
+    pub fn hard_limit_reached(&self) -> bool {
+        self.hard.is_some_and(|hard| self.elapsed() >= hard)
+    }
+}
@@ -1 +1,286 @@
+//! Fixed-size transposition table shared by all search threads.
+//!
+//! Each slot holds two atomics: the packed entry and `hash ^ entry`. A reader
+//! accepts a slot only if `key ^ data` reproduces its hash, so a torn write from
+//! a racing thread reads as a miss instead of a corrupt entry (Hyatt's
+//! lockless hashing). No locks are taken on probe or store.
+//!
+//! Probe/store counts are kept per searcher in `TTStats` and folded into the
+//! table with `record` once a worker finishes, so statistics never add a
+//! shared cache line to the hot path.
 
+use crate::types::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const SLOT_BYTES: usize = 16;
+
+/// Table size used when the caller doesn't pick one
+pub const DEFAULT_TT_MB: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Exact,
+    Lower, // fail-high: true score >= stored score
+    Upper, // fail-low: true score <= stored score
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TTEntry {
+    pub depth: u8,
+    pub bound: Bound,
+    pub score: i32,
+    pub best_move: Option<Move>,
+}
+
+/// Probe/store counters, accumulated since the last `reset_stats`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TTStats {
+    pub probes: u64,
+    pub hits: u64,
+    pub misses: u64,
+    /// Probes that found the slot occupied by a different position
+    pub collisions: u64,
+    pub stores: u64,
+}
+
+impl TTStats {
+    pub fn hit_rate(&self) -> f64 {
+        if self.probes > 0 {
+            self.hits as f64 / self.probes as f64
+        } else {
+            0.0
+        }
+    }
+
+    pub fn add(&mut self, other: &TTStats) {
+        self.probes += other.probes;
+        self.hits += other.hits;
+        self.misses += other.misses;
+        self.collisions += other.collisions;
+        self.stores += other.stores;
+    }
+}
+
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+/// Outcome of a table lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Probe {
+    Hit(TTEntry),
+    Miss,
+    /// Slot holds a different position (or a torn write)
+    Collision,
+}
+
+pub struct TranspositionTable {
+    slots: Vec<Slot>,
+    // Totals flushed by searchers via `record`; probes don't touch these
+    probes: AtomicU64,
+    hits: AtomicU64,
+    collisions: AtomicU64,
+    stores: AtomicU64,
+}
+
+impl TranspositionTable {
+    /// Allocate a table of roughly `size_mb` megabytes (at least one slot).
+    pub fn new(size_mb: usize) -> Self {
+        let count = (size_mb * 1024 * 1024 / SLOT_BYTES).max(1);
+        let slots = (0..count)
+            .map(|_| Slot {
+                key: AtomicU64::new(0),
+                data: AtomicU64::new(0),
+            })
+            .collect();
+
+        TranspositionTable {
+            slots,
+            probes: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            collisions: AtomicU64::new(0),
+            stores: AtomicU64::new(0),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn size_mb(&self) -> usize {
+        self.slots.len() * SLOT_BYTES / (1024 * 1024)
+    }
+
+    fn slot(&self, hash: u64) -> &Slot {
+        // Multiply-shift maps the hash onto any table length without a power-of-two mask
+        let index = ((hash as u128 * self.slots.len() as u128) >> 64) as usize;
+        &self.slots[index]
+    }
+
+    pub fn probe(&self, hash: u64) -> Probe {
+        let slot = self.slot(hash);
+        let data = slot.data.load(Ordering::Relaxed);
+        let key = slot.key.load(Ordering::Relaxed);
+
+        if data == 0 {
+            return Probe::Miss;
+        }
+        if key ^ data != hash {
+            return Probe::Collision;
+        }
+        match unpack(data) {
+            Some(entry) => Probe::Hit(entry),
+            None => Probe::Collision,
+        }
+    }
+
+    pub fn store(&self, hash: u64, depth: u32, bound: Bound, score: i32, best_move: Option<Move>) {
+        let slot = self.slot(hash);
+        let old_data = slot.data.load(Ordering::Relaxed);
+        let old_key = slot.key.load(Ordering::Relaxed);
+
+        // Same position: keep a deeper result unless the new one is exact
+        let mut best_move = best_move;
+        if old_data != 0 && old_key ^ old_data == hash {
+            if let Some(old) = unpack(old_data) {
+                if (old.depth as u32) > depth && bound != Bound::Exact {
+                    return;
+                }
+                // Don't forget the best move when a bound-only result replaces it
+                if best_move.is_none() {
+                    best_move = old.best_move;
+                }
+            }
+        }
+
+        let data = pack(depth, bound, score, best_move);
+        slot.key.store(hash ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    /// Empty every slot and zero the stats. Not meant to race with searches.
+    pub fn clear(&self) {
+        for slot in &self.slots {
+            slot.key.store(0, Ordering::Relaxed);
+            slot.data.store(0, Ordering::Relaxed);
+        }
+        self.reset_stats();
+    }
+
+    /// Fold a searcher's local counters into the table totals.
+    pub fn record(&self, stats: &TTStats) {
+        self.probes.fetch_add(stats.probes, Ordering::Relaxed);
+        self.hits.fetch_add(stats.hits, Ordering::Relaxed);
+        self.collisions.fetch_add(stats.collisions, Ordering::Relaxed);
+        self.stores.fetch_add(stats.stores, Ordering::Relaxed);
+    }
+
+    pub fn stats(&self) -> TTStats {
+        let probes = self.probes.load(Ordering::Relaxed);
+        let hits = self.hits.load(Ordering::Relaxed);
+        TTStats {
+            probes,
+            hits,
+            misses: probes.saturating_sub(hits),
+            collisions: self.collisions.load(Ordering::Relaxed),
+            stores: self.stores.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn reset_stats(&self) {
+        self.probes.store(0, Ordering::Relaxed);
+        self.hits.store(0, Ordering::Relaxed);
+        self.collisions.store(0, Ordering::Relaxed);
+        self.stores.store(0, Ordering::Relaxed);
+    }
+
+    /// Occupied slots per thousand, sampled from the first 1000 slots.
+    pub fn hashfull(&self) -> usize {
+        let sample = self.slots.len().min(1000);
+        let used = self.slots[..sample]
+            .iter()
+            .filter(|s| s.data.load(Ordering::Relaxed) != 0)
+            .count();
+        used * 1000 / sample
+    }
+}
+
+// Entry layout (data == 0 marks an empty slot, so bound codes start at 1):
+//   bits  0..17  move (from 6, to 6, promotion 3, special 2), 0 = none
+//   bits 17..25  depth
+//   bits 25..27  bound
+//   bits 32..64  score
+fn pack(depth: u32, bound: Bound, score: i32, best_move: Option<Move>) -> u64 {
+    let bound_bits: u64 = match bound {
+        Bound::Exact => 1,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+    encode_move(best_move)
+        | ((depth.min(255) as u64) << 17)
+        | (bound_bits << 25)
+        | ((score as u32 as u64) << 32)
+}
+
+fn unpack(data: u64) -> Option<TTEntry> {
+    let bound = match (data >> 25) & 0b11 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        3 => Bound::Upper,
+        _ => return None,
+    };
+    Some(TTEntry {
+        depth: ((data >> 17) & 0xFF) as u8,
+        bound,
+        score: (data >> 32) as u32 as i32,
+        best_move: decode_move(data & 0x1FFFF),
+    })
+}
+
+fn encode_move(mv: Option<Move>) -> u64 {
+    let mv = match mv {
+        Some(mv) => mv,
+        None => return 0,
+    };
+    let promotion: u64 = match mv.promotion {
+        None => 0,
+        Some(PieceType::Knight) => 1,
+        Some(PieceType::Bishop) => 2,
+        Some(PieceType::Rook) => 3,
+        Some(PieceType::Queen) => 4,
+        Some(_) => 0,
+    };
+    let special: u64 = match mv.special_move {
+        None => 0,
+        Some(SpecialMove::Castle) => 1,
+        Some(SpecialMove::Promotion) => 2,
+        Some(SpecialMove::EnPassant) => 3,
+    };
+    (mv.from.0 as u64) | ((mv.to.0 as u64) << 6) | (promotion << 12) | (special << 15)
+}
+
+fn decode_move(bits: u64) -> Option<Move> {
+    if bits == 0 {
+        return None; // a1a1 is never a real move
+    }
+    let promotion = match (bits >> 12) & 0b111 {
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        _ => None,
+    };
+    let special = match (bits >> 15) & 0b11 {
+        1 => Some(SpecialMove::Castle),
+        2 => Some(SpecialMove::Promotion),
+        3 => Some(SpecialMove::EnPassant),
+        _ => None,
+    };
+    Some(Move::new(
+        Square((bits & 0x3F) as u8),
+        Square(((bits >> 6) & 0x3F) as u8),
+        special,
+        promotion,
+    ))
+}
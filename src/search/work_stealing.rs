@@ -0,0 +1,319 @@
+//! Runtime reclassification for the two-phase search. The probe's heavy/light
+//! split only decides where each root move starts: P-core workers take heavy
+//! moves and E-core workers light ones, each from a queue of their own. A
+//! worker whose queue runs dry takes moves from the other queue, and an idle
+//! P-core worker also takes over the move an E-core worker has been on the
+//! longest, once that is longer than P-core workers need for a whole move.
+//! The E-core search is aborted; what it stored in the table makes the
+//! restart cheap. A wrong split then costs a few moves searched on the wrong
+//! cores instead of a phase spent waiting on them.
+
+use crate::board::Board;
+use crate::search::minimax::{SharedSearch, INFINITY};
+use crate::search::parallel::search_root_move;
+use crate::search::split::Abort;
+use crate::types::{ClassifiedMove, Move};
+use rayon::ThreadPool;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How often a worker with nothing to take looks again
+const POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// How an adaptive pass went.
+pub(crate) struct Adaptive {
+    /// Score and PV of the best move; `None` if no move was searched
+    pub(crate) best: Option<(i32, Vec<Move>)>,
+    /// Time until the P-core workers ran out of moves
+    pub(crate) p_core_ms: f64,
+    /// Time until the E-core workers ran out of moves
+    pub(crate) e_core_ms: f64,
+    /// Light moves P-core workers took from the queue, plus moves they took
+    /// over from E-core workers
+    pub(crate) migrated_to_p: usize,
+    /// Heavy moves E-core workers took from the queue
+    pub(crate) migrated_to_e: usize,
+}
+
+// A move being searched, by which kind of worker and since when
+struct Running {
+    index: usize,
+    on_e_core: bool,
+    since: Instant,
+}
+
+enum Next {
+    Search(usize),
+    Wait,
+    Done,
+}
+
+#[derive(Default)]
+struct State {
+    heavy: VecDeque<usize>,
+    light: VecDeque<usize>,
+    running: Vec<Running>,
+    // Score and PV of every move that beat the bound
+    results: Vec<Option<(i32, Vec<Move>)>>,
+    // Time P-core workers spent on the moves they finished, and how many
+    p_core_time: Duration,
+    p_core_moves: u32,
+    migrated_to_p: usize,
+    migrated_to_e: usize,
+}
+
+struct Scheduler<'a> {
+    board: &'a Board,
+    moves: Vec<Move>,
+    depth: u32,
+    shared: &'a SharedSearch,
+    bound: AtomicI32,
+    // One per move, raised when a P-core worker takes it over
+    aborts: Vec<Abort<'static>>,
+    state: Mutex<State>,
+}
+
+/// Search `heavy` and `light` on the P- and E-core pools at once, moving work
+/// between them as they go. Every move is searched against the best root
+/// score so far, as in root-split search.
+pub(crate) fn adaptive_search(
+    board: &Board,
+    heavy: &[ClassifiedMove],
+    light: &[ClassifiedMove],
+    depth: u32,
+    shared: &SharedSearch,
+    p_pool: &ThreadPool,
+    e_pool: &ThreadPool,
+) -> Adaptive {
+    let moves: Vec<Move> = heavy.iter().chain(light).map(|cm| cm.mv).collect();
+    let scheduler = Scheduler {
+        board,
+        depth,
+        shared,
+        bound: AtomicI32::new(-INFINITY),
+        aborts: moves.iter().map(|_| Abort::new(None)).collect(),
+        state: Mutex::new(State {
+            heavy: (0..heavy.len()).collect(),
+            light: (heavy.len()..moves.len()).collect(),
+            results: vec![None; moves.len()],
+            ..State::default()
+        }),
+        moves,
+    };
+
+    let start = Instant::now();
+    let elapsed_ms = || start.elapsed().as_secs_f64() * 1000.0;
+    let (p_core_ms, e_core_ms) = thread::scope(|s| {
+        let e_cores = s.spawn(|| {
+            e_pool.broadcast(|_| scheduler.work(true));
+            elapsed_ms()
+        });
+        p_pool.broadcast(|_| scheduler.work(false));
+        let p_core_ms = elapsed_ms();
+        (p_core_ms, e_cores.join().expect("E-core workers panicked"))
+    });
+
+    let state = scheduler.state.into_inner().unwrap();
+    Adaptive {
+        best: state.results.into_iter().flatten().max_by_key(|(score, _)| *score),
+        p_core_ms,
+        e_core_ms,
+        migrated_to_p: state.migrated_to_p,
+        migrated_to_e: state.migrated_to_e,
+    }
+}
+
+impl Scheduler<'_> {
+    fn work(&self, on_e_core: bool) {
+        loop {
+            match self.next(on_e_core) {
+                Next::Done => return,
+                Next::Wait => thread::sleep(POLL_INTERVAL),
+                Next::Search(index) => {
+                    let started = Instant::now();
+                    // P-core workers are never taken over, so only E-core searches can abort
+                    let abort = on_e_core.then(|| &self.aborts[index]);
+                    let result = search_root_move(self.board, self.moves[index], self.depth, &self.bound, self.shared, abort);
+                    self.finish(index, on_e_core, started, result);
+                }
+            }
+        }
+    }
+
+    fn next(&self, on_e_core: bool) -> Next {
+        if self.shared.is_stopped() {
+            return Next::Done;
+        }
+        let mut state = self.state.lock().unwrap();
+
+        let index = if on_e_core {
+            // Light moves wait for a heavy one to set the bound, as they
+            // would for phase 1. Without the root bound it is never set
+            let heavy_pending = !state.heavy.is_empty() || state.running.iter().any(|r| !r.on_e_core);
+            if self.shared.config.root_bound && heavy_pending && self.bound.load(Ordering::Relaxed) == -INFINITY {
+                return Next::Wait;
+            }
+            if let Some(index) = state.light.pop_front() {
+                index
+            } else if let Some(index) = state.heavy.pop_back() {
+                // The heavy move P-core workers would reach last
+                state.migrated_to_e += 1;
+                index
+            } else {
+                return Next::Done;
+            }
+        } else if let Some(index) = state.heavy.pop_front() {
+            index
+        } else if let Some(index) = state.light.pop_front() {
+            state.migrated_to_p += 1;
+            index
+        } else {
+            return self.take_over(&mut state);
+        };
+        state.running.push(Running {
+            index,
+            on_e_core,
+            since: Instant::now(),
+        });
+        Next::Search(index)
+    }
+
+    // An idle P-core worker's pick among the moves E-core workers are on: the
+    // one they have been on longest, once that is longer than P-core workers
+    // take for a move. Until one of theirs has finished there is nothing to
+    // compare with, so nothing is taken over
+    fn take_over(&self, state: &mut State) -> Next {
+        let typical = state.p_core_time.checked_div(state.p_core_moves);
+        let Some(oldest) = state.running.iter_mut().filter(|r| r.on_e_core).min_by_key(|r| r.since) else {
+            return Next::Done;
+        };
+        if typical.is_none_or(|typical| oldest.since.elapsed() < typical) {
+            return Next::Wait;
+        }
+
+        self.aborts[oldest.index].raise();
+        oldest.on_e_core = false;
+        oldest.since = Instant::now();
+        let index = oldest.index;
+        state.migrated_to_p += 1;
+        Next::Search(index)
+    }
+
+    fn finish(&self, index: usize, on_e_core: bool, started: Instant, result: Option<(i32, Vec<Move>)>) {
+        let mut state = self.state.lock().unwrap();
+        // Aborted searches return nothing, so a result stands whoever owns the
+        // move by now. An E-core search can finish just as a P-core worker
+        // takes the move over, which then fails low against the bound it set
+        if result.is_some() {
+            state.results[index] = result;
+        }
+        // Gone if a P-core worker took the move over
+        let Some(pos) = state.running.iter().position(|r| r.index == index && r.on_e_core == on_e_core) else {
+            return;
+        };
+        state.running.swap_remove(pos);
+        if !on_e_core {
+            state.p_core_time += started.elapsed();
+            state.p_core_moves += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SearchConfig;
+
+    fn scheduler<'a>(board: &'a Board, shared: &'a SharedSearch, state: State) -> Scheduler<'a> {
+        Scheduler {
+            board,
+            moves: Vec::new(),
+            depth: 1,
+            shared,
+            bound: AtomicI32::new(-INFINITY),
+            aborts: vec![Abort::new(None)],
+            state: Mutex::new(state),
+        }
+    }
+
+    #[test]
+    fn test_no_take_over_before_a_p_core_move_finishes() {
+        let board = Board::new();
+        let shared = SharedSearch::new(SearchConfig::default());
+        let scheduler = scheduler(
+            &board,
+            &shared,
+            State {
+                running: vec![Running {
+                    index: 0,
+                    on_e_core: true,
+                    since: Instant::now() - Duration::from_secs(1),
+                }],
+                results: vec![None],
+                ..State::default()
+            },
+        );
+        let mut state = scheduler.state.lock().unwrap();
+
+        assert!(matches!(scheduler.take_over(&mut state), Next::Wait));
+        assert!(!scheduler.aborts[0].is_raised());
+
+        state.p_core_time = Duration::from_millis(10);
+        state.p_core_moves = 1;
+        assert!(matches!(scheduler.take_over(&mut state), Next::Search(0)));
+        assert!(scheduler.aborts[0].is_raised());
+    }
+
+    #[test]
+    fn test_result_finished_during_take_over_is_kept() {
+        let board = Board::new();
+        let shared = SharedSearch::new(SearchConfig::default());
+        let state = State {
+            // Taken over: the entry already belongs to a P-core worker
+            running: vec![Running {
+                index: 0,
+                on_e_core: false,
+                since: Instant::now(),
+            }],
+            results: vec![None],
+            ..State::default()
+        };
+        let scheduler = scheduler(&board, &shared, state);
+
+        scheduler.finish(0, true, Instant::now(), Some((25, Vec::new())));
+        // The P-core search fails low against the bound the E-core one set
+        scheduler.finish(0, false, Instant::now(), None);
+
+        let state = scheduler.state.lock().unwrap();
+        assert_eq!(state.results[0], Some((25, Vec::new())));
+        assert!(state.running.is_empty());
+    }
+
+    #[test]
+    fn test_light_moves_wait_for_the_root_bound_only_if_there_is_one() {
+        let board = Board::new();
+        for root_bound in [true, false] {
+            let shared = SharedSearch::new(SearchConfig {
+                root_bound,
+                ..SearchConfig::default()
+            });
+            let state = State {
+                heavy: VecDeque::from([0]),
+                light: VecDeque::from([1]),
+                results: vec![None; 2],
+                ..State::default()
+            };
+            let scheduler = scheduler(&board, &shared, state);
+
+            let next = scheduler.next(true);
+            if root_bound {
+                assert!(matches!(next, Next::Wait));
+            } else {
+                assert!(matches!(next, Next::Search(1)));
+            }
+        }
+    }
+}
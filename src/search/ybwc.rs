@@ -0,0 +1,132 @@
+//! Young Brothers Wait Concept. At every node deep enough to be worth it the
+//! eldest brother, the first move in the ordering, is searched alone; only
+//! once it has set a bound are the younger brothers split across the pool.
+//! They search with null windows against an alpha shared through the split
+//! point, so a brother that raises it narrows the windows of those that
+//! start after it. A brother that fails high raises the split point's abort,
+//! which reaches every task below it, nested split points included.
+//!
+//! Split nodes search full width; null moves, reductions, pruning and
+//! extensions only apply in the serial search below `MIN_SPLIT_DEPTH`.
+//! Jamboree (`jamboree.rs`) splits the same nodes but tests the brothers
+//! first.
+
+use crate::board::{Board, BoardRepresentation};
+use crate::scheduling::CorePolicy;
+use crate::search::iterative::SearchResult;
+use crate::search::minimax::{SearchContext, SharedSearch};
+use crate::search::split::{split_iterative_search, split_node, split_search_shared, split_stopped, start_task, Abort};
+use crate::types::*;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicI32, Ordering};
+
+/// YBWC search to `depth`; table stats and node counts end up in `shared`.
+/// If `shared` is stopped before the search finishes, the first ordered move
+/// is returned with a score of 0.
+pub fn ybwc_search_shared(
+    board: &mut Board,
+    depth: u32,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> (Move, i32) {
+    split_search_shared(board, depth, policy, threads, mixed_ratio, shared, ybwc_split)
+}
+
+/// Iterative deepening with YBWC passes, bounded by the limits attached to
+/// `shared`. Returns the deepest completed iteration.
+pub fn ybwc_iterative_search(
+    board: &mut Board,
+    policy: CorePolicy,
+    threads: usize,
+    mixed_ratio: f32,
+    shared: &SharedSearch,
+) -> SearchResult {
+    split_iterative_search(board, policy, threads, mixed_ratio, shared, ybwc_split)
+}
+
+// The eldest here, then the younger brothers in parallel (a `SplitFn`)
+fn ybwc_split(
+    board: &mut Board,
+    moves: &[Move],
+    depth: u32,
+    alpha: i32,
+    beta: i32,
+    pv_move: Option<Move>,
+    ctx: &mut SearchContext,
+) -> (i32, Option<Move>) {
+    let shared = ctx.shared().expect("split points need a shared search");
+    let eldest = moves[0];
+    let undo = board.make_move(&eldest);
+    ctx.follow_pv = pv_move == Some(eldest);
+    ctx.ply += 1;
+    let eldest_score = -split_node(board, depth - 1, -beta, -alpha, ctx, ybwc_split);
+    ctx.ply -= 1;
+    board.unmake_move(&eldest, undo);
+    if ctx.stopped {
+        return (0, None);
+    }
+    if eldest_score > alpha {
+        ctx.update_pv(eldest);
+    }
+    if eldest_score >= beta {
+        ctx.orderer.record_cutoff(board, &eldest, ctx.ply, depth);
+        return (eldest_score, Some(eldest));
+    }
+
+    // Best score so far; with `alpha` it bounds the brothers still to start
+    let best = AtomicI32::new(eldest_score);
+    let abort = Abort::new(ctx.abort());
+    let node = *board;
+    let ply = ctx.ply;
+    let orderer = &ctx.orderer;
+
+    let brothers: Vec<Option<(i32, Move, Vec<Move>)>> = moves[1..]
+        .par_iter()
+        .map(|&mv| {
+            if abort.is_raised() {
+                return None;
+            }
+            let (mut task, mut child) = start_task(shared, &abort, orderer, &node, mv, ply);
+
+            // Prove the move is no better than alpha; search properly if it is
+            let alpha = alpha.max(best.load(Ordering::Relaxed));
+            let mut score = -split_node(&mut child, depth - 1, -alpha - 1, -alpha, &mut task, ybwc_split);
+            if score > alpha && score < beta && !task.stopped {
+                score = -split_node(&mut child, depth - 1, -beta, -alpha, &mut task, ybwc_split);
+            }
+            let stopped = task.stopped;
+            let line = task.line().to_vec();
+            task.finish();
+            if stopped {
+                return None;
+            }
+
+            best.fetch_max(score, Ordering::Relaxed);
+            if score >= beta {
+                abort.raise();
+            }
+            Some((score, mv, line))
+        })
+        .collect();
+
+    if split_stopped(ctx, shared) {
+        return (0, None);
+    }
+
+    let (mut best_score, mut best_move) = (eldest_score, eldest);
+    for (score, mv, line) in brothers.into_iter().flatten() {
+        if score > best_score {
+            best_score = score;
+            best_move = mv;
+            if score > alpha {
+                ctx.set_pv(mv, &line);
+            }
+        }
+    }
+    if best_score >= beta {
+        ctx.orderer.record_cutoff(board, &best_move, ctx.ply, depth);
+    }
+    (best_score, Some(best_move))
+}
@@ -0,0 +1,83 @@
+//! Labelled positions for tuning, read from EPD or CSV files.
+//!
+//! EPD lines hold the four FEN fields, optional move clocks and the result
+//! as `c9 "1-0";`, `"1/2-1/2"` or `[0.5]`. CSV lines are `fen,result` with
+//! the result as `1-0`/`0-1`/`1/2-1/2` or `1`/`0.5`/`0`; a header line is
+//! skipped. Results are always from White's point of view.
+
+use crate::board::{Board, BoardRepresentation};
+use std::fs;
+
+#[derive(Clone, Copy, Debug)]
+pub struct LabelledPosition {
+    pub board: Board,
+    /// 1.0 White won, 0.5 draw, 0.0 Black won
+    pub result: f64,
+}
+
+/// Load a `.csv` file as CSV and anything else as EPD. Blank lines and
+/// lines starting with `#` are ignored.
+pub fn load_positions(path: &str) -> Result<Vec<LabelledPosition>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let csv = path.to_ascii_lowercase().ends_with(".csv");
+
+    let mut positions = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if csv && number == 0 && line.to_ascii_lowercase().starts_with("fen") {
+            continue;
+        }
+        let position = if csv { parse_csv_line(line) } else { parse_epd_line(line) };
+        positions.push(position.map_err(|e| format!("{}:{}: {}", path, number + 1, e))?);
+    }
+    Ok(positions)
+}
+
+pub fn parse_epd_line(line: &str) -> Result<LabelledPosition, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 5 {
+        return Err("expected a FEN followed by a result".to_string());
+    }
+    let board = Board::from_fen(&fields[..4].join(" "))?;
+
+    // The result is the first token after the FEN that reads as one; bare
+    // numbers are move clocks, not results
+    let result = fields[4..]
+        .iter()
+        .map(|token| token.trim_end_matches(';'))
+        .find_map(|token| {
+            let quoted = token.trim_matches('"');
+            let bracketed = token.strip_prefix('[').and_then(|t| t.strip_suffix(']'));
+            bracketed.and_then(parse_score).or_else(|| parse_result(quoted))
+        })
+        .ok_or_else(|| format!("no result in '{}'", line))?;
+    Ok(LabelledPosition { board, result })
+}
+
+pub fn parse_csv_line(line: &str) -> Result<LabelledPosition, String> {
+    let (fen, result) = line.rsplit_once(',').ok_or("expected fen,result")?;
+    let result = result.trim().trim_matches('"');
+    let result = parse_result(result)
+        .or_else(|| parse_score(result))
+        .ok_or_else(|| format!("bad result '{}'", result))?;
+    let board = Board::from_fen(fen.trim().trim_matches('"'))?;
+    Ok(LabelledPosition { board, result })
+}
+
+// PGN-style result
+fn parse_result(token: &str) -> Option<f64> {
+    match token {
+        "1-0" => Some(1.0),
+        "0-1" => Some(0.0),
+        "1/2-1/2" => Some(0.5),
+        _ => None,
+    }
+}
+
+// Numeric result, 0 to 1
+fn parse_score(token: &str) -> Option<f64> {
+    token.parse::<f64>().ok().filter(|r| (0.0..=1.0).contains(r))
+}
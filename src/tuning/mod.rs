@@ -0,0 +1,5 @@
+pub mod dataset;
+pub mod texel;
+
+pub use dataset::{load_positions, LabelledPosition};
+pub use texel::{fit_k, mean_error, quiet_positions, tune, TuneResult, TunerConfig};
@@ -0,0 +1,147 @@
+//! Texel tuning: fit the evaluation weights to game results. A score `q`
+//! (White's view) predicts White's result as `1 / (1 + 10^(-K q / 400))`,
+//! and the weights are nudged one at a time, as in Texel's local search,
+//! while the mean squared error against the real results falls.
+//!
+//! Positions are first played out along their quiescence line, so the
+//! static evaluation being tuned never sees a half-finished exchange.
+
+use crate::board::BoardRepresentation;
+use crate::evaluation::{evaluate_with, EvalParams};
+use crate::search::minimax::{quiescence, SearchContext, INFINITY};
+use crate::tuning::dataset::LabelledPosition;
+use crate::types::Color;
+use rayon::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TunerConfig {
+    /// Passes over every weight; stops early once a pass changes nothing
+    pub passes: usize,
+    /// How far each weight is moved per try
+    pub step: i32,
+    /// Sigmoid scale; fitted to the starting weights when `None`
+    pub k: Option<f64>,
+}
+
+impl Default for TunerConfig {
+    fn default() -> Self {
+        Self {
+            passes: 10,
+            step: 1,
+            k: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TuneResult {
+    pub params: EvalParams,
+    pub k: f64,
+    pub initial_error: f64,
+    pub final_error: f64,
+    /// Passes run, including the last one that found nothing
+    pub passes: usize,
+}
+
+/// Each position at the end of its quiescence line under `params`.
+pub fn quiet_positions(positions: &[LabelledPosition], params: &EvalParams) -> Vec<LabelledPosition> {
+    positions
+        .par_iter()
+        .map(|position| {
+            let mut board = position.board;
+            let mut ctx = SearchContext::without_tt();
+            ctx.eval = *params;
+            quiescence(&mut board, -INFINITY, INFINITY, &mut ctx);
+            for mv in ctx.pv() {
+                board.make_move(mv);
+            }
+            LabelledPosition { board, ..*position }
+        })
+        .collect()
+}
+
+/// Mean squared difference between the results and the predictions of the
+/// static evaluation.
+pub fn mean_error(positions: &[LabelledPosition], params: &EvalParams, k: f64) -> f64 {
+    if positions.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = positions
+        .par_iter()
+        .map(|position| {
+            let score = match position.board.to_move() {
+                Color::White => evaluate_with(&position.board, params),
+                Color::Black => -evaluate_with(&position.board, params),
+            };
+            (position.result - sigmoid(score, k)).powi(2)
+        })
+        .sum();
+    total / positions.len() as f64
+}
+
+/// The `K` that best fits `params` to the results, to two decimals.
+pub fn fit_k(positions: &[LabelledPosition], params: &EvalParams) -> f64 {
+    let mut best = (f64::MAX, 1.0);
+    let mut search = |candidates: Vec<f64>| {
+        for k in candidates {
+            let error = mean_error(positions, params, k);
+            if error < best.0 {
+                best = (error, k);
+            }
+        }
+        best.1
+    };
+    let coarse = search((1..=30).map(|i| i as f64 / 10.0).collect());
+    search((-9..=9).map(|i| coarse + i as f64 / 100.0).filter(|k| *k > 0.0).collect())
+}
+
+/// Tune every weight in `initial.weights_mut()` against `positions`.
+/// `progress` is called after each pass with its number and the error.
+pub fn tune(
+    positions: &[LabelledPosition],
+    initial: &EvalParams,
+    config: &TunerConfig,
+    mut progress: impl FnMut(usize, f64),
+) -> TuneResult {
+    let quiet = quiet_positions(positions, initial);
+    let k = config.k.unwrap_or_else(|| fit_k(&quiet, initial));
+    let initial_error = mean_error(&quiet, initial, k);
+
+    let mut params = *initial;
+    let mut error = initial_error;
+    let weights = params.weights_mut().len();
+    let mut passes = 0;
+    while passes < config.passes {
+        passes += 1;
+        let mut improved = false;
+        for index in 0..weights {
+            for delta in [config.step, -config.step] {
+                let mut candidate = params;
+                *candidate.weights_mut()[index] += delta;
+                let candidate_error = mean_error(&quiet, &candidate, k);
+                if candidate_error < error {
+                    params = candidate;
+                    error = candidate_error;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        progress(passes, error);
+        if !improved {
+            break;
+        }
+    }
+
+    TuneResult {
+        params,
+        k,
+        initial_error,
+        final_error: error,
+        passes,
+    }
+}
+
+fn sigmoid(score: i32, k: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * score as f64 / 400.0))
+}
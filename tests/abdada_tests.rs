@@ -0,0 +1,54 @@
+mod common;
+
+use common::{exact_config, KIWIPETE, STARTPOS};
+use devi::board::{Board, BoardRepresentation};
+use devi::moves::generate_legal_moves;
+use devi::scheduling::CorePolicy;
+use devi::search::{
+    abdada_iterative_search, abdada_search_shared, lazy_smp_search_shared, search_shared, SearchConfig, SharedSearch,
+    TimeManager,
+};
+
+#[test]
+fn test_abdada_matches_serial() {
+    for (fen, depth) in [(STARTPOS, 5), (KIWIPETE, 5)] {
+        let board = Board::from_fen(fen).unwrap();
+        let (_, serial) = search_shared(&mut board.clone(), depth, &SharedSearch::new(exact_config()));
+
+        for _ in 0..3 {
+            let shared = SharedSearch::new(exact_config());
+            let (mv, score) = abdada_search_shared(&mut board.clone(), depth, CorePolicy::None, 4, 0.0, &shared);
+            assert_eq!(score, serial, "{}", fen);
+            assert!(generate_legal_moves(&mut board.clone(), board.to_move()).contains(&mv));
+        }
+    }
+}
+
+#[test]
+fn test_deferring_saves_nodes_over_lazy_smp() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+
+    let abdada = SharedSearch::new(SearchConfig::default());
+    abdada_search_shared(&mut board.clone(), 5, CorePolicy::None, 4, 0.0, &abdada);
+    let lazy = SharedSearch::new(SearchConfig::default());
+    lazy_smp_search_shared(&mut board.clone(), 5, CorePolicy::None, 4, 0.0, &lazy);
+
+    assert!(
+        abdada.nodes() < lazy.nodes(),
+        "abdada {} lazy smp {}",
+        abdada.nodes(),
+        lazy.nodes()
+    );
+}
+
+#[test]
+fn test_iterative_abdada() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let shared = SharedSearch::new(SearchConfig::default()).with_time(TimeManager::fixed_depth(5));
+    let result = abdada_iterative_search(&mut board.clone(), CorePolicy::None, 4, 0.0, &shared);
+
+    let depths: Vec<u32> = result.iterations.iter().map(|i| i.depth).collect();
+    assert_eq!(depths, vec![1, 2, 3, 4, 5]);
+    assert_eq!(result.pv[0], result.best_move);
+    assert_eq!(result.nodes, shared.nodes());
+}
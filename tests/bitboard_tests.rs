@@ -0,0 +1,93 @@
+//! Cross-checks the bitboard representation against the mailbox board.
+//! Move generation is covered by perft_tests/movegen_tests under `--features bitboard`.
+
+use devi::board::{ArrayBoard, BitBoard, BoardRepresentation};
+use devi::types::*;
+
+const POSITIONS: &[&str] = &[
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "8/8/8/8/k2Pp2Q/8/8/3K4 b - d3 0 1",
+];
+
+#[test]
+fn test_fen_round_trip_matches_array_board() {
+    for fen in POSITIONS {
+        let array = ArrayBoard::from_fen(fen).unwrap();
+        let bits = BitBoard::from_fen(fen).unwrap();
+        assert_eq!(array.to_fen(), bits.to_fen());
+        assert_eq!(bits.to_fen(), *fen);
+    }
+}
+
+#[test]
+fn test_attack_queries_match_array_board() {
+    for fen in POSITIONS {
+        let array = ArrayBoard::from_fen(fen).unwrap();
+        let bits = BitBoard::from_fen(fen).unwrap();
+
+        for color in [Color::White, Color::Black] {
+            assert_eq!(array.find_king(color), bits.find_king(color), "{}", fen);
+            assert_eq!(array.is_in_check(color), bits.is_in_check(color), "{}", fen);
+
+            for sq in 0..64 {
+                let square = Square(sq);
+                assert_eq!(
+                    array.is_square_attacked(square, color),
+                    bits.is_square_attacked(square, color),
+                    "{} attacked by {:?} in {}",
+                    square,
+                    color,
+                    fen
+                );
+                assert_eq!(
+                    array.count_attackers(square, color),
+                    bits.count_attackers(square, color),
+                    "{} attackers of {:?} in {}",
+                    square,
+                    color,
+                    fen
+                );
+            }
+
+            for piece_type in [
+                PieceType::Pawn,
+                PieceType::Knight,
+                PieceType::Bishop,
+                PieceType::Rook,
+                PieceType::Queen,
+                PieceType::King,
+            ] {
+                assert_eq!(
+                    array.count_pieces(piece_type, color),
+                    bits.count_pieces(piece_type, color)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_special_moves_restore_position() {
+    let mut bits =
+        BitBoard::from_fen("r3k2r/Pppp1ppp/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap();
+    let original = bits.to_fen();
+
+    let moves = [
+        Move::new(Square(4), Square(6), Some(SpecialMove::Castle), None),
+        Move::new(Square(4), Square(2), Some(SpecialMove::Castle), None),
+        Move::new(Square(36), Square(43), Some(SpecialMove::EnPassant), None),
+        Move::new(Square(48), Square(57), Some(SpecialMove::Promotion), Some(PieceType::Queen)),
+        Move::new(Square(0), Square(56), None, None),
+    ];
+
+    for mv in &moves {
+        let undo = bits.make_move(mv);
+        assert!(bits.get_piece(mv.to).is_some());
+        bits.unmake_move(mv, undo);
+        assert_eq!(bits.to_fen(), original, "after {}", mv);
+    }
+}
@@ -0,0 +1,35 @@
+//! Positions and helpers shared by the integration tests. Each test crate
+//! uses its own subset.
+#![allow(dead_code)]
+
+use devi::board::{Board, BoardRepresentation};
+use devi::moves::generate_legal_moves;
+use devi::search::{QuiescenceConfig, SearchConfig};
+use devi::types::*;
+
+pub const STARTPOS: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+pub const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+
+/// Search settings under which a parallel search must score like the serial
+/// one. Delta pruning cuts against alpha, so with it on a position's table
+/// bounds depend on the window it was first searched with, and the windows
+/// of a split search differ from the serial ones.
+pub fn exact_config() -> SearchConfig {
+    SearchConfig {
+        quiescence: QuiescenceConfig {
+            delta_pruning: false,
+            ..QuiescenceConfig::default()
+        },
+        ..SearchConfig::default()
+    }
+}
+
+/// Every PV move must be legal in the position the previous ones lead to.
+pub fn assert_pv_playable(board: &Board, pv: &[Move]) {
+    let mut b = *board;
+    for mv in pv {
+        let side = b.to_move();
+        assert!(generate_legal_moves(&mut b, side).contains(mv), "illegal PV move {}", mv.to_algebraic());
+        b.make_move(mv);
+    }
+}
@@ -0,0 +1,136 @@
+//! Emulation is process-wide, so these tests live in their own binary and
+//! all use the same slowdown.
+
+use devi::board::{Board, BoardRepresentation};
+use devi::scheduling::emulation::{is_slow_worker, mark_slow_worker, set_emulated_slowdown};
+use devi::scheduling::{create_e_core_pool, create_p_core_pool, worker_is_efficient, CorePolicy};
+use devi::search::probe::ClassificationConfig;
+use devi::search::{search_shared, two_phase_search, two_phase_search_with_metrics, SearchConfig, SharedSearch, TwoPhaseConfig};
+use std::time::{Duration, Instant};
+
+const SLOWDOWN: f32 = 4.0;
+
+fn start_position() -> Board {
+    let mut board = Board::new();
+    board.setup_starting_position();
+    board
+}
+
+/// Time and score of a depth 4 search run on `pool`.
+fn timed_search(pool: &rayon::ThreadPool) -> (Duration, i32) {
+    pool.install(|| {
+        let start = Instant::now();
+        let shared = SharedSearch::new(SearchConfig::default());
+        let (_, score) = search_shared(&mut start_position(), 4, &shared);
+        (start.elapsed(), score)
+    })
+}
+
+#[test]
+fn test_efficient_workers() {
+    assert!(!worker_is_efficient(CorePolicy::None, 3, 4, 0.5));
+    assert!(!worker_is_efficient(CorePolicy::FastBias, 3, 4, 0.5));
+    assert!(worker_is_efficient(CorePolicy::EfficientBias, 0, 4, 0.5));
+
+    let mixed: Vec<bool> = (0..10).map(|i| worker_is_efficient(CorePolicy::Mixed, i, 10, 0.8)).collect();
+    assert_eq!(mixed.iter().filter(|slow| **slow).count(), 2);
+    assert!(!mixed[7] && mixed[8] && mixed[9]);
+}
+
+#[test]
+fn test_only_marked_threads_are_slow() {
+    set_emulated_slowdown(SLOWDOWN);
+    assert!(!is_slow_worker());
+    std::thread::spawn(|| {
+        mark_slow_worker();
+        assert!(is_slow_worker());
+    })
+    .join()
+    .unwrap();
+    assert!(!is_slow_worker());
+}
+
+#[test]
+fn test_e_core_pool_runs_slower_with_same_result() {
+    set_emulated_slowdown(SLOWDOWN);
+    let p_pool = create_p_core_pool(1).unwrap();
+    let e_pool = create_e_core_pool(1).unwrap();
+
+    let (fast, fast_score) = timed_search(&p_pool);
+    let (slow, slow_score) = timed_search(&e_pool);
+
+    assert_eq!(fast_score, slow_score);
+    // 4x nominal; leave room for timer noise
+    assert!(slow > fast * 2, "P {:?} vs E {:?}", fast, slow);
+}
+
+#[test]
+fn test_two_phase_result_unchanged_by_emulation() {
+    set_emulated_slowdown(SLOWDOWN);
+    let config = TwoPhaseConfig {
+        probe_depth: 1,
+        p_core_threads: 2,
+        e_core_threads: 2,
+        classification: ClassificationConfig::default(),
+        search: SearchConfig::default(),
+    };
+
+    let (_, emulated) = two_phase_search(&mut start_position(), 4, &config);
+    let (_, reference) = search_shared(&mut start_position(), 4, &SharedSearch::new(SearchConfig::default()));
+    assert_eq!(emulated, reference);
+}
+
+/// Two-phase config that sends all but one move of the start position to the
+/// E-cores.
+fn lopsided(adaptive: bool) -> TwoPhaseConfig {
+    TwoPhaseConfig {
+        probe_depth: 1,
+        p_core_threads: 2,
+        e_core_threads: 2,
+        classification: ClassificationConfig {
+            heavy_ratio: 0.05,
+            light_threshold: 1.0,
+            adaptive,
+        },
+        search: SearchConfig::default(),
+    }
+}
+
+#[test]
+fn test_adaptive_two_phase_migrates_light_moves() {
+    set_emulated_slowdown(SLOWDOWN);
+    let (_, score, metrics) = two_phase_search_with_metrics(&mut start_position(), 4, &lopsided(true));
+    let (_, reference) = search_shared(&mut start_position(), 4, &SharedSearch::new(SearchConfig::default()));
+
+    assert_eq!(score, reference);
+    assert_eq!(metrics.heavy_move_count, 1);
+    assert!(metrics.migrated_to_p > 0, "{:?}", metrics);
+}
+
+#[test]
+fn test_adaptive_two_phase_without_root_bound() {
+    set_emulated_slowdown(SLOWDOWN);
+    let config = TwoPhaseConfig {
+        search: SearchConfig {
+            root_bound: false,
+            ..SearchConfig::default()
+        },
+        ..lopsided(true)
+    };
+    let (_, score, metrics) = two_phase_search_with_metrics(&mut start_position(), 4, &config);
+    let (_, reference) = search_shared(&mut start_position(), 4, &SharedSearch::new(SearchConfig::default()));
+
+    assert_eq!(score, reference);
+    // E-core workers don't wait on a bound that is never set
+    assert!(metrics.migrated_to_p < metrics.light_move_count, "{:?}", metrics);
+}
+
+#[test]
+fn test_static_two_phase_keeps_its_split() {
+    set_emulated_slowdown(SLOWDOWN);
+    let (_, adaptive_score, _) = two_phase_search_with_metrics(&mut start_position(), 4, &lopsided(true));
+    let (_, static_score, metrics) = two_phase_search_with_metrics(&mut start_position(), 4, &lopsided(false));
+
+    assert_eq!(adaptive_score, static_score);
+    assert_eq!((metrics.migrated_to_p, metrics.migrated_to_e), (0, 0));
+}
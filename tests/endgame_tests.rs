@@ -0,0 +1,101 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::endgame::{kpk_is_win, scale_factor, SCALE_NORMAL};
+use devi::evaluation::{evaluate, is_insufficient_material, KNOWN_WIN};
+use devi::moves::generate_legal_moves;
+use devi::search::search;
+use devi::types::*;
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+fn sq(name: &str) -> Square {
+    let bytes = name.as_bytes();
+    Square((bytes[1] - b'1') * 8 + (bytes[0] - b'a'))
+}
+
+#[test]
+fn test_insufficient_material() {
+    for fen in [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",    // KK
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",  // KBK
+        "4k3/8/8/8/8/8/8/1N2K3 b - - 0 1",  // KNK
+        "4k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", // KNNK
+        "2b1k3/8/8/8/8/8/8/3BK3 w - - 0 1", // bishops all on light squares
+    ] {
+        assert!(is_insufficient_material(&board(fen)), "{}", fen);
+        assert_eq!(evaluate(&board(fen)), 0, "{}", fen);
+    }
+
+    for fen in [
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",  // a pawn can queen
+        "4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1", // bishops on both colours
+        "4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1", // KBNK
+        "3nk3/8/8/8/8/8/8/1N2K3 w - - 0 1", // KNKN can be mated with help
+    ] {
+        assert!(!is_insufficient_material(&board(fen)), "{}", fen);
+    }
+}
+
+#[test]
+fn test_opposite_coloured_bishops_are_scaled() {
+    // Same material; only the colour of Black's bishop differs
+    let opposite = board("4k1b1/5p2/8/8/8/8/4PP2/2B1K3 w - - 0 1");
+    let same = board("4kb2/5p2/8/8/8/8/4PP2/2B1K3 w - - 0 1");
+    assert_eq!(scale_factor(&opposite), SCALE_NORMAL / 2);
+    assert_eq!(scale_factor(&same), SCALE_NORMAL);
+    assert!(evaluate(&opposite) < evaluate(&same));
+
+    // Other pieces on the board restore the normal scale
+    assert_eq!(scale_factor(&board("r3k1b1/5p2/8/8/8/8/4PP2/R1B1K3 w - - 0 1")), SCALE_NORMAL);
+}
+
+#[test]
+fn test_kpk_bitbase() {
+    // King on the sixth in front of its pawn wins whoever moves
+    assert!(kpk_is_win(sq("e6"), sq("e8"), sq("e5"), Color::White, true));
+    assert!(kpk_is_win(sq("e6"), sq("e8"), sq("e5"), Color::White, false));
+    // Outside the square of the pawn
+    assert!(kpk_is_win(sq("h1"), sq("h8"), sq("a4"), Color::White, true));
+    // Rook pawn with the defender in the corner
+    assert!(!kpk_is_win(sq("a6"), sq("a8"), sq("a5"), Color::White, true));
+    // Defender takes the loose pawn
+    assert!(!kpk_is_win(sq("a1"), sq("d3"), sq("e2"), Color::White, false));
+
+    // The same positions for Black, and mirrored to the kingside
+    assert!(kpk_is_win(sq("e3"), sq("e1"), sq("e4"), Color::Black, false));
+    assert!(!kpk_is_win(sq("h3"), sq("h1"), sq("h4"), Color::Black, false));
+    assert!(kpk_is_win(sq("a1"), sq("a8"), sq("h4"), Color::White, true));
+
+    assert!(evaluate(&board("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1")) > KNOWN_WIN);
+    assert_eq!(evaluate(&board("k7/8/K7/P7/8/8/8/8 w - - 0 1")), 0);
+    assert!(evaluate(&board("8/8/8/8/4p3/4k3/8/4K3 w - - 0 1")) < -KNOWN_WIN);
+}
+
+#[test]
+fn test_mop_up_drives_king_to_the_right_corner() {
+    // Strong side's view: the lone king on the edge scores higher
+    let edge = evaluate(&board("k7/8/2K5/8/8/8/8/7R w - - 0 1"));
+    let center = evaluate(&board("8/8/2K5/8/3k4/8/8/7R w - - 0 1"));
+    assert!(edge > center && center > KNOWN_WIN);
+
+    // Dark-squared bishop mates in a1 or h8, not a8
+    let right = evaluate(&board("8/8/8/8/8/2K5/8/k1B1N3 w - - 0 1"));
+    let wrong = evaluate(&board("k7/8/2K5/8/8/8/8/2B1N3 w - - 0 1"));
+    assert!(right > wrong && wrong > KNOWN_WIN, "{} vs {}", right, wrong);
+}
+
+#[test]
+fn test_converts_kqk_in_self_play() {
+    let mut board = board("8/8/8/3k4/8/8/8/4K2Q w - - 0 1");
+    for _ in 0..60 {
+        let side = board.to_move();
+        if generate_legal_moves(&mut board, side).is_empty() {
+            assert!(board.is_in_check(side), "stalemate: {}", board.to_fen());
+            return;
+        }
+        let (mv, _) = search(&mut board, 4);
+        board.make_move(&mv);
+    }
+    panic!("no mate within 30 moves: {}", board.to_fen());
+}
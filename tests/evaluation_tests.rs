@@ -0,0 +1,83 @@
+mod common;
+
+use common::KIWIPETE;
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::positional::{self, PieceSquareTables};
+use devi::evaluation::{evaluate, game_phase, MAX_PHASE};
+use devi::types::*;
+
+fn start_position() -> Board {
+    let mut board = Board::new();
+    board.setup_starting_position();
+    board
+}
+
+fn flip(color: Color) -> Color {
+    match color {
+        Color::White => Color::Black,
+        Color::Black => Color::White,
+    }
+}
+
+/// The same position with colours swapped and the board flipped.
+fn mirror(board: &Board) -> Board {
+    let mut mirrored = Board::new();
+    mirrored.clear();
+    for idx in 0..64 {
+        if let Some(piece) = board.get_piece(Square(idx)) {
+            mirrored.set_piece(Square(idx ^ 56), Some(Piece::new(piece.piece_type, flip(piece.color))));
+        }
+    }
+    mirrored.set_to_move(flip(board.to_move()));
+    mirrored
+}
+
+fn after(board: &Board, text: &str) -> Board {
+    let mut board = *board;
+    let mv = devi::cli::uci::parse_move(&mut board, text).unwrap();
+    board.make_move(&mv);
+    board
+}
+
+#[test]
+fn test_evaluation_is_symmetric() {
+    assert_eq!(evaluate(&start_position()), 0);
+
+    let kiwipete = Board::from_fen(KIWIPETE).unwrap();
+    assert_eq!(evaluate(&kiwipete), evaluate(&mirror(&kiwipete)));
+
+    // Same position, other side to move: same magnitude, other sign
+    let mut black_to_move = kiwipete;
+    black_to_move.set_to_move(Color::Black);
+    assert_eq!(evaluate(&black_to_move), -evaluate(&kiwipete));
+}
+
+#[test]
+fn test_development_beats_the_rim() {
+    let start = start_position();
+    // Scores are for Black after White's move, so lower is better for White
+    assert!(evaluate(&after(&start, "g1f3")) < evaluate(&after(&start, "g1h3")));
+    assert!(evaluate(&after(&start, "e2e4")) < evaluate(&after(&start, "a2a3")));
+}
+
+#[test]
+fn test_game_phase() {
+    assert_eq!(game_phase(&start_position()), MAX_PHASE);
+    assert_eq!(game_phase(&Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap()), 0);
+    assert_eq!(game_phase(&Board::from_fen("3qk3/8/8/8/8/8/8/R3K3 w - - 0 1").unwrap()), 6);
+    // Promoted queens don't overflow the phase
+    assert_eq!(game_phase(&Board::from_fen("QQQQkQQQ/8/8/8/8/8/8/4K3 w - - 0 1").unwrap()), MAX_PHASE);
+}
+
+#[test]
+fn test_king_centralises_in_the_endgame() {
+    // With queens on a central king is exposed; in a pawn ending it is active
+    let tables = PieceSquareTables::default();
+    let middlegame_center = Board::from_fen("rnbqkbnr/pppppppp/8/8/4K3/8/PPPPPPPP/RNBQ1BNR w kq - 0 1").unwrap();
+    let middlegame_home = start_position();
+    assert!(positional::evaluate(&middlegame_center, &tables) < positional::evaluate(&middlegame_home, &tables));
+
+    let ending_center = Board::from_fen("4k3/pppp4/8/8/4K3/8/PPPP4/8 w - - 0 1").unwrap();
+    let ending_home = Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/4K3 w - - 0 1").unwrap();
+    assert!(positional::evaluate(&ending_center, &tables) > positional::evaluate(&ending_home, &tables));
+}
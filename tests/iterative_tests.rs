@@ -0,0 +1,73 @@
+mod common;
+
+use common::{assert_pv_playable, KIWIPETE};
+use devi::board::{Board, BoardRepresentation};
+use devi::scheduling::CorePolicy;
+use devi::search::minimax::MATE_SCORE;
+use devi::search::{
+    iterative_search, parallel_iterative_search, search_shared, SearchConfig, SearchResult, SharedSearch,
+    TimeManager,
+};
+
+fn to_depth(depth: u32) -> SharedSearch {
+    SharedSearch::new(SearchConfig::default()).with_time(TimeManager::fixed_depth(depth))
+}
+
+#[test]
+fn test_iterations_are_recorded() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let shared = to_depth(4);
+    let result = iterative_search(&mut board.clone(), &shared);
+
+    assert_eq!(result.depth, 4);
+    let depths: Vec<u32> = result.iterations.iter().map(|i| i.depth).collect();
+    assert_eq!(depths, vec![1, 2, 3, 4]);
+
+    let last = result.iterations.last().unwrap();
+    assert_eq!(result.score, last.score);
+    assert_eq!(result.pv, last.pv);
+    assert_eq!(result.iterations.iter().map(|i| i.nodes).sum::<u64>(), result.nodes);
+    assert_eq!(result.nodes, shared.nodes());
+    assert!(result.nps > 0);
+    assert!(result.iterations.windows(2).all(|w| w[0].elapsed <= w[1].elapsed));
+}
+
+#[test]
+fn test_pv_starts_with_best_move_and_is_legal() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let result = iterative_search(&mut board.clone(), &to_depth(4));
+
+    assert_eq!(result.pv[0], result.best_move);
+    assert!(result.pv.len() > 1, "PV: {}", result.pv_string());
+    assert_pv_playable(&board, &result.pv);
+    for info in &result.iterations {
+        assert_pv_playable(&board, &info.pv);
+    }
+}
+
+#[test]
+fn test_iterative_score_matches_fixed_depth() {
+    let mut board = Board::new();
+    board.setup_starting_position();
+
+    let (_, fixed) = search_shared(&mut board.clone(), 4, &SharedSearch::new(SearchConfig::default()));
+    let serial = iterative_search(&mut board.clone(), &to_depth(4));
+    assert_eq!(serial.score, fixed);
+
+    let parallel = parallel_iterative_search(&mut board.clone(), CorePolicy::None, 4, 0.0, &to_depth(4));
+    assert_eq!(parallel.score, fixed);
+    assert_eq!(parallel.depth, 4);
+    assert_pv_playable(&board, &parallel.pv);
+}
+
+#[test]
+fn test_no_legal_moves() {
+    // Fool's mate
+    let mut board = Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+    let result: SearchResult = iterative_search(&mut board, &to_depth(3));
+
+    assert_eq!(result.score, -MATE_SCORE);
+    assert_eq!(result.depth, 0);
+    assert!(result.pv.is_empty());
+    assert!(result.iterations.is_empty());
+}
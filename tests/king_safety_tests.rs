@@ -0,0 +1,73 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::king_safety::{self, KingSafetyConfig, KingSafetyTerms};
+use devi::evaluation::mobility::{self, MobilityConfig};
+use devi::evaluation::{evaluate, evaluate_with, EvalParams, Score};
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+fn white(fen: &str) -> KingSafetyTerms {
+    king_safety::breakdown(&board(fen), &KingSafetyConfig::default())[0]
+}
+
+#[test]
+fn test_pawn_shield_and_open_files() {
+    let intact = white("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1");
+    let pushed = white("6k1/5ppp/8/8/8/6PP/5P2/6K1 w - - 0 1");
+    assert!(intact.pawn_shield.mg > pushed.pawn_shield.mg);
+    assert!(pushed.pawn_shield.mg > 0);
+    assert_eq!(intact.open_files, Score::ZERO);
+
+    // No g-pawns at all, then only Black's
+    let open = white("6k1/5p1p/8/8/8/8/5P1P/6K1 w - - 0 1");
+    let half_open = white("6k1/5ppp/8/8/8/8/5P1P/6K1 w - - 0 1");
+    assert!(open.open_files.mg < half_open.open_files.mg);
+    assert!(half_open.open_files.mg < 0);
+}
+
+#[test]
+fn test_king_attack() {
+    // Queen and knight bearing down on g1 against a bare king shelter
+    let attacked = white("6k1/8/8/8/8/5n2/5PPq/6K1 w - - 0 1");
+    let quiet = white("6k1/8/8/3n4/8/8/5PPq/6K1 w - - 0 1");
+    assert!(attacked.king_attack.mg < quiet.king_attack.mg);
+    assert!(quiet.king_attack.mg < 0);
+    assert_eq!(white("6k1/5ppp/8/8/8/8/5PPP/6K1 w - - 0 1").king_attack, Score::ZERO);
+
+    // Each term can be switched off on its own
+    let config = KingSafetyConfig { king_attack: false, ..KingSafetyConfig::default() };
+    let terms = king_safety::breakdown(&board("6k1/8/8/8/8/5n2/5PPq/6K1 w - - 0 1"), &config)[0];
+    assert_eq!(terms.king_attack, Score::ZERO);
+    assert_eq!(terms.pawn_shield, attacked.pawn_shield);
+}
+
+#[test]
+fn test_mobility() {
+    let config = MobilityConfig::default();
+    let centre = mobility::breakdown(&board("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1"), &config)[0];
+    let corner = mobility::breakdown(&board("4k3/8/8/8/8/8/8/N3K3 w - - 0 1"), &config)[0];
+    assert!(centre.mg > 0 && corner.mg < 0);
+
+    // Squares Black's pawns guard don't count
+    let guarded = mobility::breakdown(&board("4k3/8/2p1p3/8/3N4/8/8/4K3 w - - 0 1"), &config)[0];
+    assert!(guarded.mg < centre.mg);
+
+    let rook_only = MobilityConfig { weights: [Score::ZERO, Score::ZERO, Score::new(2, 4), Score::ZERO], ..config };
+    assert_eq!(mobility::breakdown(&board("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1"), &rook_only)[0], Score::ZERO);
+    let disabled = MobilityConfig { enabled: false, ..config };
+    assert_eq!(mobility::evaluate(&board("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1"), &disabled), Score::ZERO);
+}
+
+#[test]
+fn test_eval_config() {
+    let fen = "r1bq1rk1/ppp2ppp/2n2n2/3pp3/1b1PP3/2N2N2/PPP1BPPP/R1BQ1RK1 w - - 0 1";
+    let mirrored = "r1bq1rk1/ppp1bppp/2n2n2/1B1pp3/3PP3/2N2N2/PPP2PPP/R1BQ1RK1 b - - 0 1";
+    assert_eq!(evaluate(&board(fen)), evaluate_with(&board(fen), &EvalParams::default()));
+    assert_eq!(evaluate(&board(fen)), evaluate(&board(mirrored)));
+
+    let none = EvalParams::none();
+    assert_eq!(king_safety::evaluate(&board(fen), &none.king_safety), Score::ZERO);
+    assert_eq!(mobility::evaluate(&board(fen), &none.mobility), Score::ZERO);
+    assert_eq!(evaluate_with(&board(fen), &none), evaluate_with(&board(mirrored), &none));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::KIWIPETE;
+use devi::board::{Board, BoardRepresentation};
+use devi::scheduling::CorePolicy;
+use devi::search::minimax::mate_distance;
+use devi::search::{
+    iterative_search, lazy_smp_iterative_search, lazy_smp_search_shared, parallel_search_with_mode, ParallelMode,
+    SearchConfig, SharedSearch, TimeManager,
+};
+
+// (FEN, mating move, moves to mate)
+const MATES: [(&str, &str, i32); 3] = [
+    ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", 1),
+    ("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1", "a8a1", 1),
+    ("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1", "b1b7", 2),
+];
+
+fn to_depth(depth: u32) -> SharedSearch {
+    SharedSearch::new(SearchConfig::default()).with_time(TimeManager::fixed_depth(depth))
+}
+
+#[test]
+fn test_lazy_smp_finds_mates() {
+    for (fen, mating_move, n) in MATES {
+        let mut board = Board::from_fen(fen).unwrap();
+        let shared = SharedSearch::new(SearchConfig::default());
+        let (mv, score) = lazy_smp_search_shared(&mut board, 2 * n as u32, CorePolicy::None, 4, 0.0, &shared);
+        assert_eq!(mate_distance(score), Some(n), "{}", fen);
+        // The rook roller also mates in two starting with Ra7
+        if n == 1 {
+            assert_eq!(mv.to_algebraic(), mating_move, "{}", fen);
+        }
+    }
+}
+
+#[test]
+fn test_every_mode_finds_mates() {
+    for mode in [ParallelMode::RootSplit, ParallelMode::LazySmp, ParallelMode::TwoPhase, ParallelMode::Ybwc, ParallelMode::Jamboree, ParallelMode::Abdada] {
+        for (fen, _, n) in MATES {
+            let mut board = Board::from_fen(fen).unwrap();
+            let shared = SharedSearch::new(SearchConfig::default());
+            let (_, score) = parallel_search_with_mode(&mut board, 2 * n as u32, mode, CorePolicy::None, 4, 0.0, &shared);
+            assert_eq!(mate_distance(score), Some(n), "{:?} {}", mode, fen);
+        }
+    }
+}
+
+#[test]
+fn test_single_thread_is_serial_search() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let serial = iterative_search(&mut board.clone(), &to_depth(4));
+    let lazy = lazy_smp_iterative_search(&mut board.clone(), CorePolicy::None, 1, 0.0, &to_depth(4));
+
+    assert_eq!(lazy.best_move, serial.best_move);
+    assert_eq!(lazy.score, serial.score);
+    assert_eq!(lazy.pv, serial.pv);
+    assert_eq!(lazy.nodes, serial.nodes);
+}
+
+#[test]
+fn test_depth_limit_and_helper_nodes() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let shared = to_depth(5);
+    let result = lazy_smp_iterative_search(&mut board.clone(), CorePolicy::None, 4, 0.0, &shared);
+
+    // Only the main worker's iterations are reported, but every node counts
+    let depths: Vec<u32> = result.iterations.iter().map(|i| i.depth).collect();
+    assert_eq!(depths, vec![1, 2, 3, 4, 5]);
+    assert_eq!(result.depth, 5);
+    assert_eq!(result.pv[0], result.best_move);
+    assert_eq!(result.nodes, shared.nodes());
+    assert!(result.nodes > result.iterations.iter().map(|i| i.nodes).sum::<u64>());
+}
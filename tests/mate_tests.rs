@@ -0,0 +1,86 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::scheduling::CorePolicy;
+use devi::search::minimax::{mate_distance, mated_in, MATE_SCORE};
+use devi::search::{
+    iterative_search, parallel_search_shared, search_shared, SearchConfig, SharedSearch, TimeManager,
+};
+
+// (FEN, mating move, moves to mate)
+const MATES: [(&str, &str, i32); 5] = [
+    // Back rank
+    ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", 1),
+    // Scholar's mate
+    ("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", "h5f7", 1),
+    ("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1", "a8a1", 1),
+    // Rook roller: Rb7 Kg8 Ra8#
+    ("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1", "b1b7", 2),
+    ("8/8/7k/8/8/8/R7/1R4K1 w - - 0 1", "a2g2", 3),
+];
+
+fn search(fen: &str, depth: u32) -> (String, i32) {
+    let mut board = Board::from_fen(fen).unwrap();
+    let (mv, score) = search_shared(&mut board, depth, &SharedSearch::new(SearchConfig::default()));
+    (mv.to_algebraic(), score)
+}
+
+#[test]
+fn test_mate_in_n() {
+    for (fen, mating_move, n) in MATES {
+        // A mate is seen once the mated side's node is searched, 2n plies down
+        let (mv, score) = search(fen, 2 * n as u32);
+        assert_eq!(score, MATE_SCORE - (2 * n - 1), "{}", fen);
+        assert_eq!(mate_distance(score), Some(n), "{}", fen);
+        if n < 3 {
+            assert_eq!(mv, mating_move, "{}", fen);
+        }
+
+        // One ply short of seeing it, there's no mate score
+        let (_, shallow) = search(fen, 2 * n as u32 - 1);
+        assert_eq!(mate_distance(shallow), None, "{}", fen);
+    }
+}
+
+#[test]
+fn test_prefers_faster_mate() {
+    // Deeper searches find longer mates too, but the shortest scores highest
+    let (mv, score) = search(MATES[0].0, 5);
+    assert_eq!((mv.as_str(), score), ("a1a8", MATE_SCORE - 1));
+
+    let (mv, score) = search(MATES[3].0, 6);
+    assert_eq!((mv.as_str(), score), ("b1b7", MATE_SCORE - 3));
+}
+
+#[test]
+fn test_mated_side_sees_negative_distance() {
+    // After Rb7 in the rook roller, Black is mated next move whatever it plays
+    let (_, score) = search("7k/1R6/8/8/8/8/R7/6K1 b - - 1 1", 3);
+    assert_eq!(score, mated_in(2));
+    assert_eq!(mate_distance(score), Some(-1));
+}
+
+#[test]
+fn test_parallel_and_iterative_agree_on_mates() {
+    for (fen, _, n) in MATES {
+        let depth = 2 * n as u32;
+        let board = Board::from_fen(fen).unwrap();
+        let (_, serial) = search(fen, depth);
+
+        let shared = SharedSearch::new(SearchConfig::default());
+        let (_, parallel) = parallel_search_shared(&mut board.clone(), depth, CorePolicy::None, 4, 0.0, None, &shared);
+        assert_eq!(parallel, serial, "{}", fen);
+
+        // Mate scores survive the table across iterations
+        let shared = SharedSearch::new(SearchConfig::default()).with_time(TimeManager::fixed_depth(depth + 1));
+        let result = iterative_search(&mut board.clone(), &shared);
+        assert_eq!(result.score, serial, "{}", fen);
+        assert_eq!(result.pv.len(), 2 * n as usize - 1, "{}: {}", fen, result.pv_string());
+    }
+}
+
+#[test]
+fn test_mate_distance_of_ordinary_scores() {
+    assert_eq!(mate_distance(0), None);
+    assert_eq!(mate_distance(-950), None);
+    assert_eq!(mate_distance(MATE_SCORE - 1), Some(1));
+    assert_eq!(mate_distance(mated_in(0)), Some(0));
+}
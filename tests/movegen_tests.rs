@@ -556,3 +556,33 @@ fn test_find_random_position_king() {
     assert_eq!(board.find_king(Color::White), Some(Square(39)));
     assert_eq!(board.find_king(Color::Black), Some(Square(35)));
 }
+
+#[test]
+fn test_captures_match_full_generator() {
+    // Kiwipete, en passant, and capturing/quiet promotions
+    for fen in [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "r3k3/1P6/8/8/8/8/6p1/4K2R b K - 0 1",
+    ] {
+        let board = Board::from_fen(fen).unwrap();
+        for color in [Color::White, Color::Black] {
+            let all = generate_moves(&board, color);
+            let captures = generate_captures(&board, color);
+            let expected: Vec<Move> = all
+                .iter()
+                .copied()
+                .filter(|mv| !board.is_empty(mv.to) || mv.special_move == Some(SpecialMove::EnPassant))
+                .collect();
+            assert_eq!(captures.len(), expected.len(), "{} {:?}", fen, color);
+            assert!(expected.iter().all(|mv| captures.contains(mv)), "{} {:?}", fen, color);
+
+            let promotions = generate_promotions(&board, color);
+            let expected = all
+                .iter()
+                .filter(|mv| mv.promotion.is_some() && board.is_empty(mv.to))
+                .count();
+            assert_eq!(promotions.len(), expected, "{} {:?}", fen, color);
+        }
+    }
+}
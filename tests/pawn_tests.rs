@@ -0,0 +1,93 @@
+use devi::board::zobrist::{compute_hash, compute_pawn_key};
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::pawns::{self, clear_pawn_table, pawn_table_stats, PawnParams, PawnTerms};
+use devi::evaluation::Score;
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+fn breakdown(board: &Board) -> [PawnTerms; 2] {
+    pawns::breakdown(board, &PawnParams::default())
+}
+
+fn white(fen: &str) -> PawnTerms {
+    breakdown(&board(fen))[0]
+}
+
+#[test]
+fn test_structure_terms() {
+    // Doubled and isolated on the c-file
+    let terms = white("4k3/8/8/8/2P5/2P5/8/4K3 w - - 0 1");
+    assert!(terms.doubled.eg < 0);
+    assert!(terms.isolated.eg < 0);
+    assert_eq!(terms.connected, Score::ZERO);
+
+    // A phalanx and a supported chain
+    let terms = white("4k3/8/8/8/3PP3/8/8/4K3 w - - 0 1");
+    assert!(terms.connected.mg > 0);
+    assert_eq!(terms.isolated, Score::ZERO);
+    let terms = white("4k3/8/8/4P3/3P4/8/8/4K3 w - - 0 1");
+    assert!(terms.connected.mg > 0);
+
+    // d3 is left behind by c4 and its stop square is covered by e5
+    let terms = white("4k3/8/8/4p3/2P5/3P4/8/4K3 w - - 0 1");
+    assert!(terms.backward.mg < 0);
+    assert_eq!(white("4k3/8/8/8/2P5/3P4/8/4K3 w - - 0 1").backward, Score::ZERO);
+}
+
+#[test]
+fn test_passed_pawns() {
+    // a-pawn is passed, the c-pawn faces d-pawn
+    let [w, b] = breakdown(&board("4k3/3p4/8/8/P1P5/8/8/4K3 w - - 0 1"));
+    assert!(w.passed.eg > 0);
+    assert_eq!(b.passed, Score::ZERO);
+
+    // Further up is worth more
+    let low = white("4k3/8/8/8/8/P7/8/4K3 w - - 0 1").passed;
+    let high = white("4k3/8/P7/8/8/8/8/4K3 w - - 0 1").passed;
+    assert!(high.eg > low.eg);
+
+    // A blocker in the way, or a guarded path, costs the path bonus
+    let free = white("4k3/8/8/P7/8/8/8/4K3 w - - 0 1").passed;
+    let blocked = white("n3k3/8/8/P7/8/8/8/4K3 w - - 0 1").passed;
+    let guarded = white("4k3/7r/8/P7/8/8/8/4K3 w - - 0 1").passed;
+    assert!(free.eg > guarded.eg && guarded.eg > blocked.eg, "{:?} {:?} {:?}", free, guarded, blocked);
+
+    // Only the front pawn of a doubled pair counts
+    let single = white("4k3/8/8/P7/8/8/8/4K3 w - - 0 1").passed;
+    let doubled = white("4k3/8/8/P7/P7/8/8/4K3 w - - 0 1").passed;
+    assert_eq!(single, doubled);
+}
+
+#[test]
+fn test_symmetric() {
+    let fen = "r1bqkb1r/pp3ppp/2n1pn2/2pp4/3P4/2P1PN2/PP1N1PPP/R1BQKB1R w KQkq - 0 1";
+    let mirrored = "r1bqkb1r/pp1n1ppp/2p1pn2/3p4/2PP4/2N1PN2/PP3PPP/R1BQKB1R b KQkq - 0 1";
+    let [w, b] = breakdown(&board(fen));
+    let [mw, mb] = breakdown(&board(mirrored));
+    assert_eq!(w, mb);
+    assert_eq!(b, mw);
+    let params = PawnParams::default();
+    assert_eq!(pawns::evaluate(&board(fen), &params), -pawns::evaluate(&board(mirrored), &params));
+}
+
+#[test]
+fn test_pawn_hash() {
+    let a = board("4k3/pp6/8/8/8/8/PP6/4K3 w - - 0 1");
+    // Same pawns, other pieces moved
+    let b = board("3k4/pp6/8/8/8/8/PP6/3K4 b - - 0 1");
+    assert_eq!(a.pawn_key(), b.pawn_key());
+    assert_eq!(a.pawn_key(), compute_pawn_key(&a));
+    assert_ne!(compute_hash(&a), compute_hash(&b));
+    assert_ne!(a.pawn_key(), board("4k3/pp6/8/8/8/P7/1P6/4K3 w - - 0 1").pawn_key());
+
+    clear_pawn_table();
+    let fresh = breakdown(&a);
+    let (probes, hits) = pawn_table_stats();
+    assert_eq!((probes, hits), (1, 0));
+
+    assert_eq!(breakdown(&a), fresh);
+    breakdown(&b);
+    assert_eq!(pawn_table_stats(), (3, 2));
+}
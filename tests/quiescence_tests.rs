@@ -0,0 +1,107 @@
+mod common;
+
+use common::KIWIPETE;
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::evaluate;
+use devi::search::minimax::{alphabeta_with_context, INFINITY};
+use devi::search::{search_shared, QuiescenceConfig, SearchConfig, SearchContext, SharedSearch};
+
+fn with_quiescence(quiescence: QuiescenceConfig) -> SharedSearch {
+    SharedSearch::new(SearchConfig { quiescence, ..SearchConfig::default() })
+}
+
+/// Static score of `fen` after `moves`, for the side to move in `fen`.
+fn score_after(fen: &str, moves: &[&str]) -> i32 {
+    let mut board = Board::from_fen(fen).unwrap();
+    for text in moves {
+        let mv = devi::cli::uci::parse_move(&mut board, text).unwrap();
+        board.make_move(&mv);
+    }
+    if moves.len().is_multiple_of(2) { evaluate(&board) } else { -evaluate(&board) }
+}
+
+/// Score of `fen` at depth 0 for the side to move.
+fn horizon_score(fen: &str, shared: &SharedSearch) -> i32 {
+    let mut board = Board::from_fen(fen).unwrap();
+    let mut ctx = SearchContext::new(shared);
+    let score = alphabeta_with_context(&mut board, 0, -INFINITY, INFINITY, &mut ctx);
+    ctx.finish();
+    score
+}
+
+#[test]
+fn test_hanging_queen_is_seen_at_the_horizon() {
+    // White just took d5 with the queen; the e6 pawn takes it back
+    let fen = "4k3/8/4p3/3Q4/8/8/8/4K3 b - - 0 1";
+    let board = Board::from_fen(fen).unwrap();
+
+    let static_score = horizon_score(fen, &with_quiescence(QuiescenceConfig::disabled()));
+    assert_eq!(static_score, evaluate(&board));
+
+    let shared = with_quiescence(QuiescenceConfig::default());
+    let score = horizon_score(fen, &shared);
+    assert_eq!(score, score_after(fen, &["e6d5"]), "queen should be lost");
+    assert!(score > static_score + 800);
+    assert!(shared.qnodes() > 1);
+}
+
+#[test]
+fn test_quiet_position_stands_pat() {
+    let fen = "4k3/8/4p3/8/8/8/3Q4/4K3 b - - 0 1";
+    let board = Board::from_fen(fen).unwrap();
+
+    let shared = with_quiescence(QuiescenceConfig::default());
+    assert_eq!(horizon_score(fen, &shared), evaluate(&board));
+    assert_eq!(shared.qnodes(), 1);
+}
+
+#[test]
+fn test_promotions_are_extended() {
+    // Black's pawn queens unless promotions are left to the static evaluation
+    let fen = "4k3/8/8/8/8/8/6p1/K7 b - - 0 1";
+
+    let promotions = horizon_score(fen, &with_quiescence(QuiescenceConfig::default()));
+    let captures_only = horizon_score(
+        fen,
+        &with_quiescence(QuiescenceConfig { promotions: false, ..QuiescenceConfig::default() }),
+    );
+    assert_eq!(captures_only, score_after(fen, &[]));
+    assert_eq!(promotions, score_after(fen, &["g2g1q"]));
+    assert!(promotions > captures_only + 500);
+}
+
+#[test]
+fn test_delta_pruning_saves_nodes() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+
+    let pruned = with_quiescence(QuiescenceConfig::default());
+    search_shared(&mut board.clone(), 3, &pruned);
+
+    let full = with_quiescence(QuiescenceConfig { delta_pruning: false, ..QuiescenceConfig::default() });
+    search_shared(&mut board.clone(), 3, &full);
+
+    let off = with_quiescence(QuiescenceConfig::disabled());
+    search_shared(&mut board.clone(), 3, &off);
+
+    assert_eq!(off.qnodes(), 0);
+    assert!(pruned.qnodes() > 0);
+    assert!(pruned.qnodes() <= full.qnodes(), "{} vs {}", pruned.qnodes(), full.qnodes());
+}
+
+#[test]
+fn test_see_pruning_saves_nodes() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+
+    let pruned = with_quiescence(QuiescenceConfig::default());
+    search_shared(&mut board.clone(), 3, &pruned);
+
+    let full = with_quiescence(QuiescenceConfig { see_pruning: false, ..QuiescenceConfig::default() });
+    search_shared(&mut board.clone(), 3, &full);
+
+    assert!(pruned.qnodes() > 0);
+    assert!(pruned.qnodes() < full.qnodes(), "{} vs {}", pruned.qnodes(), full.qnodes());
+
+    // A losing capture is still no reason to miss a winning one
+    let fen = "4k3/8/4p3/3Q4/8/8/8/4K3 b - - 0 1";
+    assert_eq!(horizon_score(fen, &pruned), horizon_score(fen, &full));
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::{exact_config, KIWIPETE, STARTPOS};
+use devi::board::{Board, BoardRepresentation};
+use devi::moves::generate_legal_moves;
+use devi::scheduling::CorePolicy;
+use devi::search::{
+    parallel_search_shared, search_shared, two_phase_search_shared, SearchConfig, SharedSearch, TwoPhaseConfig,
+};
+
+fn exact_config_with(root_bound: bool) -> SearchConfig {
+    SearchConfig {
+        root_bound,
+        ..exact_config()
+    }
+}
+
+#[test]
+fn test_root_split_matches_serial() {
+    for (fen, depth) in [(STARTPOS, 5), (KIWIPETE, 5)] {
+        let board = Board::from_fen(fen).unwrap();
+        let (_, serial) = search_shared(&mut board.clone(), depth, &SharedSearch::new(exact_config()));
+
+        for root_bound in [true, false] {
+            let shared = SharedSearch::new(exact_config_with(root_bound));
+            let (mv, score) = parallel_search_shared(&mut board.clone(), depth, CorePolicy::None, 4, 0.0, None, &shared);
+            assert_eq!(score, serial, "{} root bound {}", fen, root_bound);
+            assert!(generate_legal_moves(&mut board.clone(), board.to_move()).contains(&mv));
+        }
+    }
+}
+
+#[test]
+fn test_two_phase_matches_serial() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let (_, serial) = search_shared(&mut board.clone(), 4, &SharedSearch::new(exact_config()));
+
+    let shared = SharedSearch::new(exact_config());
+    let (_, score) = two_phase_search_shared(&mut board.clone(), 4, &TwoPhaseConfig::default(), &shared);
+    assert_eq!(score, serial);
+}
+
+#[test]
+fn test_root_bound_saves_nodes() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+
+    let bound = SharedSearch::new(SearchConfig::default());
+    parallel_search_shared(&mut board.clone(), 5, CorePolicy::None, 4, 0.0, None, &bound);
+    let full = SharedSearch::new(SearchConfig {
+        root_bound: false,
+        ..SearchConfig::default()
+    });
+    parallel_search_shared(&mut board.clone(), 5, CorePolicy::None, 4, 0.0, None, &full);
+
+    assert!(
+        bound.nodes() < full.nodes(),
+        "root bound {} full windows {}",
+        bound.nodes(),
+        full.nodes()
+    );
+}
@@ -0,0 +1,121 @@
+use devi::scheduling::{CoreSets, CoreSource, CpuSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Fresh directory standing in for `/sys/devices`.
+fn fake_sysfs(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("devi-sysfs-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("system/cpu")).unwrap();
+    root
+}
+
+fn write(root: &Path, file: &str, contents: &str) {
+    let path = root.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+fn set(list: &str) -> CpuSet {
+    list.parse().unwrap()
+}
+
+#[test]
+fn test_cpu_list_format() {
+    assert_eq!(set("0-3,8,10-11").cpus(), &[0, 1, 2, 3, 8, 10, 11]);
+    assert_eq!(set("3,1,2,2").cpus(), &[1, 2, 3]);
+    assert_eq!(set("0-3,8,10-11\n").to_string(), "0-3,8,10-11");
+    assert!(set("").is_empty());
+
+    assert!("4-2".parse::<CpuSet>().is_err());
+    assert!("0-x".parse::<CpuSet>().is_err());
+}
+
+#[test]
+fn test_hybrid_pmu() {
+    let root = fake_sysfs("hybrid");
+    write(&root, "system/cpu/online", "0-15\n");
+    write(&root, "cpu_core/cpus", "0-7\n");
+    write(&root, "cpu_atom/cpus", "8-15\n");
+
+    let sets = CoreSets::discover_in(&root);
+    assert_eq!(sets.source, CoreSource::HybridPmu);
+    assert_eq!(sets.p_cores, set("0-7"));
+    assert_eq!(sets.e_cores, set("8-15"));
+    assert_eq!(sets.fast(), Some(&set("0-7")));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_capacity_tiers() {
+    // Prime + big + little: the middle tier counts as fast
+    let root = fake_sysfs("capacity");
+    write(&root, "system/cpu/online", "0-7");
+    for (cpu, capacity) in [1024, 870, 870, 870, 380, 380, 380, 380].iter().enumerate() {
+        write(&root, &format!("system/cpu/cpu{}/cpu_capacity", cpu), &capacity.to_string());
+    }
+
+    let sets = CoreSets::discover_in(&root);
+    assert_eq!(sets.source, CoreSource::Capacity);
+    assert_eq!(sets.p_cores, set("0-3"));
+    assert_eq!(sets.e_cores, set("4-7"));
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_frequency_and_homogeneous() {
+    let root = fake_sysfs("freq");
+    for cpu in 0..4 {
+        let freq = if cpu < 2 { 3_200_000 } else { 2_000_000 };
+        write(&root, &format!("system/cpu/cpu{}/cpufreq/cpuinfo_max_freq", cpu), &freq.to_string());
+    }
+
+    // No `online` file: CPUs come from the cpuN directories
+    let sets = CoreSets::discover_in(&root);
+    assert_eq!(sets.source, CoreSource::Frequency);
+    assert_eq!(sets.p_cores, set("0-1"));
+    assert_eq!(sets.e_cores, set("2-3"));
+
+    for cpu in 2..4 {
+        write(&root, &format!("system/cpu/cpu{}/cpufreq/cpuinfo_max_freq", cpu), "3200000");
+    }
+    let sets = CoreSets::discover_in(&root);
+    assert_eq!(sets.source, CoreSource::Homogeneous);
+    assert_eq!(sets.p_cores, set("0-3"));
+    assert_eq!(sets.fast(), None);
+    assert_eq!(sets.efficient(), None);
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_explicit_sets() {
+    let sets = CoreSets::default().with_overrides(Some(set("0-7")), Some(set("8-9")));
+    assert_eq!(sets.source, CoreSource::Explicit);
+    assert_eq!(sets.fast(), Some(&set("0-7")));
+    assert_eq!(sets.efficient(), Some(&set("8-9")));
+
+    // Only P-cores given: fast workers get pinned, efficient ones don't
+    let sets = CoreSets::default().with_overrides(Some(set("2-3")), None);
+    assert_eq!(sets.fast(), Some(&set("2-3")));
+    assert_eq!(sets.efficient(), None);
+
+    let missing = CoreSets::discover_in(Path::new("/nonexistent"));
+    assert_eq!(missing.source, CoreSource::Unknown);
+    assert_eq!(missing.clone().with_overrides(None, None), missing);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pin_current_thread() {
+    use devi::scheduling::pin_current_thread;
+
+    // On its own thread so the other tests keep every CPU
+    std::thread::spawn(|| {
+        let cpu = unsafe { libc::sched_getcpu() };
+        assert!(cpu >= 0);
+        pin_current_thread(&CpuSet::new(vec![cpu as usize])).unwrap();
+        assert_eq!(unsafe { libc::sched_getcpu() }, cpu);
+    })
+    .join()
+    .unwrap();
+}
@@ -0,0 +1,71 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::see::en_prise;
+use devi::evaluation::{evaluate_traced, see};
+use devi::moves::generate_legal_moves;
+
+/// SEE of the legal move written `alg` in `fen`.
+fn see_of(fen: &str, alg: &str) -> i32 {
+    let mut board = Board::from_fen(fen).unwrap();
+    let side = board.to_move();
+    let mv = generate_legal_moves(&mut board, side)
+        .into_iter()
+        .find(|m| m.to_algebraic() == alg)
+        .unwrap_or_else(|| panic!("{} not legal in {}", alg, fen));
+    see(&board, mv)
+}
+
+#[test]
+fn test_known_exchanges() {
+    for (fen, mv, expected) in [
+        // Undefended pawn
+        ("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "e1e5", 100),
+        // Pawn takes a defended knight
+        ("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1", "d4e5", 220),
+        // Queen takes a pawn guarded by a pawn
+        ("4k3/8/3p4/4p3/8/8/4Q3/4K3 w - - 0 1", "e2e5", -800),
+        // Knight, rook and queen against knight, bishop and the queen behind it
+        ("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1", "d3e5", -220),
+        // Bishop for knight is even
+        ("4k3/8/5p2/4n3/8/2B5/8/4K3 w - - 0 1", "c3e5", -10),
+    ] {
+        assert_eq!(see_of(fen, mv), expected, "{} {}", fen, mv);
+    }
+}
+
+#[test]
+fn test_x_ray_attackers() {
+    // The second rook backs up the first through e2
+    assert_eq!(see_of("4r1k1/8/8/4p3/8/8/4R3/4R1K1 w - - 0 1", "e2e5"), 100);
+    assert_eq!(see_of("4r1k1/8/8/4p3/8/8/4R3/6K1 w - - 0 1", "e2e5"), -400);
+    // Queen behind bishop on the diagonal
+    assert_eq!(see_of("4k3/8/5p2/4p3/3B4/2Q5/8/4K3 w - - 0 1", "d4e5"), 100 - 330 + 100);
+}
+
+#[test]
+fn test_king_recaptures_only_undefended() {
+    assert_eq!(see_of("4k3/8/8/8/8/8/3r4/3RK3 b - - 0 1", "d2d1"), 0);
+    // The rook on d8 covers d1 through d2, so the king can't take back
+    assert_eq!(see_of("3rk3/8/8/8/8/8/3r4/3RK3 b - - 0 1", "d2d1"), 500);
+}
+
+#[test]
+fn test_special_moves() {
+    assert_eq!(see_of("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2", "e5d6"), 100);
+    assert_eq!(see_of("4k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q"), 800);
+    // Promoting under the rook just gives the pawn away
+    assert_eq!(see_of("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7b8q"), -100);
+    assert_eq!(see_of("r3k3/1P6/8/8/8/8/8/4K3 w - - 0 1", "b7a8q"), 1300);
+}
+
+#[test]
+fn test_en_prise_in_trace() {
+    // White's queen hangs to the pawn, Black's knight to the queen
+    let board = Board::from_fen("4k3/8/3p4/4Q3/8/2n5/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(en_prise(&board), [900, 320]);
+    assert_eq!(evaluate_traced(&board).en_prise, [900, 320]);
+
+    let mut start = Board::new();
+    start.setup_starting_position();
+    assert_eq!(evaluate_traced(&start).en_prise, [0, 0]);
+    assert!(evaluate_traced(&start).to_string().contains("En prise"));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::KIWIPETE;
+use devi::board::{Board, BoardRepresentation};
+use devi::search::minimax::{mate_distance, MATE_SCORE};
+use devi::search::selectivity::{has_non_pawn_material, lmr_reduction};
+use devi::search::{search_shared, SearchConfig, SelectivityConfig, SharedSearch};
+use devi::types::Color;
+
+// Kings and pawns only: a pass is no bound here
+const PAWN_ENDING: &str = "8/5k2/3p4/1p1Pp2p/pP2Pp1P/P4P1K/8/8 b - - 0 1";
+
+/// Best move, score and nodes of a `depth` search of `fen` with `selectivity`.
+fn search(fen: &str, depth: u32, selectivity: SelectivityConfig) -> (String, i32, u64) {
+    let mut board = Board::from_fen(fen).unwrap();
+    let shared = SharedSearch::new(SearchConfig { selectivity, ..SearchConfig::default() });
+    let (mv, score) = search_shared(&mut board, depth, &shared);
+    (mv.to_algebraic(), score, shared.nodes())
+}
+
+fn only(set: impl Fn(&mut SelectivityConfig)) -> SelectivityConfig {
+    let mut config = SelectivityConfig::default();
+    set(&mut config);
+    config
+}
+
+#[test]
+fn test_reduction_table() {
+    for depth in 3..20 {
+        assert_eq!(lmr_reduction(depth, 1), 0);
+        for move_number in 1..60 {
+            let reduction = lmr_reduction(depth, move_number);
+            // At least one ply is always left to search
+            assert!(reduction + 2 <= depth, "{} {}", depth, move_number);
+            assert!(reduction <= lmr_reduction(depth, move_number + 1));
+            assert!(reduction <= lmr_reduction(depth + 1, move_number));
+        }
+    }
+    assert!(lmr_reduction(8, 20) >= 2);
+}
+
+#[test]
+fn test_each_technique_saves_nodes() {
+    let (_, _, full) = search(KIWIPETE, 5, SelectivityConfig::default());
+    for config in [
+        only(|c| c.null_move = true),
+        only(|c| c.lmr = true),
+        only(|c| c.reverse_futility = true),
+        only(|c| c.futility = true),
+    ] {
+        let (_, _, nodes) = search(KIWIPETE, 5, config);
+        assert!(nodes < full, "{:?}: {} vs {}", config, nodes, full);
+    }
+    let (_, _, all) = search(KIWIPETE, 5, SelectivityConfig::all());
+    assert!(all < full / 2, "{} vs {}", all, full);
+}
+
+#[test]
+fn test_null_move_zugzwang_guard() {
+    let board = Board::from_fen(PAWN_ENDING).unwrap();
+    assert!(!has_non_pawn_material(&board, Color::White));
+    assert!(has_non_pawn_material(&Board::from_fen(KIWIPETE).unwrap(), Color::Black));
+
+    // Without pieces the null move is never tried, so nothing changes
+    let full = search(PAWN_ENDING, 6, SelectivityConfig::default());
+    let null_move = search(PAWN_ENDING, 6, only(|c| c.null_move = true));
+    assert_eq!(null_move, full);
+}
+
+#[test]
+fn test_check_extension_sees_mate_a_ply_early() {
+    let fen = "6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1";
+    let (_, plain, _) = search(fen, 1, SelectivityConfig::default());
+    assert_eq!(mate_distance(plain), None);
+
+    let (mv, extended, _) = search(fen, 1, only(|c| c.check_extensions = true));
+    assert_eq!((mv.as_str(), extended), ("a1a8", MATE_SCORE - 1));
+}
+
+#[test]
+fn test_selective_search_keeps_mates() {
+    for (fen, mating_move, n) in [
+        ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8", 1),
+        ("r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4", "h5f7", 1),
+        ("7k/8/8/8/8/8/R7/1R4K1 w - - 0 1", "b1b7", 2),
+    ] {
+        let (mv, score, _) = search(fen, 2 * n as u32, SelectivityConfig::all());
+        assert_eq!(mv, mating_move, "{}", fen);
+        assert_eq!(mate_distance(score), Some(n), "{}", fen);
+    }
+}
@@ -0,0 +1,140 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::moves::generate_legal_moves;
+use devi::scheduling::CorePolicy;
+use devi::search::probe::ClassificationConfig;
+use devi::search::{
+    parallel_search_timed, search_shared, search_timed, two_phase_search_timed, SearchConfig, SharedSearch,
+    TimeControl, TimeManager, TwoPhaseConfig,
+};
+use devi::types::*;
+use std::time::{Duration, Instant};
+
+fn start_position() -> Board {
+    let mut board = Board::new();
+    board.setup_starting_position();
+    board
+}
+
+fn is_legal(board: &Board, mv: Move) -> bool {
+    let mut b = *board;
+    let side = b.to_move();
+    generate_legal_moves(&mut b, side).contains(&mv)
+}
+
+fn movetime_search(ms: u64) -> SharedSearch {
+    SharedSearch::new(SearchConfig::default()).with_time(TimeManager::new(&TimeControl::movetime(ms), Color::White))
+}
+
+#[test]
+fn test_clock_budget() {
+    let control = TimeControl {
+        wtime: Some(60_000),
+        btime: Some(1_000),
+        winc: Some(1_000),
+        ..TimeControl::default()
+    };
+
+    // 60s over 30 moves plus most of the increment
+    let white = TimeManager::new(&control, Color::White);
+    let soft = white.soft_limit().unwrap();
+    let hard = white.hard_limit().unwrap();
+    assert!(soft > Duration::from_millis(2_000) && soft < Duration::from_millis(3_000), "{:?}", soft);
+    assert!(hard > soft && hard <= Duration::from_millis(30_000));
+
+    // Never plan on more than half of a short clock
+    let black = TimeManager::new(&control, Color::Black);
+    assert!(black.hard_limit().unwrap() <= Duration::from_millis(500));
+
+    let sudden = TimeControl { movestogo: Some(1), ..control };
+    let last_move = TimeManager::new(&sudden, Color::White);
+    assert!(last_move.soft_limit().unwrap() < Duration::from_millis(60_000));
+}
+
+#[test]
+fn test_movetime_and_depth_limits() {
+    let fixed = TimeManager::new(&TimeControl::movetime(500), Color::Black);
+    assert_eq!(fixed.soft_limit(), fixed.hard_limit());
+    assert!(fixed.hard_limit().unwrap() < Duration::from_millis(500));
+
+    let depth = TimeManager::fixed_depth(3);
+    assert_eq!(depth.max_depth(), 3);
+    assert_eq!(depth.hard_limit(), None);
+    assert!(!depth.hard_limit_reached());
+}
+
+#[test]
+fn test_timed_search_respects_movetime() {
+    let board = start_position();
+    let shared = movetime_search(200);
+
+    let start = Instant::now();
+    let (best_move, _) = search_timed(&mut board.clone(), &shared);
+    let elapsed = start.elapsed();
+
+    assert!(is_legal(&board, best_move));
+    assert!(elapsed < Duration::from_millis(1_000), "took {:?}", elapsed);
+}
+
+#[test]
+fn test_parallel_and_two_phase_respect_movetime() {
+    let board = start_position();
+
+    let shared = movetime_search(200);
+    let start = Instant::now();
+    let (best_move, _) = parallel_search_timed(&mut board.clone(), CorePolicy::None, 4, 0.0, &shared);
+    assert!(is_legal(&board, best_move));
+    assert!(start.elapsed() < Duration::from_millis(1_000), "took {:?}", start.elapsed());
+
+    let config = TwoPhaseConfig {
+        probe_depth: 1,
+        p_core_threads: 2,
+        e_core_threads: 2,
+        classification: ClassificationConfig::default(),
+        search: SearchConfig::default(),
+    };
+    let shared = movetime_search(200);
+    let start = Instant::now();
+    let (best_move, _) = two_phase_search_timed(&mut board.clone(), &config, &shared);
+    assert!(is_legal(&board, best_move));
+    assert!(start.elapsed() < Duration::from_millis(1_000), "took {:?}", start.elapsed());
+}
+
+#[test]
+fn test_depth_limited_timed_search_matches_fixed_depth() {
+    let board = start_position();
+
+    let fixed = SharedSearch::new(SearchConfig::default());
+    let (_, fixed_score) = search_shared(&mut board.clone(), 4, &fixed);
+
+    let timed = SharedSearch::new(SearchConfig::default()).with_time(TimeManager::fixed_depth(4));
+    let (best_move, timed_score) = search_timed(&mut board.clone(), &timed);
+
+    assert!(is_legal(&board, best_move));
+    assert_eq!(fixed_score, timed_score);
+}
+
+#[test]
+fn test_stop_flag_aborts_search() {
+    let board = start_position();
+    let shared = SharedSearch::new(SearchConfig::default());
+    shared.stop();
+
+    // Stopped before the first iteration, but there is still a move to play
+    let start = Instant::now();
+    let (best_move, _) = search_timed(&mut board.clone(), &shared);
+    assert!(is_legal(&board, best_move));
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // Stopped from another thread mid-search
+    let shared = SharedSearch::new(SearchConfig::default());
+    let handle = shared.stop_handle();
+    let stopper = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(100));
+        handle.store(true, std::sync::atomic::Ordering::Relaxed);
+    });
+    let start = Instant::now();
+    let (best_move, _) = search_timed(&mut board.clone(), &shared);
+    stopper.join().unwrap();
+    assert!(is_legal(&board, best_move));
+    assert!(start.elapsed() < Duration::from_secs(2), "took {:?}", start.elapsed());
+}
@@ -0,0 +1,66 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::evaluation::endgame::SCALE_NORMAL;
+use devi::evaluation::{evaluate, evaluate_traced, evaluate_traced_with, evaluate_with, EvalParams, Score, SideTrace};
+
+const POSITIONS: [&str; 5] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r1bq1rk1/ppp2ppp/2n2n2/3pp3/1b1PP3/2N2N2/PPP1BPPP/R1BQ1RK1 b - - 0 1",
+    "6k1/5ppp/8/8/8/5n2/5PPq/6K1 w - - 0 1",
+    "4k1b1/5p2/8/8/8/8/4PP2/2B1K3 b - - 0 1", // opposite-coloured bishops
+    "4k3/8/4K3/4P3/8/8/8/8 w - - 0 1",       // KPK
+];
+
+fn board(fen: &str) -> Board {
+    Board::from_fen(fen).unwrap()
+}
+
+#[test]
+fn test_trace_matches_evaluate() {
+    for fen in POSITIONS {
+        let board = board(fen);
+        assert_eq!(evaluate_traced(&board).score, evaluate(&board), "{}", fen);
+        let none = EvalParams::none();
+        assert_eq!(evaluate_traced_with(&board, &none).score, evaluate_with(&board, &none), "{}", fen);
+    }
+}
+
+#[test]
+fn test_terms_add_up() {
+    for fen in &POSITIONS[..4] {
+        let trace = evaluate_traced(&board(fen));
+        let [white, black] = trace.sides;
+        assert_eq!(trace.endgame, None);
+        let positional = white.positional() - black.positional();
+        assert_eq!(trace.unscaled, white.material - black.material + positional.taper(trace.phase));
+        assert_eq!(trace.white_score, trace.unscaled * trace.scale / SCALE_NORMAL);
+    }
+    assert_eq!(evaluate_traced(&board(POSITIONS[3])).scale, SCALE_NORMAL / 2);
+
+    // Switched-off terms show up as zero
+    let trace = evaluate_traced_with(&board(POSITIONS[2]), &EvalParams::none());
+    for side in trace.sides {
+        assert_eq!(side.king_safety.total(), Score::ZERO);
+        assert_eq!(side.mobility, Score::ZERO);
+    }
+}
+
+#[test]
+fn test_recognised_ending() {
+    let trace = evaluate_traced(&board(POSITIONS[4]));
+    assert_eq!(trace.endgame, Some(trace.white_score));
+    assert_eq!(trace.sides, [SideTrace::default(); 2]);
+}
+
+#[test]
+fn test_output_formats() {
+    let trace = evaluate_traced(&board(POSITIONS[1]));
+    let table = trace.to_string();
+    for row in ["Material", "Piece-square", "Passed pawns", "King attack", "Mobility", "Scale"] {
+        assert!(table.contains(row), "{} missing from\n{}", row, table);
+    }
+
+    let json: serde_json::Value = serde_json::from_str(&serde_json::to_string(&trace).unwrap()).unwrap();
+    assert_eq!(json["score"], trace.score);
+    assert_eq!(json["sides"][1]["mobility"]["mg"], trace.sides[1].mobility.mg);
+    assert!(json["endgame"].is_null());
+}
@@ -0,0 +1,103 @@
+use devi::board::{Board, BoardRepresentation};
+use devi::scheduling::CorePolicy;
+use devi::moves::generate_legal_moves;
+use devi::search::transposition::{Bound, Probe, TranspositionTable};
+use devi::search::{minimax::alphabeta, parallel_search_shared, search_shared, SearchConfig, SharedSearch};
+use devi::types::*;
+
+fn e2e4() -> Move {
+    Move::new(Square(12), Square(28), None, None)
+}
+
+#[test]
+fn test_store_and_probe_roundtrip() {
+    let tt = TranspositionTable::new(1);
+    let promo = Move::new(Square(52), Square(60), Some(SpecialMove::Promotion), Some(PieceType::Knight));
+
+    tt.store(0xDEAD_BEEF, 5, Bound::Lower, -1234, Some(promo));
+    match tt.probe(0xDEAD_BEEF) {
+        Probe::Hit(entry) => {
+            assert_eq!(entry.depth, 5);
+            assert_eq!(entry.bound, Bound::Lower);
+            assert_eq!(entry.score, -1234);
+            assert_eq!(entry.best_move, Some(promo));
+        }
+        other => panic!("expected hit, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_probe_reports_collision() {
+    // A single slot forces every key onto the same entry
+    let tt = TranspositionTable::new(0);
+    assert_eq!(tt.capacity(), 1);
+
+    assert_eq!(tt.probe(1), Probe::Miss);
+    tt.store(1, 3, Bound::Exact, 10, None);
+    assert_eq!(tt.probe(2), Probe::Collision);
+}
+
+#[test]
+fn test_replacement_keeps_deeper_bound_and_best_move() {
+    let tt = TranspositionTable::new(1);
+    tt.store(42, 6, Bound::Exact, 50, Some(e2e4()));
+
+    // Shallower bound doesn't overwrite
+    tt.store(42, 2, Bound::Upper, -10, None);
+    let Probe::Hit(entry) = tt.probe(42) else { panic!("lost entry") };
+    assert_eq!((entry.depth, entry.score), (6, 50));
+
+    // Exact result replaces but keeps the previous move when it has none
+    tt.store(42, 3, Bound::Exact, 7, None);
+    let Probe::Hit(entry) = tt.probe(42) else { panic!("lost entry") };
+    assert_eq!((entry.depth, entry.score), (3, 7));
+    assert_eq!(entry.best_move, Some(e2e4()));
+}
+
+#[test]
+fn test_tt_search_matches_plain_alphabeta() {
+    let mut board = Board::from_fen(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    )
+    .unwrap();
+
+    let side = board.to_move();
+    let moves = generate_legal_moves(&mut board, side);
+    let plain = moves
+        .iter()
+        .map(|mv| {
+            let mut b = board;
+            b.make_move(mv);
+            -alphabeta(&mut b, 3, -i32::MAX, i32::MAX)
+        })
+        .max()
+        .unwrap();
+
+    let shared = SharedSearch::new(SearchConfig::default());
+    // Deep enough for move-order transpositions under the root
+    let (_, score) = search_shared(&mut board, 4, &shared);
+    assert_eq!(score, plain);
+
+    let stats = shared.tt_stats();
+    assert!(stats.hits > 0, "no transpositions found: {:?}", stats);
+    assert_eq!(stats.probes, stats.hits + stats.misses);
+}
+
+#[test]
+fn test_shared_tt_parallel_matches_serial() {
+    let mut board = Board::new();
+    board.setup_starting_position();
+
+    let serial = SharedSearch::new(SearchConfig::default());
+    let (_, serial_score) = search_shared(&mut board, 4, &serial);
+
+    // Ties may resolve to a different move, but the score must not depend on thread timing.
+    // A small table so the hashfull sample sees some entries
+    let shared = SharedSearch::new(SearchConfig { tt_size_mb: 1, ..SearchConfig::default() });
+    let (_, score) =
+        parallel_search_shared(&mut board, 4, CorePolicy::None, 4, 0.0, None, &shared);
+
+    assert_eq!(serial_score, score);
+    assert!(shared.tt_stats().stores > 0);
+    assert!(shared.tt.hashfull() > 0);
+}
@@ -0,0 +1,95 @@
+use devi::board::BoardRepresentation;
+use devi::evaluation::EvalParams;
+use devi::tuning::dataset::{parse_csv_line, parse_epd_line};
+use devi::tuning::{fit_k, load_positions, mean_error, quiet_positions, tune, LabelledPosition, TunerConfig};
+
+// White is a knight up in each of these
+const KNIGHT_UP: [&str; 3] = [
+    "4k3/pppp4/8/8/8/8/PPPP4/1N2K3 w - - 0 1",
+    "4k3/pppp4/8/8/8/2N5/PPPP4/4K3 b - - 0 1",
+    "4k3/pppp4/8/8/3N4/8/PPPP4/4K3 w - - 0 1",
+];
+
+fn labelled(fen: &str, result: f64) -> LabelledPosition {
+    parse_csv_line(&format!("{},{}", fen, result)).unwrap()
+}
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("devi-{}-{}", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn test_parse_results() {
+    let fen = "4k3/8/8/8/8/8/8/4K2R w K -";
+    for (line, result) in [
+        (format!("{} c9 \"1-0\";", fen), 1.0),
+        (format!("{} 0 1 c9 \"1/2-1/2\";", fen), 0.5),
+        (format!("{} [0.0]", fen), 0.0),
+        (format!("{} 12 40 [0.5]", fen), 0.5),
+        (format!("{} 0-1", fen), 0.0),
+    ] {
+        let position = parse_epd_line(&line).unwrap();
+        assert_eq!(position.result, result, "{}", line);
+        assert_eq!(position.board.to_fen().split(' ').next(), Some("4k3/8/8/8/8/8/8/4K2R"));
+    }
+    // Move clocks alone are not a result
+    assert!(parse_epd_line(&format!("{} 0 1", fen)).is_err());
+
+    assert_eq!(parse_csv_line(&format!("{} 0 1,1/2-1/2", fen)).unwrap().result, 0.5);
+    assert_eq!(parse_csv_line(&format!("\"{} 0 1\",0", fen)).unwrap().result, 0.0);
+    assert!(parse_csv_line(&format!("{},2", fen)).is_err());
+}
+
+#[test]
+fn test_load_files() {
+    let epd = temp_path("positions.epd");
+    std::fs::write(&epd, format!("# comment\n{} c9 \"1-0\";\n\n{} [0.5]\n", KNIGHT_UP[0], KNIGHT_UP[1])).unwrap();
+    let positions = load_positions(&epd).unwrap();
+    assert_eq!(positions.iter().map(|p| p.result).collect::<Vec<_>>(), vec![1.0, 0.5]);
+
+    let csv = temp_path("positions.csv");
+    std::fs::write(&csv, format!("fen,result\n{},1-0\n{},bad\n", KNIGHT_UP[0], KNIGHT_UP[1])).unwrap();
+    let error = load_positions(&csv).unwrap_err();
+    assert!(error.contains(":3:"), "{}", error);
+
+    std::fs::remove_file(epd).unwrap();
+    std::fs::remove_file(csv).unwrap();
+}
+
+#[test]
+fn test_error_and_quiescence() {
+    let params = EvalParams::default();
+    let wins: Vec<_> = KNIGHT_UP.iter().map(|fen| labelled(fen, 1.0)).collect();
+    let losses: Vec<_> = KNIGHT_UP.iter().map(|fen| labelled(fen, 0.0)).collect();
+    let k = fit_k(&wins, &params);
+    assert!(k > 0.0);
+    assert!(mean_error(&wins, &params, k) < mean_error(&losses, &params, k));
+
+    // The hanging rook is taken before the position is scored
+    let hanging = [labelled("4k3/8/8/8/8/8/3r4/4K3 w - - 0 1", 0.5)];
+    let quiet = quiet_positions(&hanging, &params);
+    assert!(quiet[0].board.to_fen().starts_with("4k3/8/8/8/8/8/3K4/8 b"), "{}", quiet[0].board.to_fen());
+    assert_eq!(quiet[0].result, 0.5);
+}
+
+#[test]
+fn test_tune_reduces_error() {
+    // Knight-up positions that were only drawn: the tuner should cool on knights
+    let positions: Vec<_> = KNIGHT_UP.iter().map(|fen| labelled(fen, 0.5)).collect();
+    let initial = EvalParams::default();
+    let config = TunerConfig { passes: 1, step: 5, k: Some(1.0) };
+    let mut reported = Vec::new();
+    let result = tune(&positions, &initial, &config, |pass, error| reported.push((pass, error)));
+
+    assert!(result.final_error < result.initial_error);
+    assert_eq!(reported, vec![(1, result.final_error)]);
+    assert!(result.params.piece_values[1] < initial.piece_values[1]);
+
+    let path = temp_path("params.json");
+    result.params.save(&path).unwrap();
+    assert_eq!(EvalParams::load(&path).unwrap(), result.params);
+    std::fs::remove_file(path).unwrap();
+}
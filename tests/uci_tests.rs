@@ -0,0 +1,199 @@
+use devi::board::BoardRepresentation;
+use devi::cli::uci::{format_score, parse_go, parse_position, UciEngine};
+use devi::evaluation::EvalParams;
+use devi::search::minimax::{mated_in, MATE_SCORE};
+use devi::search::{ParallelMode, TimeControl};
+use devi::types::*;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// Writer the test can read back after the engine has written to it.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedBuffer {
+    fn lines(&self) -> Vec<String> {
+        let bytes = self.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap().lines().map(str::to_string).collect()
+    }
+}
+
+fn engine() -> (UciEngine, SharedBuffer) {
+    let buffer = SharedBuffer::default();
+    (UciEngine::new(Box::new(buffer.clone())), buffer)
+}
+
+#[test]
+fn test_parse_position() {
+    let board = parse_position("startpos moves e2e4 e7e5 g1f3").unwrap();
+    assert_eq!(board.to_move(), Color::Black);
+    assert_eq!(
+        board.get_piece(Square(21)),
+        Some(Piece::new(PieceType::Knight, Color::White))
+    );
+
+    let board = parse_position("fen 4k3/P7/8/8/8/8/8/4K3 w - - 0 1 moves a7a8q").unwrap();
+    assert_eq!(
+        board.get_piece(Square(56)),
+        Some(Piece::new(PieceType::Queen, Color::White))
+    );
+
+    assert!(parse_position("startpos moves e2e5").is_err());
+    assert!(parse_position("somewhere").is_err());
+}
+
+#[test]
+fn test_parse_go() {
+    let go = parse_go("wtime 60000 btime 59000 winc 1000 binc 1000 movestogo 20").unwrap();
+    assert_eq!(
+        go.control,
+        TimeControl {
+            wtime: Some(60_000),
+            btime: Some(59_000),
+            winc: Some(1_000),
+            binc: Some(1_000),
+            movestogo: Some(20),
+            ..TimeControl::default()
+        }
+    );
+    assert!(!go.infinite);
+
+    assert_eq!(parse_go("depth 5").unwrap().control, TimeControl::depth(5));
+    assert_eq!(parse_go("nodes 10000").unwrap().control.nodes, Some(10_000));
+    assert!(parse_go("infinite").unwrap().infinite);
+    assert_eq!(parse_go("wtime -20").unwrap().control.wtime, Some(0));
+    assert!(parse_go("movetime").is_err());
+}
+
+#[test]
+fn test_format_score() {
+    assert_eq!(format_score(35), "cp 35");
+    assert_eq!(format_score(-120), "cp -120");
+    assert_eq!(format_score(MATE_SCORE - 3), "mate 2");
+    assert_eq!(format_score(mated_in(2)), "mate -1");
+}
+
+#[test]
+fn test_handshake_and_options() {
+    let (mut engine, out) = engine();
+    assert!(engine.handle("uci"));
+    assert!(engine.handle("isready"));
+    assert!(engine.handle("setoption name Threads value 4"));
+    assert!(engine.handle("setoption name CorePolicy value mixed"));
+    assert!(engine.handle("setoption name TwoPhase value true"));
+    assert!(engine.handle("setoption name Hash value 8"));
+    assert!(engine.handle("setoption name Selective value false"));
+
+    let lines = out.lines();
+    assert!(lines[0].starts_with("id name devi"));
+    assert!(lines.iter().any(|l| l.starts_with("option name Threads")));
+    assert_eq!(lines.iter().filter(|l| *l == "uciok").count(), 1);
+    assert_eq!(lines.last().unwrap(), "readyok");
+
+    let options = engine.options();
+    assert_eq!(options.threads, 4);
+    assert_eq!(options.hash_mb, 8);
+    assert!(options.two_phase);
+    assert!(!options.selective);
+
+    assert!(!engine.handle("quit"));
+}
+
+#[test]
+fn test_parallel_mode_option() {
+    let (mut engine, out) = engine();
+    assert!(engine.handle("setoption name ParallelMode value lazy-smp"));
+    assert!(engine.handle("setoption name Threads value 2"));
+    assert_eq!(engine.options().parallel_mode, ParallelMode::LazySmp);
+
+    engine.handle("position fen 6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    engine.handle("go depth 3");
+    engine.wait();
+    assert!(out.lines().last().unwrap().starts_with("bestmove a1a8"));
+
+    engine.handle("setoption name ParallelMode value sideways");
+    assert!(out.lines().last().unwrap().starts_with("info string bad ParallelMode"));
+    assert_eq!(engine.options().parallel_mode, ParallelMode::LazySmp);
+}
+
+#[test]
+fn test_go_depth_reports_bestmove() {
+    let (mut engine, out) = engine();
+    engine.handle("ucinewgame");
+    engine.handle("position startpos moves e2e4");
+    engine.handle("go depth 3");
+    engine.wait();
+
+    let lines = out.lines();
+    let infos: Vec<&String> = lines.iter().filter(|l| l.starts_with("info depth")).collect();
+    assert_eq!(infos.len(), 3);
+    assert!(infos[2].contains(" score cp ") && infos[2].contains(" pv "), "{}", infos[2]);
+
+    let bestmove = lines.last().unwrap();
+    let mv = bestmove.strip_prefix("bestmove ").unwrap().split(' ').next().unwrap();
+    let mut board = *engine.board();
+    assert!(devi::cli::uci::parse_move(&mut board, mv).is_some(), "{}", bestmove);
+}
+
+#[test]
+fn test_infinite_search_waits_for_stop() {
+    let (mut engine, out) = engine();
+    engine.handle("position startpos");
+    engine.handle("go infinite");
+    thread::sleep(Duration::from_millis(200));
+    assert!(!out.lines().iter().any(|l| l.starts_with("bestmove")));
+
+    engine.handle("stop");
+    let lines = out.lines();
+    assert!(lines.last().unwrap().starts_with("bestmove "), "{:?}", lines);
+}
+
+#[test]
+fn test_mate_score_and_no_moves() {
+    let (mut engine, out) = engine();
+    engine.handle("position fen 6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1");
+    engine.handle("go depth 2");
+    engine.wait();
+    let lines = out.lines();
+    assert!(lines.iter().any(|l| l.contains("score mate 1")), "{:?}", lines);
+    assert!(lines.last().unwrap().starts_with("bestmove a1a8"));
+
+    // Checkmated side has nothing to play
+    engine.handle("position fen R5k1/5ppp/8/8/8/8/5PPP/6K1 b - - 0 1");
+    engine.handle("go depth 2");
+    engine.wait();
+    assert_eq!(out.lines().last().unwrap(), "bestmove 0000");
+}
+
+#[test]
+fn test_eval_params_option() {
+    let path = std::env::temp_dir().join(format!("devi-{}-uci-params.json", std::process::id()));
+    let path = path.to_string_lossy().into_owned();
+    let mut params = EvalParams::default();
+    params.piece_values[1] += 25;
+    params.save(&path).unwrap();
+
+    let (mut engine, out) = engine();
+    engine.handle(&format!("setoption name EvalParams value {}", path));
+    assert_eq!(engine.options().eval, params);
+    engine.handle("setoption name EvalParams value <empty>");
+    assert_eq!(engine.options().eval, EvalParams::default());
+
+    engine.handle("setoption name EvalParams value /nonexistent/params.json");
+    assert!(out.lines().last().unwrap().starts_with("info string"));
+    assert_eq!(engine.options().eval, EvalParams::default());
+    std::fs::remove_file(path).unwrap();
+}
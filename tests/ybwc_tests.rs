@@ -0,0 +1,71 @@
+mod common;
+
+use common::{assert_pv_playable, exact_config, KIWIPETE, STARTPOS};
+use devi::board::{Board, BoardRepresentation};
+use devi::moves::generate_legal_moves;
+use devi::scheduling::CorePolicy;
+use devi::search::{
+    parallel_search_shared, search_shared, ybwc_iterative_search, ybwc_search_shared, SearchConfig, SharedSearch,
+    TimeManager,
+};
+
+#[test]
+fn test_ybwc_matches_serial() {
+    for (fen, depth) in [(STARTPOS, 5), (KIWIPETE, 5)] {
+        let board = Board::from_fen(fen).unwrap();
+        let (_, serial) = search_shared(&mut board.clone(), depth, &SharedSearch::new(exact_config()));
+
+        // Ties may resolve to a different move, but the score must not depend on thread timing
+        for _ in 0..3 {
+            let shared = SharedSearch::new(exact_config());
+            let (mv, score) = ybwc_search_shared(&mut board.clone(), depth, CorePolicy::None, 4, 0.0, &shared);
+            assert_eq!(score, serial, "{}", fen);
+            assert!(generate_legal_moves(&mut board.clone(), board.to_move()).contains(&mv));
+        }
+    }
+}
+
+#[test]
+fn test_bound_saves_nodes_over_root_split() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+
+    let ybwc = SharedSearch::new(SearchConfig::default());
+    ybwc_search_shared(&mut board.clone(), 5, CorePolicy::None, 4, 0.0, &ybwc);
+    // Without the shared root bound every root move gets a full window
+    let root_split = SharedSearch::new(SearchConfig {
+        root_bound: false,
+        ..SearchConfig::default()
+    });
+    parallel_search_shared(&mut board.clone(), 5, CorePolicy::None, 4, 0.0, None, &root_split);
+
+    assert!(
+        2 * ybwc.nodes() < root_split.nodes(),
+        "ybwc {} root split {}",
+        ybwc.nodes(),
+        root_split.nodes()
+    );
+}
+
+#[test]
+fn test_iterative_ybwc() {
+    let board = Board::from_fen(KIWIPETE).unwrap();
+    let shared = SharedSearch::new(SearchConfig::default()).with_time(TimeManager::fixed_depth(5));
+    let result = ybwc_iterative_search(&mut board.clone(), CorePolicy::None, 4, 0.0, &shared);
+
+    let depths: Vec<u32> = result.iterations.iter().map(|i| i.depth).collect();
+    assert_eq!(depths, vec![1, 2, 3, 4, 5]);
+    assert_eq!(result.pv[0], result.best_move);
+    assert_eq!(result.nodes, shared.nodes());
+    assert_pv_playable(&board, &result.pv);
+}
+
+#[test]
+fn test_stopped_ybwc_returns_first_move() {
+    let mut board = Board::from_fen(KIWIPETE).unwrap();
+    let shared = SharedSearch::new(SearchConfig::default());
+    shared.stop();
+
+    let (mv, score) = ybwc_search_shared(&mut board, 5, CorePolicy::None, 4, 0.0, &shared);
+    assert_eq!(score, 0);
+    assert!(generate_legal_moves(&mut board.clone(), board.to_move()).contains(&mv));
+}
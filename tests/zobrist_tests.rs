@@ -0,0 +1,86 @@
+use devi::board::zobrist::compute_hash;
+use devi::board::{Board, BoardRepresentation};
+use devi::moves::generate_legal_moves;
+use devi::types::*;
+
+fn play(board: &mut Board, moves: &[&str]) {
+    for alg in moves {
+        let side = board.to_move();
+        let mv = generate_legal_moves(board, side)
+            .into_iter()
+            .find(|m| m.to_algebraic() == *alg)
+            .unwrap_or_else(|| panic!("illegal move {}", alg));
+        board.make_move(&mv);
+    }
+}
+
+#[test]
+fn test_hash_matches_fen_setup() {
+    let mut board = Board::new();
+    board.setup_starting_position();
+    let from_fen = Board::from_fen(&board.to_fen()).unwrap();
+
+    assert_eq!(board.hash(), compute_hash(&board));
+    assert_eq!(board.hash(), from_fen.hash());
+}
+
+#[test]
+fn test_transpositions_share_hash() {
+    let mut a = Board::new();
+    a.setup_starting_position();
+    play(&mut a, &["g1f3", "g8f6", "b1c3", "b8c6"]);
+
+    let mut b = Board::new();
+    b.setup_starting_position();
+    play(&mut b, &["b1c3", "b8c6", "g1f3", "g8f6"]);
+
+    assert_eq!(a.hash(), b.hash());
+}
+
+#[test]
+fn test_hash_distinguishes_state() {
+    let white = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    let black = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1").unwrap();
+    let no_castle = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1").unwrap();
+    assert_ne!(white.hash(), black.hash());
+    assert_ne!(white.hash(), no_castle.hash());
+
+    let ep = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+    let no_ep = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_ne!(ep.hash(), no_ep.hash());
+}
+
+#[test]
+fn test_make_unmake_restores_hash() {
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "8/2p5/3p4/KP5r/1R2Pp1k/8/6P1/8 b - e3 0 1",
+    ];
+
+    for fen in fens {
+        let mut board = Board::from_fen(fen).unwrap();
+        let original = board.hash();
+
+        let side = board.to_move();
+        for mv in generate_legal_moves(&mut board, side) {
+            let undo = board.make_move(&mv);
+            assert_eq!(board.hash(), compute_hash(&board), "after {} in {}", mv, fen);
+            board.unmake_move(&mv, undo);
+            assert_eq!(board.hash(), original, "undoing {} in {}", mv, fen);
+        }
+    }
+}
+
+#[test]
+fn test_set_piece_updates_hash() {
+    let mut board = Board::new();
+    let empty = board.hash();
+
+    board.set_piece(Square(28), Some(Piece::new(PieceType::Knight, Color::White)));
+    assert_ne!(board.hash(), empty);
+    assert_eq!(board.hash(), compute_hash(&board));
+
+    board.set_piece(Square(28), None);
+    assert_eq!(board.hash(), empty);
+}